@@ -81,7 +81,7 @@ impl RpcProvider for TestRpcProvider {
 		LOCALNET.to_string()
 	}
 
-	async fn send(&self, method: &'static str, request: Value) -> ClientResult<Value> {
+	async fn send(&self, method: &str, request: Value) -> ClientResult<Value> {
 		let future = async move {
 			let context = {
 				Context {
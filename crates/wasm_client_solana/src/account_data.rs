@@ -0,0 +1,164 @@
+//! Decode account data with the serialization format used by its program.
+//!
+//! Native programs serialize their accounts with bincode, while most SPL and
+//! anchor programs use borsh. [`decode_account_as`] is a single entry point
+//! for both.
+
+use std::any::type_name;
+
+use borsh::BorshDeserialize;
+use serde::de::DeserializeOwned;
+
+use crate::ClientError;
+use crate::ClientResult;
+
+/// The serialization format of account data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AccountDataFormat {
+	/// Used by native programs, such as the system, stake and vote programs.
+	Bincode,
+	/// Used by most SPL and anchor programs. The first `skip` bytes, such as a
+	/// discriminator, are ignored.
+	Borsh { skip: usize },
+}
+
+impl AccountDataFormat {
+	/// The format of anchor accounts, which are prefixed with an 8 byte
+	/// discriminator.
+	pub const ANCHOR: Self = Self::Borsh { skip: 8 };
+}
+
+/// Marks types which are decoded with [`AccountDataFormat::Bincode`].
+pub struct BincodeMarker;
+
+/// Marks types which are decoded with [`AccountDataFormat::Borsh`].
+pub struct BorshMarker;
+
+/// Types which can be decoded from account data.
+///
+/// This is implemented for every type which implements either
+/// [`DeserializeOwned`] or [`BorshDeserialize`]. The `Marker` is inferred and
+/// only needs to be named for types which implement both.
+pub trait DecodeAccountData<Marker>: Sized {
+	fn decode_account_data(data: &[u8], format: AccountDataFormat) -> ClientResult<Self>;
+}
+
+impl<T: DeserializeOwned> DecodeAccountData<BincodeMarker> for T {
+	fn decode_account_data(data: &[u8], format: AccountDataFormat) -> ClientResult<Self> {
+		if format != AccountDataFormat::Bincode {
+			return Err(unsupported_format::<T>(format));
+		}
+
+		bincode::deserialize(data).map_err(|error| {
+			ClientError::Other(format!(
+				"Account data could not be decoded as {}: {error}",
+				type_name::<T>()
+			))
+		})
+	}
+}
+
+impl<T: BorshDeserialize> DecodeAccountData<BorshMarker> for T {
+	fn decode_account_data(data: &[u8], format: AccountDataFormat) -> ClientResult<Self> {
+		let AccountDataFormat::Borsh { skip } = format else {
+			return Err(unsupported_format::<T>(format));
+		};
+		let mut data = data.get(skip..).ok_or_else(|| {
+			ClientError::Other(format!(
+				"Account data is shorter than the {skip} bytes which should be skipped"
+			))
+		})?;
+
+		// accounts are often allocated with more space than they use, so
+		// trailing bytes are allowed
+		T::deserialize(&mut data).map_err(|error| {
+			ClientError::Other(format!(
+				"Account data could not be decoded as {}: {error}",
+				type_name::<T>()
+			))
+		})
+	}
+}
+
+fn unsupported_format<T>(format: AccountDataFormat) -> ClientError {
+	ClientError::Other(format!(
+		"{} can't be decoded from {format:?} account data",
+		type_name::<T>()
+	))
+}
+
+/// Decode the account `data` as `T` with the serialization `format` of the
+/// program which owns the account.
+///
+/// ```rust
+/// use solana_nonce::versions::Versions;
+/// use wasm_client_solana::account_data::AccountDataFormat;
+/// use wasm_client_solana::account_data::decode_account_as;
+///
+/// # fn run(data: &[u8]) -> wasm_client_solana::ClientResult<()> {
+/// let nonce: Versions = decode_account_as(data, AccountDataFormat::Bincode)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn decode_account_as<T: DecodeAccountData<M>, M>(
+	data: &[u8],
+	format: AccountDataFormat,
+) -> ClientResult<T> {
+	T::decode_account_data(data, format)
+}
+
+#[cfg(test)]
+mod tests {
+	use assert2::check;
+	use borsh::BorshSerialize;
+	use solana_hash::Hash;
+	use solana_nonce::state::Data;
+	use solana_nonce::state::DurableNonce;
+	use solana_nonce::state::State;
+	use solana_nonce::versions::Versions;
+	use solana_pubkey::Pubkey;
+
+	use super::*;
+
+	#[derive(Debug, PartialEq, BorshSerialize, BorshDeserialize)]
+	struct Counter {
+		authority: [u8; 32],
+		count: u64,
+	}
+
+	#[test]
+	fn decode_bincode_nonce() -> anyhow::Result<()> {
+		let data = Data::new(
+			Pubkey::new_unique(),
+			DurableNonce::from_blockhash(&Hash::new_unique()),
+			5_000,
+		);
+		let versions = Versions::new(State::Initialized(data));
+		let encoded = bincode::serialize(&versions)?;
+		let decoded: Versions = decode_account_as(&encoded, AccountDataFormat::Bincode)?;
+
+		check!(decoded == versions);
+		check!(decode_account_as::<Versions, _>(&encoded, AccountDataFormat::ANCHOR).is_err());
+
+		Ok(())
+	}
+
+	#[test]
+	fn decode_borsh_with_discriminator() -> anyhow::Result<()> {
+		let counter = Counter {
+			authority: [1; 32],
+			count: 42,
+		};
+		let mut encoded = vec![9; 8];
+		counter.serialize(&mut encoded)?;
+		// unused space at the end of the account
+		encoded.extend([0; 16]);
+		let decoded: Counter = decode_account_as(&encoded, AccountDataFormat::ANCHOR)?;
+
+		check!(decoded == counter);
+		check!(decode_account_as::<Counter, _>(&encoded[..4], AccountDataFormat::ANCHOR).is_err());
+		check!(decode_account_as::<Counter, _>(&encoded, AccountDataFormat::Bincode).is_err());
+
+		Ok(())
+	}
+}
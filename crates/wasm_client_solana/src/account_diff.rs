@@ -0,0 +1,257 @@
+//! Compare two snapshots of an account, for example the previous and current
+//! notification of an `accountSubscribe` subscription.
+
+use std::fmt;
+
+use serde_json::Map;
+use serde_json::Value;
+use solana_pubkey::Pubkey;
+
+use crate::solana_account_decoder::UiAccount;
+use crate::solana_account_decoder::UiAccountData;
+
+/// The changes between two snapshots of an account.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccountDiff {
+	/// The change in lamports, negative when lamports were withdrawn.
+	pub lamports_delta: i128,
+	/// The `(old, new)` owner when the owner changed.
+	pub owner: Option<(Pubkey, Pubkey)>,
+	/// The `(old, new)` executable flag when it changed.
+	pub executable: Option<(bool, bool)>,
+	/// The changes to the parsed account data. Only populated when both
+	/// snapshots are `jsonParsed`.
+	pub parsed: Vec<JsonChange>,
+	/// Whether the account data changed.
+	pub data_changed: bool,
+}
+
+impl AccountDiff {
+	/// Whether the two snapshots are identical.
+	pub fn is_empty(&self) -> bool {
+		self.lamports_delta == 0
+			&& self.owner.is_none()
+			&& self.executable.is_none()
+			&& !self.data_changed
+	}
+}
+
+impl fmt::Display for AccountDiff {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		if self.lamports_delta != 0 {
+			writeln!(f, "lamports: {:+}", self.lamports_delta)?;
+		}
+
+		if let Some((old, new)) = self.owner {
+			writeln!(f, "owner: {old} -> {new}")?;
+		}
+
+		if let Some((old, new)) = self.executable {
+			writeln!(f, "executable: {old} -> {new}")?;
+		}
+
+		if self.parsed.is_empty() && self.data_changed {
+			writeln!(f, "data changed")?;
+		}
+
+		for change in &self.parsed {
+			writeln!(f, "{change}")?;
+		}
+
+		Ok(())
+	}
+}
+
+/// A change to a value in the parsed account data. The `path` is the dot
+/// separated list of object keys leading to the value, e.g.
+/// `info.tokenAmount.amount`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum JsonChange {
+	Added {
+		path: String,
+		value: Value,
+	},
+	Removed {
+		path: String,
+		value: Value,
+	},
+	Changed {
+		path: String,
+		old: Value,
+		new: Value,
+	},
+}
+
+impl JsonChange {
+	pub fn path(&self) -> &str {
+		match self {
+			Self::Added { path, .. } | Self::Removed { path, .. } | Self::Changed { path, .. } => {
+				path
+			}
+		}
+	}
+}
+
+impl fmt::Display for JsonChange {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Added { path, value } => write!(f, "+ {path}: {value}"),
+			Self::Removed { path, value } => write!(f, "- {path}: {value}"),
+			Self::Changed { path, old, new } => write!(f, "~ {path}: {old} -> {new}"),
+		}
+	}
+}
+
+/// Diff two snapshots of the same account.
+///
+/// When both snapshots contain `jsonParsed` data the parsed values are
+/// compared key by key, descending into nested objects. Arrays are compared
+/// as a single value.
+pub fn diff_accounts(old: &UiAccount, new: &UiAccount) -> AccountDiff {
+	let parsed = match (&old.data, &new.data) {
+		(UiAccountData::Json(old), UiAccountData::Json(new)) => {
+			let mut changes = vec![];
+
+			if old.program != new.program {
+				changes.push(JsonChange::Changed {
+					path: "program".into(),
+					old: old.program.clone().into(),
+					new: new.program.clone().into(),
+				});
+			}
+
+			diff_json("", &old.parsed, &new.parsed, &mut changes);
+			changes
+		}
+		_ => vec![],
+	};
+
+	AccountDiff {
+		lamports_delta: i128::from(new.lamports) - i128::from(old.lamports),
+		owner: (old.owner != new.owner).then_some((old.owner, new.owner)),
+		executable: (old.executable != new.executable).then_some((old.executable, new.executable)),
+		data_changed: old.data != new.data,
+		parsed,
+	}
+}
+
+fn diff_json(path: &str, old: &Value, new: &Value, changes: &mut Vec<JsonChange>) {
+	match (old, new) {
+		(Value::Object(old), Value::Object(new)) => diff_objects(path, old, new, changes),
+		_ if old != new => {
+			changes.push(JsonChange::Changed {
+				path: path.to_string(),
+				old: old.clone(),
+				new: new.clone(),
+			});
+		}
+		_ => {}
+	}
+}
+
+fn diff_objects(
+	path: &str,
+	old: &Map<String, Value>,
+	new: &Map<String, Value>,
+	changes: &mut Vec<JsonChange>,
+) {
+	let join = |key: &str| {
+		if path.is_empty() {
+			key.to_string()
+		} else {
+			format!("{path}.{key}")
+		}
+	};
+
+	for (key, old_value) in old {
+		match new.get(key) {
+			Some(new_value) => diff_json(&join(key), old_value, new_value, changes),
+			None => {
+				changes.push(JsonChange::Removed {
+					path: join(key),
+					value: old_value.clone(),
+				});
+			}
+		}
+	}
+
+	for (key, new_value) in new {
+		if !old.contains_key(key) {
+			changes.push(JsonChange::Added {
+				path: join(key),
+				value: new_value.clone(),
+			});
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use assert2::check;
+	use serde_json::json;
+
+	use super::*;
+	use crate::solana_account_decoder::parse_account_data::ParsedAccount;
+
+	fn token_account(lamports: u64, amount: &str, state: Option<&str>) -> UiAccount {
+		let mut info = json!({
+			"mint": "So11111111111111111111111111111111111111112",
+			"tokenAmount": { "amount": amount, "decimals": 9 }
+		});
+
+		if let Some(state) = state {
+			info["state"] = state.into();
+		}
+
+		UiAccount {
+			lamports,
+			data: UiAccountData::Json(ParsedAccount {
+				program: "spl-token".into(),
+				parsed: json!({ "type": "account", "info": info }),
+				space: 165,
+			}),
+			owner: spl_token_2022_interface::ID,
+			executable: false,
+			rent_epoch: u64::MAX,
+			space: Some(165),
+		}
+	}
+
+	#[test]
+	fn diff_token_accounts() {
+		let old = token_account(2_039_280, "100", Some("initialized"));
+		let new = token_account(2_039_000, "250", None);
+		let diff = diff_accounts(&old, &new);
+
+		check!(diff.lamports_delta == -280);
+		check!(diff.owner.is_none());
+		check!(diff.executable.is_none());
+		check!(diff.data_changed);
+		check!(
+			diff.parsed
+				== vec![
+					JsonChange::Removed {
+						path: "info.state".into(),
+						value: json!("initialized"),
+					},
+					JsonChange::Changed {
+						path: "info.tokenAmount.amount".into(),
+						old: json!("100"),
+						new: json!("250"),
+					},
+				]
+		);
+		insta::assert_snapshot!(diff, @r#"
+		lamports: -280
+		- info.state: "initialized"
+		~ info.tokenAmount.amount: "100" -> "250"
+		"#);
+	}
+
+	#[test]
+	fn identical_accounts() {
+		let account = token_account(1, "1", None);
+
+		check!(diff_accounts(&account, &account).is_empty());
+	}
+}
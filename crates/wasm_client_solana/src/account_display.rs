@@ -0,0 +1,366 @@
+//! Flatten a [`ParsedAccount`] into rows which are ready to be rendered.
+
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::solana_account_decoder_client_types::ParsedAccount;
+use crate::solana_account_decoder_client_types::token::real_number_string_trimmed;
+
+/// The number of decimals used to display lamports as SOL.
+const SOL_DECIMALS: u8 = 9;
+
+/// A single `label` and `value` pair which describes part of an account.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DisplayRow {
+	pub label: String,
+	pub value: String,
+}
+
+impl DisplayRow {
+	pub fn new(label: impl Into<String>, value: impl Into<String>) -> Self {
+		Self {
+			label: label.into(),
+			value: value.into(),
+		}
+	}
+}
+
+/// Create the display rows for the `account`.
+///
+/// Token, stake, vote and nonce accounts are described field by field with
+/// amounts formatted using their decimals and pubkeys truncated. Any other
+/// account is described by a single row containing its pretty printed json.
+pub fn display_rows(account: &ParsedAccount) -> Vec<DisplayRow> {
+	let parsed = &account.parsed;
+	let kind = parsed["type"].as_str().unwrap_or_default();
+	let rows = match (account.program.as_str(), kind) {
+		("spl-token" | "spl-token-2022", "account") => token_account_rows(parsed),
+		("spl-token" | "spl-token-2022", "mint") => token_mint_rows(parsed),
+		("stake", _) => stake_rows(parsed),
+		("vote", _) => vote_rows(parsed),
+		("nonce", "initialized") => nonce_rows(parsed),
+		_ => None,
+	};
+
+	rows.unwrap_or_else(|| {
+		let json = serde_json::to_string_pretty(parsed).unwrap_or_default();
+		vec![DisplayRow::new("Data", json)]
+	})
+}
+
+/// Shorten a base58 `pubkey` to its first and last four characters.
+pub fn truncate_pubkey(pubkey: &str) -> String {
+	let chars = pubkey.chars().collect::<Vec<_>>();
+
+	if chars.len() <= 8 {
+		return pubkey.to_string();
+	}
+
+	let start = chars[..4].iter().collect::<String>();
+	let end = chars[chars.len() - 4..].iter().collect::<String>();
+
+	format!("{start}…{end}")
+}
+
+fn token_account_rows(parsed: &Value) -> Option<Vec<DisplayRow>> {
+	let info = &parsed["info"];
+	let decimals = u8::try_from(info["tokenAmount"]["decimals"].as_u64()?).ok()?;
+	let mut rows = Rows::new("Token Account");
+
+	rows.pubkey("Mint", &info["mint"]);
+	rows.pubkey("Owner", &info["owner"]);
+	rows.amount("Balance", &info["tokenAmount"]["amount"], decimals);
+	rows.text("State", &info["state"]);
+	rows.pubkey("Delegate", &info["delegate"]);
+	rows.amount(
+		"Delegated Amount",
+		&info["delegatedAmount"]["amount"],
+		decimals,
+	);
+	rows.pubkey("Close Authority", &info["closeAuthority"]);
+
+	if extension_state(info, "memoTransfer", "requireIncomingTransferMemos") {
+		rows.push("Incoming Transfers", "Memo required".to_string());
+	}
+
+	if extension_state(info, "cpiGuard", "lockCpi") {
+		rows.push("CPI Guard", "Locked".to_string());
+	}
+
+	Some(rows.finish())
+}
+
+fn token_mint_rows(parsed: &Value) -> Option<Vec<DisplayRow>> {
+	let info = &parsed["info"];
+	let decimals = u8::try_from(info["decimals"].as_u64()?).ok()?;
+	let mut rows = Rows::new("Token Mint");
+
+	rows.amount("Supply", &info["supply"], decimals);
+	rows.text("Decimals", &info["decimals"]);
+	rows.pubkey("Mint Authority", &info["mintAuthority"]);
+	rows.pubkey("Freeze Authority", &info["freezeAuthority"]);
+
+	Some(rows.finish())
+}
+
+fn stake_rows(parsed: &Value) -> Option<Vec<DisplayRow>> {
+	let info = parsed.get("info")?;
+	let meta = &info["meta"];
+	let delegation = &info["stake"]["delegation"];
+	let mut rows = Rows::new("Stake Account");
+
+	rows.text("State", &parsed["type"]);
+	rows.pubkey("Staker", &meta["authorized"]["staker"]);
+	rows.pubkey("Withdrawer", &meta["authorized"]["withdrawer"]);
+	rows.lamports("Rent Exempt Reserve", &meta["rentExemptReserve"]);
+	rows.pubkey("Vote Account", &delegation["voter"]);
+	rows.lamports("Delegated Stake", &delegation["stake"]);
+	rows.text("Activation Epoch", &delegation["activationEpoch"]);
+
+	if as_u64(&delegation["deactivationEpoch"]) != Some(u64::MAX) {
+		rows.text("Deactivation Epoch", &delegation["deactivationEpoch"]);
+	}
+
+	Some(rows.finish())
+}
+
+fn vote_rows(parsed: &Value) -> Option<Vec<DisplayRow>> {
+	let info = parsed.get("info")?;
+	let mut rows = Rows::new("Vote Account");
+
+	rows.pubkey("Validator Identity", &info["nodePubkey"]);
+	rows.pubkey("Authorized Withdrawer", &info["authorizedWithdrawer"]);
+
+	if let Some(commission) = as_u64(&info["commission"]) {
+		rows.push("Commission", format!("{commission}%"));
+	}
+
+	rows.text("Root Slot", &info["rootSlot"]);
+
+	Some(rows.finish())
+}
+
+fn nonce_rows(parsed: &Value) -> Option<Vec<DisplayRow>> {
+	let info = parsed.get("info")?;
+	let mut rows = Rows::new("Nonce Account");
+
+	rows.pubkey("Authority", &info["authority"]);
+	rows.text("Blockhash", &info["blockhash"]);
+	rows.lamports(
+		"Fee Per Signature",
+		&info["feeCalculator"]["lamportsPerSignature"],
+	);
+
+	Some(rows.finish())
+}
+
+/// Whether the boolean `field` of the `extension` on the account `info` is
+/// set.
+fn extension_state(info: &Value, extension: &str, field: &str) -> bool {
+	info["extensions"].as_array().is_some_and(|extensions| {
+		extensions.iter().any(|item| {
+			item["extension"] == extension && item["state"][field].as_bool() == Some(true)
+		})
+	})
+}
+
+/// Read a `u64` which may be encoded as either a json number or a string.
+fn as_u64(value: &Value) -> Option<u64> {
+	match value {
+		Value::Number(number) => number.as_u64(),
+		Value::String(string) => string.parse().ok(),
+		_ => None,
+	}
+}
+
+/// Collects rows while skipping any values which are missing.
+struct Rows(Vec<DisplayRow>);
+
+impl Rows {
+	fn new(kind: &str) -> Self {
+		Self(vec![DisplayRow::new("Type", kind)])
+	}
+
+	fn push(&mut self, label: &str, value: String) {
+		self.0.push(DisplayRow::new(label, value));
+	}
+
+	fn text(&mut self, label: &str, value: &Value) {
+		match value {
+			Value::String(string) => self.push(label, string.clone()),
+			Value::Number(number) => self.push(label, number.to_string()),
+			Value::Bool(boolean) => self.push(label, boolean.to_string()),
+			_ => {}
+		}
+	}
+
+	fn pubkey(&mut self, label: &str, value: &Value) {
+		if let Some(pubkey) = value.as_str() {
+			self.push(label, truncate_pubkey(pubkey));
+		}
+	}
+
+	fn amount(&mut self, label: &str, value: &Value, decimals: u8) {
+		if let Some(amount) = as_u64(value) {
+			self.push(label, real_number_string_trimmed(amount, decimals));
+		}
+	}
+
+	fn lamports(&mut self, label: &str, value: &Value) {
+		if let Some(lamports) = as_u64(value) {
+			let sol = real_number_string_trimmed(lamports, SOL_DECIMALS);
+			self.push(label, format!("{sol} SOL"));
+		}
+	}
+
+	fn finish(self) -> Vec<DisplayRow> {
+		self.0
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use assert2::check;
+	use serde_json::json;
+
+	use super::*;
+
+	const MINT: &str = "So11111111111111111111111111111111111111112";
+	const OWNER: &str = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+
+	fn rows(program: &str, parsed: Value) -> Vec<(String, String)> {
+		let account = ParsedAccount {
+			program: program.to_string(),
+			parsed,
+			space: 0,
+		};
+
+		display_rows(&account)
+			.into_iter()
+			.map(|row| (row.label, row.value))
+			.collect()
+	}
+
+	fn row(label: &str, value: &str) -> (String, String) {
+		(label.to_string(), value.to_string())
+	}
+
+	#[test]
+	fn token_account_rows() {
+		let parsed = json!({
+			"type": "account",
+			"info": {
+				"isNative": false,
+				"mint": MINT,
+				"owner": OWNER,
+				"state": "initialized",
+				"tokenAmount": {
+					"amount": "1500000",
+					"decimals": 6,
+					"uiAmount": 1.5,
+					"uiAmountString": "1.5"
+				}
+			}
+		});
+
+		check!(
+			rows("spl-token-2022", parsed)
+				== vec![
+					row("Type", "Token Account"),
+					row("Mint", "So11…1112"),
+					row("Owner", "9WzD…AWWM"),
+					row("Balance", "1.5"),
+					row("State", "initialized"),
+				]
+		);
+	}
+
+	#[test]
+	fn token_account_rows_warn_about_memo_and_cpi_guard() {
+		let parsed = json!({
+			"type": "account",
+			"info": {
+				"isNative": false,
+				"mint": MINT,
+				"owner": OWNER,
+				"state": "initialized",
+				"tokenAmount": {
+					"amount": "0",
+					"decimals": 6,
+					"uiAmount": 0.0,
+					"uiAmountString": "0"
+				},
+				"extensions": [
+					{ "extension": "memoTransfer", "state": { "requireIncomingTransferMemos": true } },
+					{ "extension": "cpiGuard", "state": { "lockCpi": true } }
+				]
+			}
+		});
+
+		check!(
+			rows("spl-token-2022", parsed)
+				== vec![
+					row("Type", "Token Account"),
+					row("Mint", "So11…1112"),
+					row("Owner", "9WzD…AWWM"),
+					row("Balance", "0"),
+					row("State", "initialized"),
+					row("Incoming Transfers", "Memo required"),
+					row("CPI Guard", "Locked"),
+				]
+		);
+	}
+
+	#[test]
+	fn stake_account_rows() {
+		let parsed = json!({
+			"type": "delegated",
+			"info": {
+				"meta": {
+					"rentExemptReserve": "2282880",
+					"authorized": { "staker": OWNER, "withdrawer": OWNER },
+					"lockup": { "unixTimestamp": 0, "epoch": 0, "custodian": MINT }
+				},
+				"stake": {
+					"delegation": {
+						"voter": MINT,
+						"stake": "5000000000",
+						"activationEpoch": "100",
+						"deactivationEpoch": "18446744073709551615",
+						"warmupCooldownRate": 0.25
+					},
+					"creditsObserved": 10
+				}
+			}
+		});
+
+		check!(
+			rows("stake", parsed)
+				== vec![
+					row("Type", "Stake Account"),
+					row("State", "delegated"),
+					row("Staker", "9WzD…AWWM"),
+					row("Withdrawer", "9WzD…AWWM"),
+					row("Rent Exempt Reserve", "0.00228288 SOL"),
+					row("Vote Account", "So11…1112"),
+					row("Delegated Stake", "5 SOL"),
+					row("Activation Epoch", "100"),
+				]
+		);
+	}
+
+	#[test]
+	fn unknown_account_falls_back_to_json() {
+		let parsed = json!({ "type": "config", "info": { "keys": [] } });
+		let expected = serde_json::to_string_pretty(&parsed).unwrap();
+
+		check!(rows("config", parsed) == vec![row("Data", &expected)]);
+	}
+
+	#[test]
+	fn short_pubkeys_are_not_truncated() {
+		check!(truncate_pubkey("1111") == "1111");
+		check!(truncate_pubkey(OWNER) == "9WzD…AWWM");
+	}
+}
@@ -0,0 +1,269 @@
+//! Send several requests to the node in a single JSON-RPC batch.
+//!
+//! Each request added to an [`RpcBatch`] returns a [`BatchHandle`] which reads
+//! its typed result from the [`RpcBatchResponses`]. A failing request only
+//! fails its own handle, so the other results of the batch are still usable.
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use solana_account::Account;
+use solana_pubkey::Pubkey;
+
+use crate::ClientError;
+use crate::ClientResponse;
+use crate::ClientResult;
+use crate::SolanaRpcClient;
+use crate::methods::*;
+use crate::rpc_config::RpcAccountInfoConfig;
+use crate::solana_account_decoder::UiAccountEncoding;
+use crate::solana_client::parse_response;
+use crate::solana_client::request_params;
+
+/// A batch of requests created with [`SolanaRpcClient::batch`].
+pub struct RpcBatch<'a> {
+	rpc: &'a SolanaRpcClient,
+	requests: Vec<(&'static str, Value)>,
+	error: Option<ClientError>,
+}
+
+impl<'a> RpcBatch<'a> {
+	pub(crate) fn new(rpc: &'a SolanaRpcClient) -> Self {
+		Self {
+			rpc,
+			requests: vec![],
+			error: None,
+		}
+	}
+
+	/// Add any `request` to the batch. The result is read as the full
+	/// `ClientResponse` of the method, e.g.
+	/// `ClientResponse<GetSlotResponse>`.
+	pub fn add<T: HttpMethod, R: DeserializeOwned>(&mut self, request: T) -> BatchHandle<R> {
+		self.push(request, parse_response)
+	}
+
+	/// Add a request for the account, which is `None` when it doesn't exist.
+	/// The client commitment and default data slice are used.
+	pub fn add_get_account(&mut self, pubkey: &Pubkey) -> BatchHandle<Option<Account>> {
+		let config = self.rpc.with_default_data_slice(RpcAccountInfoConfig {
+			commitment: Some(self.rpc.commitment_config()),
+			encoding: Some(UiAccountEncoding::Base64),
+			..Default::default()
+		});
+		let request = GetAccountInfoRequest::builder()
+			.pubkey(*pubkey)
+			.config(config)
+			.build();
+
+		self.push(request, |value| {
+			let response: ClientResponse<GetAccountInfoResponse> = parse_response(value)?;
+
+			Ok(response.result.value.and_then(|account| account.decode()))
+		})
+	}
+
+	/// Add a request for the lamports of the account with the client
+	/// commitment.
+	pub fn add_get_balance(&mut self, pubkey: &Pubkey) -> BatchHandle<u64> {
+		let request = GetBalanceRequest::new_with_config(*pubkey, self.rpc.commitment_config());
+
+		self.push(request, |value| {
+			let response: ClientResponse<GetBalanceResponse> = parse_response(value)?;
+
+			Ok(response.result.value)
+		})
+	}
+
+	/// The number of requests in the batch.
+	pub fn len(&self) -> usize {
+		self.requests.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.requests.is_empty()
+	}
+
+	/// Send every request in a single JSON-RPC batch. An empty batch completes
+	/// without a request to the node.
+	///
+	/// This only fails when the batch itself fails, such as when the node is
+	/// unreachable. The errors of individual requests are returned by
+	/// [`RpcBatchResponses::get`].
+	pub async fn send(self) -> ClientResult<RpcBatchResponses> {
+		if let Some(error) = self.error {
+			return Err(error);
+		}
+
+		if self.requests.is_empty() {
+			return Ok(RpcBatchResponses { responses: vec![] });
+		}
+
+		let len = self.requests.len();
+		let responses = self.rpc.send_batch(self.requests).await?;
+
+		if responses.len() != len {
+			return Err(ClientError::Other(format!(
+				"expected {len} batch responses but received {}",
+				responses.len()
+			)));
+		}
+
+		Ok(RpcBatchResponses { responses })
+	}
+
+	fn push<T: HttpMethod, R>(
+		&mut self,
+		request: T,
+		parse: fn(Value) -> ClientResult<R>,
+	) -> BatchHandle<R> {
+		let index = self.requests.len();
+
+		match request_params(request) {
+			Ok(params) => self.requests.push((T::NAME, params)),
+			Err(error) => {
+				// keep the indexes of later requests in line with their handles
+				self.requests.push((T::NAME, Value::Null));
+				self.error.get_or_insert(error);
+			}
+		}
+
+		BatchHandle { index, parse }
+	}
+}
+
+/// Reads the typed result of a request from the [`RpcBatchResponses`] of the
+/// [`RpcBatch`] which created it.
+pub struct BatchHandle<T> {
+	index: usize,
+	parse: fn(Value) -> ClientResult<T>,
+}
+
+impl<T> Clone for BatchHandle<T> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<T> Copy for BatchHandle<T> {}
+
+/// The responses of a sent [`RpcBatch`], in the order of its requests.
+#[derive(Clone, Debug)]
+pub struct RpcBatchResponses {
+	responses: Vec<Value>,
+}
+
+impl RpcBatchResponses {
+	/// The result of the request for the `handle`, or the error which the node
+	/// returned for it.
+	pub fn get<T>(&self, handle: BatchHandle<T>) -> ClientResult<T> {
+		let response = self.responses.get(handle.index).ok_or_else(|| {
+			ClientError::Other(format!(
+				"the batch has no response at index {}",
+				handle.index
+			))
+		})?;
+
+		(handle.parse)(response.clone())
+	}
+
+	/// The number of responses in the batch.
+	pub fn len(&self) -> usize {
+		self.responses.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.responses.is_empty()
+	}
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+	use assert2::check;
+	use base64::Engine;
+	use base64::prelude::BASE64_STANDARD;
+	use serde_json::json;
+	use solana_clock::Slot;
+
+	use super::*;
+	use crate::MockRpcProvider;
+	use crate::RpcError;
+
+	#[tokio::test]
+	async fn send_the_requests_in_one_batch() -> anyhow::Result<()> {
+		let account = Pubkey::new_unique();
+		let owner = Pubkey::new_unique();
+		let provider = MockRpcProvider::new()
+			.on(GetAccountInfoRequest::NAME, move |params| {
+				check!(params[1]["encoding"] == "base64");
+				json!({
+					"context": { "slot": 1 },
+					"value": {
+						"lamports": 10,
+						"data": [BASE64_STANDARD.encode([1, 2, 3]), "base64"],
+						"owner": owner.to_string(),
+						"executable": false,
+						"rentEpoch": 0,
+						"space": 3,
+					},
+				})
+			})
+			.on(
+				GetBalanceRequest::NAME,
+				|_| json!({ "context": { "slot": 1 }, "value": 500 }),
+			)
+			.on(GetSlotRequest::NAME, |_| json!(42));
+		let rpc = provider.to_rpc_client();
+		let mut batch = rpc.batch();
+		let account = batch.add_get_account(&account);
+		let balance = batch.add_get_balance(&Pubkey::new_unique());
+		let slot = batch.add::<_, ClientResponse<GetSlotResponse>>(GetSlotRequest::default());
+		let responses = batch.send().await?;
+
+		let account = responses.get(account)?.expect("the account exists");
+		check!(account.data == [1, 2, 3]);
+		check!(account.owner == owner);
+		check!(responses.get(balance)? == 500);
+		check!(Slot::from(responses.get(slot)?.result) == 42);
+		check!(provider.batch_count() == 1);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn failed_requests_only_fail_their_handle() -> anyhow::Result<()> {
+		let provider = MockRpcProvider::new()
+			.on_error(
+				GetAccountInfoRequest::NAME,
+				json!({ "code": -32602, "message": "Invalid param" }),
+			)
+			.on(
+				GetBalanceRequest::NAME,
+				|_| json!({ "context": { "slot": 1 }, "value": 500 }),
+			);
+		let rpc = provider.to_rpc_client();
+		let mut batch = rpc.batch();
+		let account = batch.add_get_account(&Pubkey::new_unique());
+		let balance = batch.add_get_balance(&Pubkey::new_unique());
+		let responses = batch.send().await?;
+
+		let Err(ClientError::Rpc(error)) = responses.get(account) else {
+			panic!("the account request should fail");
+		};
+		check!(error.code() == -32602);
+		check!(matches!(error, RpcError::Custom { .. }));
+		check!(responses.get(balance)? == 500);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn an_empty_batch_sends_nothing() -> anyhow::Result<()> {
+		let provider = MockRpcProvider::new();
+		let responses = provider.to_rpc_client().batch().send().await?;
+
+		check!(responses.is_empty());
+		check!(provider.batch_count() == 0);
+
+		Ok(())
+	}
+}
@@ -0,0 +1,140 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use futures::future::AbortHandle;
+use futures::future::Abortable;
+use pin_project::pin_project;
+
+use crate::ClientError;
+use crate::ClientResult;
+
+/// Wrap the request `future` so that it can be cancelled with the returned
+/// [`CancelHandle`].
+///
+/// ```rust
+/// # use wasm_client_solana::ClientError;
+/// # use wasm_client_solana::DEVNET;
+/// # use wasm_client_solana::SolanaRpcClient;
+/// # use wasm_client_solana::cancellable;
+/// # use solana_pubkey::pubkey;
+///
+/// # async fn run() -> anyhow::Result<()> {
+/// let pubkey = pubkey!("99P8ZgtJYe1buSK8JXkvpLh8xPsCFuLYhz9hQFNw93WJ");
+/// let client = SolanaRpcClient::new(DEVNET);
+/// let (request, handle) = cancellable(client.get_balance(&pubkey));
+///
+/// // the request was superseded by a newer one
+/// handle.cancel();
+///
+/// assert!(matches!(request.await, Err(ClientError::Cancelled)));
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Once cancelled the request resolves immediately with
+/// [`ClientError::Cancelled`]. Dropping the request drops the pending `fetch`
+/// which, in the browser, aborts it with its `AbortController`.
+pub fn cancellable<F, T>(future: F) -> (Cancellable<F>, CancelHandle)
+where
+	F: Future<Output = ClientResult<T>>,
+{
+	let (handle, registration) = AbortHandle::new_pair();
+	let request = Cancellable {
+		inner: Abortable::new(future, registration),
+	};
+
+	(request, CancelHandle(handle))
+}
+
+/// Cancels the request created by [`cancellable`]. The handle can be cloned
+/// and cancelled from anywhere, for example an event listener.
+#[derive(Clone, Debug)]
+pub struct CancelHandle(AbortHandle);
+
+impl CancelHandle {
+	/// Cancel the request. Cancelling a request which has already completed
+	/// has no effect.
+	pub fn cancel(&self) {
+		self.0.abort();
+	}
+
+	/// Whether [`CancelHandle::cancel`] has been called.
+	pub fn is_cancelled(&self) -> bool {
+		self.0.is_aborted()
+	}
+}
+
+/// The future returned by [`cancellable`].
+#[pin_project]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Cancellable<F> {
+	#[pin]
+	inner: Abortable<F>,
+}
+
+impl<F, T> Future for Cancellable<F>
+where
+	F: Future<Output = ClientResult<T>>,
+{
+	type Output = ClientResult<T>;
+
+	fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		self.project()
+			.inner
+			.poll(cx)
+			.map(|result| result.unwrap_or(Err(ClientError::Cancelled)))
+	}
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+	use std::time::Duration;
+
+	use assert2::check;
+	use futures_timer::Delay;
+	use serde_json::Value;
+	use serde_json::json;
+	use solana_commitment_config::CommitmentConfig;
+	use solana_pubkey::Pubkey;
+
+	use super::*;
+	use crate::GetAccountInfoRequest;
+	use crate::MockRpcProvider;
+	use crate::methods::HttpMethod;
+
+	#[tokio::test]
+	async fn cancelling_aborts_the_request() {
+		let provider = MockRpcProvider::new().on(
+			GetAccountInfoRequest::NAME,
+			|_| json!({ "context": { "slot": 1 }, "value": Value::Null }),
+		);
+		let rpc = provider.to_rpc_client();
+		let pubkey = Pubkey::new_unique();
+		let (request, handle) = cancellable(rpc.get_account_await(
+			&pubkey,
+			CommitmentConfig::confirmed(),
+			Duration::from_secs(60),
+		));
+		let cancel = async {
+			Delay::new(Duration::from_millis(50)).await;
+			handle.cancel();
+		};
+
+		let (result, ()) = tokio::join!(request, cancel);
+
+		check!(let Err(ClientError::Cancelled) = result);
+		check!(handle.is_cancelled());
+	}
+
+	#[tokio::test]
+	async fn completed_requests_are_not_cancelled() -> anyhow::Result<()> {
+		let (request, handle) = cancellable(async { Ok(1) });
+
+		check!(request.await? == 1);
+		handle.cancel();
+
+		Ok(())
+	}
+}
@@ -1,3 +1,6 @@
+use std::borrow::Cow;
+use std::sync::Arc;
+
 use serde::Deserialize;
 use serde::Serialize;
 use serde_json::Value;
@@ -46,6 +49,41 @@ pub struct ClientResponse<T> {
 	pub id: u32,
 }
 
+/// Renames json rpc methods before they are sent, for clusters which expose a
+/// solana compatible rpc under different method names. The default keeps
+/// every name unchanged. See
+/// [`crate::SolanaRpcClient::with_method_name_transform`].
+#[derive(Clone, Default)]
+pub struct MethodNameTransform(Option<Arc<TransformFn>>);
+
+type TransformFn = dyn Fn(&str) -> String + Send + Sync;
+
+impl MethodNameTransform {
+	pub fn new(transform: impl Fn(&str) -> String + Send + Sync + 'static) -> Self {
+		Self(Some(Arc::new(transform)))
+	}
+
+	/// The name of the `method` which is sent to the rpc.
+	pub fn apply(&self, method: &'static str) -> Cow<'static, str> {
+		match &self.0 {
+			Some(transform) => Cow::Owned(transform(method)),
+			None => Cow::Borrowed(method),
+		}
+	}
+}
+
+impl std::fmt::Debug for MethodNameTransform {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let kind = if self.0.is_some() {
+			"Custom"
+		} else {
+			"Identity"
+		};
+
+		f.debug_tuple("MethodNameTransform").field(&kind).finish()
+	}
+}
+
 pub type SubscriptionResult = ClientResponse<SubscriptionId>;
 pub type UnsubscriptionResult = ClientResponse<bool>;
 
@@ -1,3 +1,26 @@
 pub const COMPUTE_UNIT_MAX_LIMIT: usize = 1_400_000;
 pub const COMPUTE_UNIT_DEFAULT_LIMIT: usize = 200_000;
 pub const MAX_LOOKUP_ADDRESSES_PER_TRANSACTION: usize = 30;
+/// The maximum number of accounts which can be fetched in a single
+/// `getMultipleAccounts` request.
+pub const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+/// The default number of concurrent `getMultipleAccounts` requests when more
+/// than [`MAX_MULTIPLE_ACCOUNTS`] accounts are fetched.
+pub const DEFAULT_MULTIPLE_ACCOUNTS_CONCURRENCY: usize = 4;
+/// The number of addresses included in each `getInflationReward` request made
+/// by [`crate::SolanaRpcClient::get_stake_rewards`].
+pub const MAX_INFLATION_REWARD_ADDRESSES: usize = 100;
+/// The maximum number of accounts accepted by the
+/// `getRecentPrioritizationFees` request.
+pub const MAX_PRIORITIZATION_FEE_ACCOUNTS: usize = 128;
+/// The `program` of a
+/// [`crate::solana_account_decoder::parse_account_data::ParsedAccount`]
+/// which could not be parsed. See
+/// [`crate::SolanaRpcClient::get_account_auto_parsed`].
+pub const UNPARSABLE_ACCOUNT_PROGRAM: &str = "unparsable";
+/// The maximum number of signatures accepted by a single
+/// `getSignatureStatuses` request.
+pub const MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS: usize = 256;
+/// The maximum, and default, number of signatures returned by a single
+/// `getSignaturesForAddress` request.
+pub const MAX_SIGNATURES_FOR_ADDRESS_LIMIT: usize = 1_000;
@@ -1,67 +1,250 @@
-use std::fmt;
+use std::time::Duration;
 
 use serde::Deserialize;
 use serde::Serialize;
+use serde_json::Value;
+use solana_clock::Slot;
 use solana_message::CompileError;
+use solana_pubkey::Pubkey;
+use solana_signature::Signature;
 use solana_signer::SignerError;
+use solana_transaction_error::TransactionError;
 use wallet_standard::IntoWalletError;
 use wallet_standard::WalletError;
 
 use crate::nonce_utils::NonceError;
+use crate::rpc_response::RpcSimulateTransactionResult;
 
 pub const DEFAULT_ERROR_CODE: u16 = 500u16;
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub(crate) struct RpcErrorDetails {
-	pub(crate) code: i32,
-	pub(crate) message: String,
+/// The code of errors which are created by the client rather than returned
+/// by the node.
+const CLIENT_ERROR_CODE: i64 = 303;
+
+pub const JSON_RPC_SERVER_ERROR_SEND_TRANSACTION_PREFLIGHT_FAILURE: i64 = -32_002;
+pub const JSON_RPC_SERVER_ERROR_TRANSACTION_SIGNATURE_VERIFICATION_FAILURE: i64 = -32_003;
+pub const JSON_RPC_SERVER_ERROR_BLOCK_NOT_AVAILABLE: i64 = -32_004;
+pub const JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY: i64 = -32_005;
+pub const JSON_RPC_SERVER_ERROR_TRANSACTION_PRECOMPILE_VERIFICATION_FAILURE: i64 = -32_006;
+pub const JSON_RPC_SERVER_ERROR_SLOT_SKIPPED: i64 = -32_007;
+pub const JSON_RPC_SERVER_ERROR_LONG_TERM_STORAGE_SLOT_SKIPPED: i64 = -32_009;
+pub const JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED: i64 = -32_016;
+
+/// A JSON-RPC error, decoded from its `code`, `message` and `data`.
+///
+/// The errors which callers usually handle differently, such as retrying
+/// when the node is unhealthy but not when the preflight simulation failed,
+/// have their own variant. Every other error is [`RpcError::Custom`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, thiserror::Error)]
+#[serde(from = "RpcErrorObject", into = "RpcErrorObject")]
+pub enum RpcError {
+	/// The node is behind the cluster, by `num_slots_behind` slots when it's
+	/// known.
+	#[error("Node is unhealthy{}", num_slots_behind.map(|slots| format!(", {slots} slots behind")).unwrap_or_default())]
+	NodeUnhealthy { num_slots_behind: Option<u64> },
+	/// The blockhash of the transaction wasn't found by the preflight
+	/// simulation, usually because it has expired.
+	#[error("Blockhash not found")]
+	BlockhashNotFound,
+	/// The transaction failed the preflight simulation. The `result` has the
+	/// error and logs of the simulation.
+	#[error("{message}")]
+	SendTransactionPreflightFailure {
+		message: String,
+		result: Box<RpcSimulateTransactionResult>,
+	},
+	/// A signature of the transaction is invalid.
+	#[error("{message}")]
+	TransactionSignatureVerificationFailure { message: String },
+	/// A precompile, such as the ed25519 program, rejected the transaction.
+	#[error("{message}")]
+	TransactionPrecompileVerificationFailure { message: String },
+	/// The node is at `context_slot`, which is before the requested minimum
+	/// context slot.
+	#[error("Minimum context slot has not been reached, the node is at slot {context_slot}")]
+	MinContextSlotNotReached { context_slot: Slot },
+	/// Any other error, including the errors created by the client.
+	#[error("Client error: {message}")]
+	Custom {
+		code: i64,
+		message: String,
+		data: Option<Value>,
+	},
 }
 
-impl Default for RpcErrorDetails {
+impl Default for RpcError {
 	fn default() -> Self {
-		let message = "Internal Server Error".into();
-		let code = DEFAULT_ERROR_CODE.into();
+		Self::Custom {
+			code: DEFAULT_ERROR_CODE.into(),
+			message: "Internal Server Error".into(),
+			data: None,
+		}
+	}
+}
+
+impl RpcError {
+	/// Create an error on the client with the `message`.
+	pub fn new(message: impl Into<String>) -> Self {
+		Self::Custom {
+			code: CLIENT_ERROR_CODE,
+			message: message.into(),
+			data: None,
+		}
+	}
+
+	/// The JSON-RPC error code.
+	pub fn code(&self) -> i64 {
+		match self {
+			Self::NodeUnhealthy { .. } => JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY,
+			Self::BlockhashNotFound | Self::SendTransactionPreflightFailure { .. } => {
+				JSON_RPC_SERVER_ERROR_SEND_TRANSACTION_PREFLIGHT_FAILURE
+			}
+			Self::TransactionSignatureVerificationFailure { .. } => {
+				JSON_RPC_SERVER_ERROR_TRANSACTION_SIGNATURE_VERIFICATION_FAILURE
+			}
+			Self::TransactionPrecompileVerificationFailure { .. } => {
+				JSON_RPC_SERVER_ERROR_TRANSACTION_PRECOMPILE_VERIFICATION_FAILURE
+			}
+			Self::MinContextSlotNotReached { .. } => {
+				JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED
+			}
+			Self::Custom { code, .. } => *code,
+		}
+	}
 
-		Self { code, message }
+	/// Whether the node has no block for the requested slot, because the slot
+	/// was skipped or its block isn't available on the node.
+	pub fn is_missing_block(&self) -> bool {
+		matches!(
+			self.code(),
+			JSON_RPC_SERVER_ERROR_BLOCK_NOT_AVAILABLE
+				| JSON_RPC_SERVER_ERROR_SLOT_SKIPPED
+				| JSON_RPC_SERVER_ERROR_LONG_TERM_STORAGE_SLOT_SKIPPED
+		)
 	}
 }
 
+/// The `error` object of a JSON-RPC response.
 #[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct RpcError {
-	pub(crate) id: u32,
-	pub(crate) jsonrpc: String,
-	pub(crate) error: RpcErrorDetails,
+struct RpcErrorObject {
+	code: i64,
+	message: String,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	data: Option<Value>,
 }
 
-impl std::error::Error for RpcError {}
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NodeUnhealthyData {
+	num_slots_behind: Option<u64>,
+}
 
-impl Default for RpcError {
-	fn default() -> Self {
-		Self {
-			id: 0,
-			jsonrpc: String::from("2.0"),
-			error: RpcErrorDetails::default(),
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MinContextSlotNotReachedData {
+	context_slot: Slot,
+}
+
+impl From<RpcErrorObject> for RpcError {
+	fn from(
+		RpcErrorObject {
+			code,
+			message,
+			data,
+		}: RpcErrorObject,
+	) -> Self {
+		let data_as = |data: &Option<Value>| data.clone().unwrap_or_default();
+
+		match code {
+			JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY => {
+				let num_slots_behind = serde_json::from_value::<NodeUnhealthyData>(data_as(&data))
+					.ok()
+					.and_then(|data| data.num_slots_behind);
+
+				return Self::NodeUnhealthy { num_slots_behind };
+			}
+			JSON_RPC_SERVER_ERROR_SEND_TRANSACTION_PREFLIGHT_FAILURE => {
+				if let Ok(result) =
+					serde_json::from_value::<RpcSimulateTransactionResult>(data_as(&data))
+				{
+					if result.err == Some(TransactionError::BlockhashNotFound) {
+						return Self::BlockhashNotFound;
+					}
+
+					return Self::SendTransactionPreflightFailure {
+						message,
+						result: Box::new(result),
+					};
+				}
+			}
+			JSON_RPC_SERVER_ERROR_TRANSACTION_SIGNATURE_VERIFICATION_FAILURE => {
+				return Self::TransactionSignatureVerificationFailure { message };
+			}
+			JSON_RPC_SERVER_ERROR_TRANSACTION_PRECOMPILE_VERIFICATION_FAILURE => {
+				return Self::TransactionPrecompileVerificationFailure { message };
+			}
+			JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED => {
+				if let Ok(data) =
+					serde_json::from_value::<MinContextSlotNotReachedData>(data_as(&data))
+				{
+					return Self::MinContextSlotNotReached {
+						context_slot: data.context_slot,
+					};
+				}
+			}
+			_ => {}
+		}
+
+		Self::Custom {
+			code,
+			message,
+			data,
 		}
 	}
 }
 
-impl RpcError {
-	pub fn new(message: impl Into<String>) -> Self {
-		let message = message.into();
-		let code = 303;
-		let error = RpcErrorDetails { code, message };
+impl From<RpcError> for RpcErrorObject {
+	fn from(error: RpcError) -> Self {
+		let code = error.code();
+		let (message, data) = match error {
+			RpcError::NodeUnhealthy { num_slots_behind } => {
+				(
+					"Node is unhealthy".to_string(),
+					Some(serde_json::json!({ "numSlotsBehind": num_slots_behind })),
+				)
+			}
+			RpcError::BlockhashNotFound => {
+				(
+					"Transaction simulation failed: Blockhash not found".to_string(),
+					Some(serde_json::json!({ "err": "BlockhashNotFound" })),
+				)
+			}
+			RpcError::SendTransactionPreflightFailure { message, result } => {
+				(message, serde_json::to_value(result).ok())
+			}
+			RpcError::TransactionSignatureVerificationFailure { message }
+			| RpcError::TransactionPrecompileVerificationFailure { message } => (message, None),
+			RpcError::MinContextSlotNotReached { context_slot } => {
+				(
+					"Minimum context slot has not been reached".to_string(),
+					Some(serde_json::json!({ "contextSlot": context_slot })),
+				)
+			}
+			RpcError::Custom { message, data, .. } => (message, data),
+		};
 
-		RpcError {
-			error,
-			..Default::default()
+		Self {
+			code,
+			message,
+			data,
 		}
 	}
 }
 
-impl fmt::Display for RpcError {
-	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-		f.write_str(format!("Client error: {}", self.error.message).as_str())
-	}
+/// A JSON-RPC response with an `error`.
+#[derive(Deserialize)]
+pub(crate) struct RpcErrorResponse {
+	pub(crate) error: RpcError,
 }
 
 pub type ClientResult<T> = Result<T, ClientError>;
@@ -79,11 +262,69 @@ pub enum ClientError {
 	/// The nonce error.
 	#[error("{0}")]
 	Nonce(#[from] NonceError),
+	/// The operation did not complete before the timeout elapsed.
+	#[error("Timeout: {0}")]
+	Timeout(String),
+	/// The transaction didn't reach the commitment in time. This is returned
+	/// when the timeout of
+	/// [`crate::SolanaRpcClient::confirm_transaction_with_config`] elapses,
+	/// when the transaction may still land while its blockhash is valid, and
+	/// when the blockhash expires in
+	/// [`crate::SolanaRpcClient::send_and_confirm_transaction_with_rebroadcast`],
+	/// when it can no longer land.
+	#[error("Transaction {signature} was not confirmed in time")]
+	ConfirmationTimeout { signature: Signature },
+	/// The transaction `signature` was confirmed but failed on chain with the
+	/// `error`.
+	#[error("Transaction {signature} failed: {error}")]
+	TransactionFailed {
+		signature: Signature,
+		error: TransactionError,
+	},
+	/// No current or delinquent vote account has the `vote_pubkey`.
+	#[error("Validator with the vote account {vote_pubkey} was not found")]
+	ValidatorNotFound { vote_pubkey: Pubkey },
+	/// The address lookup table `key` can't be used to load the addresses of a
+	/// transaction.
+	#[error("Lookup table {key} {reason}")]
+	LookupTable {
+		key: Pubkey,
+		reason: LookupTableError,
+	},
+	/// The rpc node rejected the request with a `429 Too Many Requests`
+	/// response. `retry_after` is read from the `Retry-After` header and is
+	/// the time to wait before sending another request.
+	#[error("Rate limited{}", retry_after.map(|duration| format!(", retry after {}s", duration.as_secs())).unwrap_or_default())]
+	RateLimited { retry_after: Option<Duration> },
+	/// The request was cancelled with a [`crate::CancelHandle`].
+	#[error("The request was cancelled")]
+	Cancelled,
 	/// The string of any unsupported errors.
 	#[error("Other: {0}")]
 	Other(String),
 }
 
+/// Why an address lookup table can't be used, see [`ClientError::LookupTable`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, thiserror::Error)]
+pub enum LookupTableError {
+	/// The table account doesn't exist, it may have been closed.
+	#[error("was not found, it may have been closed")]
+	NotFound,
+	/// The table account isn't initialized.
+	#[error("is not initialized")]
+	Uninitialized,
+	/// The table account data couldn't be deserialized.
+	#[error("is invalid")]
+	InvalidData,
+	/// The table was deactivated at the slot and its cooldown has passed.
+	#[error("was deactivated at slot {0}")]
+	Deactivated(Slot),
+	/// The table has no address at the `index`, since it only has `len`
+	/// addresses.
+	#[error("has no address at index {index} of its {len} addresses")]
+	InvalidIndex { index: u8, len: usize },
+}
+
 impl IntoWalletError for ClientError {}
 impl IntoWalletError for ClientWebSocketError {}
 impl IntoWalletError for RpcError {}
@@ -140,3 +381,145 @@ impl From<&gloo_net::websocket::WebSocketError> for ClientWebSocketError {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use assert2::check;
+	use serde_json::json;
+	use solana_transaction_error::TransactionError;
+
+	use super::*;
+
+	fn decode(error: &Value) -> RpcError {
+		let response = json!({ "jsonrpc": "2.0", "id": 1, "error": error });
+
+		serde_json::from_value::<RpcErrorResponse>(response)
+			.unwrap()
+			.error
+	}
+
+	#[test]
+	fn node_unhealthy() {
+		let error = decode(&json!({
+			"code": -32005,
+			"message": "Node is behind by 42 slots",
+			"data": { "numSlotsBehind": 42 }
+		}));
+
+		check!(
+			error
+				== RpcError::NodeUnhealthy {
+					num_slots_behind: Some(42)
+				}
+		);
+		check!(error.code() == JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY);
+
+		let error = decode(&json!({ "code": -32005, "message": "Node is unhealthy" }));
+		check!(
+			error
+				== RpcError::NodeUnhealthy {
+					num_slots_behind: None
+				}
+		);
+	}
+
+	#[test]
+	fn send_transaction_preflight_failure() {
+		let error = decode(&json!({
+			"code": -32002,
+			"message": "Transaction simulation failed: Error processing Instruction 0: custom program error: 0x1",
+			"data": {
+				"err": { "InstructionError": [0, { "Custom": 1 }] },
+				"logs": ["Program 11111111111111111111111111111111 failed: custom program error: 0x1"],
+				"accounts": null,
+				"unitsConsumed": 150
+			}
+		}));
+		let RpcError::SendTransactionPreflightFailure { message, result } = &error else {
+			panic!("expected a preflight failure: {error:?}");
+		};
+
+		check!(message.starts_with("Transaction simulation failed"));
+		check!(result.units_consumed == Some(150));
+		check!(result.logs.as_ref().unwrap().len() == 1);
+		check!(
+			result.err
+				== Some(TransactionError::InstructionError(
+					0,
+					solana_instruction::error::InstructionError::Custom(1)
+				))
+		);
+	}
+
+	#[test]
+	fn blockhash_not_found() {
+		let error = decode(&json!({
+			"code": -32002,
+			"message": "Transaction simulation failed: Blockhash not found",
+			"data": { "err": "BlockhashNotFound", "logs": [], "accounts": null }
+		}));
+
+		check!(error == RpcError::BlockhashNotFound);
+		check!(error.to_string() == "Blockhash not found");
+	}
+
+	#[test]
+	fn min_context_slot_not_reached() {
+		let error = decode(&json!({
+			"code": -32016,
+			"message": "Minimum context slot has not been reached",
+			"data": { "contextSlot": 100 }
+		}));
+
+		check!(error == RpcError::MinContextSlotNotReached { context_slot: 100 });
+	}
+
+	#[test]
+	fn custom_errors_keep_the_code_message_and_data() {
+		let error = decode(&json!({
+			"code": -32602,
+			"message": "Invalid params",
+			"data": "invalid pubkey"
+		}));
+
+		check!(
+			error
+				== RpcError::Custom {
+					code: -32602,
+					message: "Invalid params".to_string(),
+					data: Some(json!("invalid pubkey")),
+				}
+		);
+		check!(error.to_string() == "Client error: Invalid params");
+
+		// a preflight failure which can't be decoded is kept as is
+		let error = decode(&json!({ "code": -32002, "message": "failed", "data": 1 }));
+		check!(error.code() == JSON_RPC_SERVER_ERROR_SEND_TRANSACTION_PREFLIGHT_FAILURE);
+		check!(let RpcError::Custom { .. } = error);
+	}
+
+	#[test]
+	fn errors_round_trip_through_json() -> anyhow::Result<()> {
+		for error in [
+			RpcError::NodeUnhealthy {
+				num_slots_behind: Some(3),
+			},
+			RpcError::BlockhashNotFound,
+			RpcError::TransactionSignatureVerificationFailure {
+				message: "Transaction signature verification failure".to_string(),
+			},
+			RpcError::TransactionPrecompileVerificationFailure {
+				message: "Transaction precompile verification failure".to_string(),
+			},
+			RpcError::MinContextSlotNotReached { context_slot: 7 },
+			RpcError::new("Account not found"),
+		] {
+			let value = serde_json::to_value(&error)?;
+
+			check!(value["code"] == error.code());
+			check!(serde_json::from_value::<RpcError>(value)? == error);
+		}
+
+		Ok(())
+	}
+}
@@ -111,6 +111,9 @@ pub trait VersionedTransactionExtension {
 		wallet: &W,
 		options: Option<SolanaSignTransactionOptions>,
 	) -> impl Future<Output = WalletResult<VersionedTransaction>>;
+	/// The version of the transaction message. `None` is returned for legacy
+	/// messages.
+	fn message_version(&self) -> Option<u8>;
 }
 
 impl VersionedTransactionExtension for VersionedTransaction {
@@ -302,16 +305,124 @@ impl VersionedTransactionExtension for VersionedTransaction {
 			Ok(transaction)
 		}
 	}
+
+	fn message_version(&self) -> Option<u8> {
+		self.message.message_version()
+	}
 }
 
 pub trait VersionedMessageExtension {
 	fn into_versioned_transaction(self) -> VersionedTransaction;
+	/// The version of the message. `None` is returned for legacy messages.
+	///
+	/// Serialized versioned messages are prefixed with a byte which has the
+	/// most significant bit set, while legacy messages start with the number
+	/// of required signatures.
+	fn message_version(&self) -> Option<u8>;
 }
 
 impl VersionedMessageExtension for VersionedMessage {
 	fn into_versioned_transaction(self) -> VersionedTransaction {
 		VersionedTransaction::new_unsigned(self)
 	}
+
+	fn message_version(&self) -> Option<u8> {
+		match self {
+			VersionedMessage::Legacy(_) => None,
+			VersionedMessage::V0(_) => Some(0),
+		}
+	}
+}
+
+/// A signed `v0` transaction which was compressed with address lookup tables.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompressedVersionedTransaction {
+	/// The signed transaction.
+	pub transaction: VersionedTransaction,
+	/// The number of bytes saved by using the lookup tables, compared to the
+	/// same transaction without them.
+	pub bytes_saved: usize,
+}
+
+/// Compile and sign a `v0` transaction which loads its accounts from the
+/// `address_lookup_tables`.
+///
+/// The lookup table accounts can be fetched with
+/// [`SolanaRpcClient::get_address_lookup_table_account`]. Signers and invoked
+/// programs must be included in the message directly, every other account
+/// referenced by the `instructions` must be found in one of the
+/// `address_lookup_tables` or an error is returned.
+pub fn compile_v0_transaction_with_lookup_tables<T: Signers + ?Sized>(
+	payer: &Pubkey,
+	instructions: &[Instruction],
+	address_lookup_tables: &[AddressLookupTableAccount],
+	recent_blockhash: Hash,
+	signers: &T,
+) -> ClientResult<CompressedVersionedTransaction> {
+	for instruction in instructions {
+		for account in &instruction.accounts {
+			let is_static = account.is_signer
+				|| account.pubkey == *payer
+				|| instructions
+					.iter()
+					.any(|instruction| instruction.program_id == account.pubkey);
+			let is_in_table = address_lookup_tables
+				.iter()
+				.any(|table| table.addresses.contains(&account.pubkey));
+
+			if !is_static && !is_in_table {
+				return Err(ClientError::Other(format!(
+					"Account {} not found in any of the provided lookup tables.",
+					account.pubkey
+				)));
+			}
+		}
+	}
+
+	let uncompressed = VersionedTransaction::try_new(
+		VersionedMessage::V0(v0::Message::try_compile(
+			payer,
+			instructions,
+			&[],
+			recent_blockhash,
+		)?),
+		signers,
+	)?;
+	let transaction = VersionedTransaction::try_new(
+		VersionedMessage::V0(v0::Message::try_compile(
+			payer,
+			instructions,
+			address_lookup_tables,
+			recent_blockhash,
+		)?),
+		signers,
+	)?;
+	let size = |transaction: &VersionedTransaction| {
+		bincode::serialized_size(transaction).map_err(|error| ClientError::Other(error.to_string()))
+	};
+	let bytes_saved = size(&uncompressed)?.saturating_sub(size(&transaction)?) as usize;
+
+	Ok(CompressedVersionedTransaction {
+		transaction,
+		bytes_saved,
+	})
+}
+
+/// The pubkeys which must sign the `message`, in the order their signatures
+/// appear in the transaction.
+///
+/// Signers are always the first `num_required_signatures` static account keys.
+/// Accounts loaded from address lookup tables can never be signers so they are
+/// never included.
+pub fn required_signers(message: &VersionedMessage) -> Vec<Pubkey> {
+	let signatures = usize::from(message.header().num_required_signatures);
+
+	message
+		.static_account_keys()
+		.iter()
+		.take(signatures)
+		.copied()
+		.collect()
 }
 
 /// Initialize a lookup table that can be used with versioned transactions.
@@ -428,7 +539,7 @@ pub async fn initialize_address_lookup_table<
 	Ok(lookup_table_address)
 }
 
-fn wasm_safe_instruction_clone(instruction: &Instruction) -> Instruction {
+pub(crate) fn wasm_safe_instruction_clone(instruction: &Instruction) -> Instruction {
 	#[cfg(target_arch = "wasm32")]
 	{
 		Instruction {
@@ -442,3 +553,116 @@ fn wasm_safe_instruction_clone(instruction: &Instruction) -> Instruction {
 		instruction.clone()
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use assert2::check;
+	use solana_instruction::AccountMeta;
+	use solana_keypair::Keypair;
+	use solana_signer::Signer;
+
+	use super::*;
+
+	fn instruction(payer: &Pubkey, accounts: &[Pubkey]) -> Instruction {
+		let mut metas = vec![AccountMeta::new(*payer, true)];
+		metas.extend(
+			accounts
+				.iter()
+				.map(|pubkey| AccountMeta::new(*pubkey, false)),
+		);
+
+		Instruction::new_with_bytes(Pubkey::new_unique(), &[], metas)
+	}
+
+	#[test]
+	fn compile_with_lookup_tables() -> anyhow::Result<()> {
+		let payer = Keypair::new();
+		let addresses = (0..8).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+		let table = AddressLookupTableAccount {
+			key: Pubkey::new_unique(),
+			addresses: addresses.clone(),
+		};
+		let compressed = compile_v0_transaction_with_lookup_tables(
+			&payer.pubkey(),
+			&[instruction(&payer.pubkey(), &addresses)],
+			&[table],
+			Hash::new_unique(),
+			&[&payer],
+		)?;
+		let VersionedMessage::V0(ref message) = compressed.transaction.message else {
+			panic!("expected a v0 message");
+		};
+
+		check!(compressed.transaction.is_signed());
+		check!(message.address_table_lookups.len() == 1);
+		check!(message.account_keys.len() == 2);
+		// each address is replaced by a one byte index into the table
+		check!(compressed.bytes_saved == 8 * 31 - 32 - 2);
+
+		Ok(())
+	}
+
+	#[test]
+	fn compile_with_missing_lookup_table_account() {
+		let payer = Keypair::new();
+		let missing = Pubkey::new_unique();
+		let table = AddressLookupTableAccount {
+			key: Pubkey::new_unique(),
+			addresses: vec![Pubkey::new_unique()],
+		};
+		let result = compile_v0_transaction_with_lookup_tables(
+			&payer.pubkey(),
+			&[instruction(&payer.pubkey(), &[table.addresses[0], missing])],
+			&[table],
+			Hash::new_unique(),
+			&[&payer],
+		);
+
+		let Err(ClientError::Other(message)) = result else {
+			panic!("expected an error");
+		};
+		check!(message.contains(&missing.to_string()));
+	}
+
+	#[test]
+	fn required_signers_of_legacy_message() {
+		let payer = Pubkey::new_unique();
+		let signer = Pubkey::new_unique();
+		let readonly = Pubkey::new_unique();
+		let instruction = Instruction::new_with_bytes(
+			Pubkey::new_unique(),
+			&[],
+			vec![
+				AccountMeta::new_readonly(readonly, false),
+				AccountMeta::new(signer, true),
+			],
+		);
+		let message = VersionedMessage::Legacy(solana_message::legacy::Message::new(
+			&[instruction],
+			Some(&payer),
+		));
+
+		check!(required_signers(&message) == vec![payer, signer]);
+	}
+
+	#[test]
+	fn required_signers_exclude_lookup_table_accounts() -> anyhow::Result<()> {
+		let payer = Pubkey::new_unique();
+		let addresses = (0..4).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+		let table = AddressLookupTableAccount {
+			key: Pubkey::new_unique(),
+			addresses: addresses.clone(),
+		};
+		let message = VersionedMessage::V0(v0::Message::try_compile(
+			&payer,
+			&[instruction(&payer, &addresses)],
+			&[table],
+			Hash::new_unique(),
+		)?);
+
+		check!(message.header().num_required_signatures == 1);
+		check!(required_signers(&message) == vec![payer]);
+
+		Ok(())
+	}
+}
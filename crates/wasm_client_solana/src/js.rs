@@ -0,0 +1,222 @@
+//! Helpers for passing values across the wasm boundary.
+//!
+//! JavaScript numbers can only represent integers up to `2^53 - 1` safely,
+//! while lamports, slots and token amounts are `u64` values which routinely
+//! exceed that. Every value handed to JavaScript should be converted with
+//! [`to_js_value`], which represents all `u64` and `i64` values as a `BigInt`,
+//! so that no precision is silently lost.
+
+use js_sys::Array;
+use js_sys::BigInt;
+use js_sys::Object;
+use js_sys::Reflect;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use serde_wasm_bindgen::Serializer;
+use solana_pubkey::Pubkey;
+use solana_signature::Signature;
+use wasm_bindgen::prelude::*;
+
+use crate::account_display::display_rows;
+use crate::siws::SiwsMessage;
+use crate::solana_account_decoder::UiAccount;
+use crate::solana_account_decoder::UiAccountData;
+use crate::solana_account_decoder::parse_account_data::AccountAdditionalDataV3;
+use crate::solana_account_decoder::parse_account_data::SplTokenAdditionalDataV2;
+use crate::solana_account_decoder::parse_account_data::parse_account_data_v3;
+use crate::solana_account_decoder_client_types::ParsedAccount;
+
+/// The serializer used for all values crossing the wasm boundary. Maps are
+/// serialized as plain objects and 64 bit integers as `BigInt`.
+const SERIALIZER: Serializer =
+	Serializer::json_compatible().serialize_large_number_types_as_bigints(true);
+
+/// Serialize the `value` into a [`JsValue`] with `u64` and `i64` values
+/// represented as a `BigInt`.
+pub fn to_js_value<T: Serialize + ?Sized>(value: &T) -> Result<JsValue, serde_wasm_bindgen::Error> {
+	value.serialize(&SERIALIZER)
+}
+
+/// Deserialize a [`JsValue`] created by [`to_js_value`]. Both `BigInt` and
+/// safe integer `number` values are accepted for `u64` fields.
+pub fn from_js_value<T: DeserializeOwned>(value: JsValue) -> Result<T, serde_wasm_bindgen::Error> {
+	serde_wasm_bindgen::from_value(value)
+}
+
+/// The largest integer which a javascript `number` represents exactly.
+const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+/// Convert a value into the [`JsValue`] with the shape of the rpc json, which
+/// is what `@solana/web3.js` consumers expect.
+///
+/// The `u64` fields of the rust types are a `BigInt`, as with
+/// [`to_js_value`]. The numbers in the `parsed` json of a [`ParsedAccount`]
+/// are only a `BigInt` when they aren't a safe integer, since serializing
+/// them with [`to_js_value`] would turn every number, such as the
+/// `decimals`, into a `BigInt`.
+pub trait ToJsValue {
+	fn to_js(&self) -> Result<JsValue, serde_wasm_bindgen::Error>;
+}
+
+impl ToJsValue for ParsedAccount {
+	fn to_js(&self) -> Result<JsValue, serde_wasm_bindgen::Error> {
+		let value = to_js_value(self)?;
+		set_property(&value, "parsed", &json_to_js_value(&self.parsed))?;
+
+		Ok(value)
+	}
+}
+
+/// The binary data is a `[data, encoding]` array, or a `string` for the
+/// legacy base58 encoding, and the parsed data is an object.
+impl ToJsValue for UiAccountData {
+	fn to_js(&self) -> Result<JsValue, serde_wasm_bindgen::Error> {
+		match self {
+			Self::Json(parsed_account) => parsed_account.to_js(),
+			Self::LegacyBinary(_) | Self::Binary(..) => to_js_value(self),
+		}
+	}
+}
+
+impl ToJsValue for UiAccount {
+	fn to_js(&self) -> Result<JsValue, serde_wasm_bindgen::Error> {
+		let value = to_js_value(self)?;
+		set_property(&value, "data", &self.data.to_js()?)?;
+
+		Ok(value)
+	}
+}
+
+fn set_property(
+	target: &JsValue,
+	key: &str,
+	value: &JsValue,
+) -> Result<(), serde_wasm_bindgen::Error> {
+	Reflect::set(target, &key.into(), value)
+		.map(|_| ())
+		.map_err(|_| serde_wasm_bindgen::Error::new(format!("failed to set the `{key}` property")))
+}
+
+/// Convert the json `value`, with integers which aren't safe as a `BigInt`.
+#[allow(clippy::cast_precision_loss)]
+fn json_to_js_value(value: &Value) -> JsValue {
+	match value {
+		Value::Null => JsValue::NULL,
+		Value::Bool(value) => JsValue::from_bool(*value),
+		Value::Number(number) => {
+			if let Some(value) = number.as_u64() {
+				if value <= MAX_SAFE_INTEGER {
+					JsValue::from_f64(value as f64)
+				} else {
+					BigInt::from(value).into()
+				}
+			} else if let Some(value) = number.as_i64() {
+				if value.unsigned_abs() <= MAX_SAFE_INTEGER {
+					JsValue::from_f64(value as f64)
+				} else {
+					BigInt::from(value).into()
+				}
+			} else {
+				JsValue::from_f64(number.as_f64().unwrap_or(f64::NAN))
+			}
+		}
+		Value::String(value) => JsValue::from_str(value),
+		Value::Array(values) => {
+			values
+				.iter()
+				.map(json_to_js_value)
+				.collect::<Array>()
+				.into()
+		}
+		Value::Object(map) => {
+			let object = Object::new();
+
+			for (key, value) in map {
+				// setting a property on a plain object can't fail
+				let _ = Reflect::set(&object, &key.into(), &json_to_js_value(value));
+			}
+
+			object.into()
+		}
+	}
+}
+
+/// Check whether the base58 encoded `address` is on the ed25519 curve. Program
+/// derived addresses are always off the curve.
+#[wasm_bindgen(js_name = isOnCurve)]
+pub fn is_on_curve(address: &str) -> Result<bool, JsError> {
+	let pubkey: Pubkey = address.parse()?;
+
+	Ok(crate::utils::is_on_curve(&pubkey))
+}
+
+/// Create the text of a sign in with Solana message which should be signed by
+/// the wallet. The `message` has the shape of a
+/// [`SiwsMessage`](crate::siws::SiwsMessage).
+#[wasm_bindgen(js_name = createSiwsMessage)]
+pub fn create_siws_message(message: JsValue) -> Result<String, JsError> {
+	let message: SiwsMessage = from_js_value(message)?;
+
+	Ok(message.text()?)
+}
+
+/// Verify that the `signature` bytes were created by the `address` of the sign
+/// in with Solana `message`.
+#[wasm_bindgen(js_name = verifySiwsMessage)]
+pub fn verify_siws_message(message: JsValue, signature: &[u8]) -> Result<bool, JsError> {
+	let message: SiwsMessage = from_js_value(message)?;
+	let signature = Signature::try_from(signature)?;
+
+	Ok(message.verify(&signature)?)
+}
+
+/// Format a `ParsedAccount` as a list of `{ label, value }` rows which can be
+/// rendered directly. See [`display_rows`] for the supported account types.
+#[wasm_bindgen(js_name = formatParsedAccount)]
+pub fn format_parsed_account(account: JsValue) -> Result<JsValue, JsError> {
+	let account: ParsedAccount = from_js_value(account)?;
+
+	Ok(to_js_value(&display_rows(&account))?)
+}
+
+/// Parse the raw `data` of the account at `address`, which is owned by the
+/// `owner` program, into the same `{ program, parsed, space }` object as a
+/// `jsonParsed` rpc response.
+///
+/// Token accounts need the raw `mintData` of their mint for the decimals and
+/// the `unixTimestamp` of the cluster for the interest bearing and scaled ui
+/// amount extensions. Other accounts ignore them.
+#[wasm_bindgen(js_name = parseAccountData)]
+pub fn parse_account_data(
+	address: &str,
+	owner: &str,
+	data: &[u8],
+	#[wasm_bindgen(js_name = mintData)] mint_data: Option<Box<[u8]>>,
+	#[wasm_bindgen(js_name = unixTimestamp)] unix_timestamp: Option<i64>,
+) -> Result<JsValue, JsError> {
+	let address = parse_address("address", address)?;
+	let owner = parse_address("owner", owner)?;
+	let additional_data = mint_data
+		.map(|mint_data| {
+			SplTokenAdditionalDataV2::from_mint_data(&mint_data, unix_timestamp.unwrap_or_default())
+				.ok_or_else(|| JsError::new("`mintData` is not the data of a token mint"))
+		})
+		.transpose()?
+		.map(|spl_token_additional_data| {
+			AccountAdditionalDataV3 {
+				spl_token_additional_data: Some(spl_token_additional_data),
+			}
+		});
+	let parsed = parse_account_data_v3(&address, &owner, data, additional_data)?;
+
+	Ok(parsed.to_js()?)
+}
+
+fn parse_address(name: &str, address: &str) -> Result<Pubkey, JsError> {
+	address.parse().map_err(|error| {
+		JsError::new(&format!(
+			"`{name}` is not a valid address `{address}`: {error}"
+		))
+	})
+}
@@ -5,6 +5,8 @@ pub use solana_account_decoder_wasm as solana_account_decoder;
 pub use solana_transaction_status_client_types_wasm as solana_transaction_status_client_types;
 pub use solana_transaction_status_wasm as solana_transaction_status;
 
+pub use crate::batch::*;
+pub use crate::cancellation::*;
 pub use crate::client::*;
 pub use crate::constants::*;
 pub use crate::errors::*;
@@ -13,20 +15,34 @@ pub use crate::methods::*;
 pub use crate::providers::*;
 pub use crate::rpc_config::*;
 pub use crate::solana_client::*;
+pub use crate::utils::parse_commitment_config;
+pub use crate::utils::parse_commitment_level;
 pub use crate::utils::spawn_local;
 
+pub mod account_data;
+pub mod account_diff;
+pub mod account_display;
+mod batch;
+mod cancellation;
 mod client;
 mod constants;
 mod errors;
 mod extensions;
+pub mod js;
 mod methods;
 pub mod nonce_utils;
+pub mod priority_fees;
+pub mod program_logs;
 mod providers;
 pub mod rpc_config;
 pub mod rpc_filter;
 pub mod rpc_response;
 pub mod runtime;
+pub mod siws;
+pub mod slot_clock;
 mod solana_client;
+pub mod stake_cooldown;
+pub mod streams;
 pub mod utils;
 
 pub mod prelude {
@@ -40,4 +56,5 @@ pub mod prelude {
 	pub use crate::RpcProvider;
 	pub use crate::extensions::VersionedMessageExtension;
 	pub use crate::extensions::VersionedTransactionExtension;
+	pub use crate::streams::SolanaStreamExt;
 }
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use serde::Deserialize;
 use serde::Serialize;
 use serde_with::DisplayFromStr;
@@ -36,6 +38,34 @@ impl From<GetClusterNodesResponse> for Vec<RpcContactInfoWasm> {
 	}
 }
 
+/// The version reported for nodes which don't advertise their software
+/// version.
+pub const UNKNOWN_CLUSTER_NODE_VERSION: &str = "unknown";
+
+/// Tally the software versions of the cluster `nodes`, sorted by the number of
+/// nodes running each version (most popular first) and then by version.
+///
+/// Nodes without a version are counted as [`UNKNOWN_CLUSTER_NODE_VERSION`].
+pub fn cluster_version_distribution(nodes: &[RpcContactInfoWasm]) -> Vec<(String, usize)> {
+	let mut counts = HashMap::<&str, usize>::new();
+
+	for node in nodes {
+		let version = node
+			.version
+			.as_deref()
+			.unwrap_or(UNKNOWN_CLUSTER_NODE_VERSION);
+		*counts.entry(version).or_default() += 1;
+	}
+
+	let mut distribution = counts
+		.into_iter()
+		.map(|(version, count)| (version.to_string(), count))
+		.collect::<Vec<_>>();
+	distribution.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+	distribution
+}
+
 #[cfg(test)]
 mod tests {
 	use assert2::check;
@@ -77,4 +107,25 @@ mod tests {
 		check!(value.feature_set.is_none());
 		check!(value.shred_version.is_none());
 	}
+
+	#[test]
+	fn version_distribution() {
+		let raw_json = r#"[
+			{"pubkey":"9QzsJf7LPLj8GkXbYT3LFDKqsj2hHG7TA3xinJHu8epQ","version":"2.1.0"},
+			{"pubkey":"9QzsJf7LPLj8GkXbYT3LFDKqsj2hHG7TA3xinJHu8epQ","version":"2.0.5"},
+			{"pubkey":"9QzsJf7LPLj8GkXbYT3LFDKqsj2hHG7TA3xinJHu8epQ","version":null},
+			{"pubkey":"9QzsJf7LPLj8GkXbYT3LFDKqsj2hHG7TA3xinJHu8epQ","version":"2.1.0"},
+			{"pubkey":"9QzsJf7LPLj8GkXbYT3LFDKqsj2hHG7TA3xinJHu8epQ"}
+		]"#;
+		let nodes: Vec<RpcContactInfoWasm> = serde_json::from_str(raw_json).unwrap();
+
+		check!(
+			cluster_version_distribution(&nodes)
+				== vec![
+					("2.1.0".to_string(), 2),
+					("unknown".to_string(), 2),
+					("2.0.5".to_string(), 1),
+				]
+		);
+	}
 }
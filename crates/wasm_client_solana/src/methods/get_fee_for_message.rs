@@ -3,7 +3,7 @@ use serde::Serializer;
 use serde_tuple::Serialize_tuple;
 use serde_with::skip_serializing_none;
 use solana_commitment_config::CommitmentConfig;
-use solana_message::Message;
+use solana_message::VersionedMessage;
 
 use super::Context;
 use crate::impl_http_method;
@@ -14,21 +14,21 @@ use crate::solana_transaction_status::UiTransactionEncoding;
 #[derive(Debug, Serialize_tuple)]
 pub struct GetFeeForMessageRequest {
 	#[serde(serialize_with = "ser_message")]
-	pub message: Message,
+	pub message: VersionedMessage,
 	pub config: Option<CommitmentConfig>,
 }
 
 impl_http_method!(GetFeeForMessageRequest, "getFeeForMessage");
 
 impl GetFeeForMessageRequest {
-	pub fn new(message: Message) -> Self {
+	pub fn new(message: VersionedMessage) -> Self {
 		Self {
 			message,
 			config: None,
 		}
 	}
 
-	pub fn new_with_config(message: Message, config: CommitmentConfig) -> Self {
+	pub fn new_with_config(message: VersionedMessage, config: CommitmentConfig) -> Self {
 		Self {
 			message,
 			config: Some(config),
@@ -36,8 +36,10 @@ impl GetFeeForMessageRequest {
 	}
 }
 
-fn ser_message<S: Serializer>(msg: &Message, ser: S) -> Result<S::Ok, S::Error> {
-	let message = serialize_and_encode::<Message>(msg, UiTransactionEncoding::Base64)
+/// Legacy messages are serialized without a version prefix, and versioned
+/// messages with it, the same as they are in a transaction.
+fn ser_message<S: Serializer>(msg: &VersionedMessage, ser: S) -> Result<S::Ok, S::Error> {
+	let message = serialize_and_encode::<VersionedMessage>(msg, UiTransactionEncoding::Base64)
 		.map_err(serde::ser::Error::custom)?;
 	ser.serialize_str(&message)
 }
@@ -51,9 +53,10 @@ pub struct GetFeeForMessageResponse {
 	pub value: FeeForMessageValue,
 }
 
-impl From<GetFeeForMessageResponse> for u64 {
+/// The fee is `None` when the blockhash of the message has expired.
+impl From<GetFeeForMessageResponse> for Option<u64> {
 	fn from(val: GetFeeForMessageResponse) -> Self {
-		val.value.0.unwrap_or_default()
+		val.value.0
 	}
 }
 
@@ -62,6 +65,9 @@ mod tests {
 	use assert2::check;
 	use base64::Engine;
 	use base64::prelude::BASE64_STANDARD;
+	use solana_hash::Hash;
+	use solana_message::v0;
+	use solana_pubkey::Pubkey;
 
 	use super::*;
 	use crate::ClientRequest;
@@ -110,4 +116,31 @@ mod tests {
 		check!(response.result.context.slot == 5068);
 		check!(response.result.value.0 == Some(5000));
 	}
+
+	#[test]
+	fn request_with_v0_message() {
+		let payer = Pubkey::new_unique();
+		let message = v0::Message::try_compile(&payer, &[], &[], Hash::default()).unwrap();
+		let request = GetFeeForMessageRequest::new(VersionedMessage::V0(message.clone()));
+		let params = serde_json::to_value(&request).unwrap();
+		let encoded = BASE64_STANDARD.decode(params[0].as_str().unwrap()).unwrap();
+
+		// the version prefix is included
+		check!(encoded[0] == 0x80);
+		check!(
+			bincode::deserialize::<VersionedMessage>(&encoded).unwrap()
+				== VersionedMessage::V0(message)
+		);
+	}
+
+	#[test]
+	fn response_with_expired_blockhash() {
+		let raw_json =
+			r#"{"jsonrpc":"2.0","result":{"context":{"slot":5068},"value":null},"id":1}"#;
+
+		let response: ClientResponse<GetFeeForMessageResponse> =
+			serde_json::from_str(raw_json).unwrap();
+
+		check!(Option::<u64>::from(response.result) == None);
+	}
 }
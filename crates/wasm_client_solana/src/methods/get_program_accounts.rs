@@ -5,7 +5,6 @@ use serde_with::serde_as;
 use serde_with::skip_serializing_none;
 use solana_pubkey::Pubkey;
 
-use super::impl_websocket_notification;
 use crate::impl_http_method;
 use crate::rpc_config::RpcKeyedAccount;
 use crate::rpc_config::RpcProgramAccountsConfig;
@@ -40,12 +39,14 @@ impl GetProgramAccountsRequest {
 #[derive(Debug, Deserialize, PartialEq, Eq)]
 pub struct GetProgramAccountsResponse(Option<Vec<RpcKeyedAccount>>);
 
-impl_websocket_notification!(GetProgramAccountsResponse, "program");
-
 impl GetProgramAccountsResponse {
 	pub fn keyed_accounts(&self) -> Option<&Vec<RpcKeyedAccount>> {
 		self.0.as_ref()
 	}
+
+	pub fn into_keyed_accounts(self) -> Option<Vec<RpcKeyedAccount>> {
+		self.0
+	}
 }
 
 #[cfg(test)]
@@ -1,5 +1,6 @@
 use serde::Deserialize;
-use serde_tuple::Serialize_tuple;
+use serde::Serialize;
+use serde::ser::SerializeTuple;
 use serde_with::DisplayFromStr;
 use serde_with::serde_as;
 use solana_pubkey::Pubkey;
@@ -7,13 +8,32 @@ use solana_pubkey::Pubkey;
 use crate::impl_http_method;
 use crate::rpc_response::RpcPrioritizationFee;
 
-#[serde_as]
-#[derive(Debug, Serialize_tuple)]
+#[derive(Debug)]
 pub struct GetRecentPrioritizationFeesRequest {
-	#[serde_as(as = "Option<Vec<DisplayFromStr>>")]
 	accounts: Option<Vec<Pubkey>>,
 }
 
+/// The params are empty, rather than `[null]`, when there are no accounts.
+impl Serialize for GetRecentPrioritizationFeesRequest {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		#[serde_as]
+		#[derive(Serialize)]
+		#[serde(transparent)]
+		struct Accounts<'a>(#[serde_as(as = "Vec<DisplayFromStr>")] &'a Vec<Pubkey>);
+
+		let Some(accounts) = &self.accounts else {
+			return serializer.serialize_tuple(0)?.end();
+		};
+		let mut tuple = serializer.serialize_tuple(1)?;
+		tuple.serialize_element(&Accounts(accounts))?;
+
+		tuple.end()
+	}
+}
+
 impl_http_method!(
 	GetRecentPrioritizationFeesRequest,
 	"getRecentPrioritizationFees"
@@ -30,9 +50,10 @@ impl GetRecentPrioritizationFeesRequest {
 		GetRecentPrioritizationFeesRequest { accounts: None }
 	}
 
+	/// The `accounts` param is omitted when no accounts are provided.
 	pub fn new_with_accounts(accounts: Vec<Pubkey>) -> Self {
 		GetRecentPrioritizationFeesRequest {
-			accounts: Some(accounts),
+			accounts: (!accounts.is_empty()).then_some(accounts),
 		}
 	}
 }
@@ -80,6 +101,19 @@ mod tests {
   "###);
 	}
 
+	#[test]
+	fn request_without_accounts() {
+		let request = ClientRequest::builder()
+			.method(GetRecentPrioritizationFeesRequest::NAME)
+			.id(1)
+			.params(GetRecentPrioritizationFeesRequest::new_with_accounts(
+				vec![],
+			))
+			.build();
+
+		insta::assert_compact_json_snapshot!(request, @r###"{"jsonrpc": "2.0", "id": 1, "method": "getRecentPrioritizationFees"}"###);
+	}
+
 	#[test]
 	fn response() {
 		let raw_json = r#"{"jsonrpc":"2.0","result":[{"slot":348125,"prioritizationFee":0},{"slot":348126,"prioritizationFee":1000},{"slot":348127,"prioritizationFee":500},{"slot":348128,"prioritizationFee":0},{"slot":348129,"prioritizationFee":1234}],"id":1}"#;
@@ -34,6 +34,12 @@ impl GetSlotLeadersRequest {
 #[derive(Debug, Deserialize, IntoIterator)]
 pub struct GetSlotLeadersResponse(#[serde_as(as = "Vec<DisplayFromStr>")] Vec<Pubkey>);
 
+impl From<GetSlotLeadersResponse> for Vec<Pubkey> {
+	fn from(value: GetSlotLeadersResponse) -> Self {
+		value.0
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use assert2::check;
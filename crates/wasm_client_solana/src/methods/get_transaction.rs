@@ -53,6 +53,7 @@ mod tests {
 
 	use assert2::check;
 	use solana_message::MessageHeader;
+	use solana_pubkey::Pubkey;
 
 	use super::*;
 	use crate::ClientRequest;
@@ -60,7 +61,9 @@ mod tests {
 	use crate::methods::HttpMethod;
 	use crate::solana_transaction_status::EncodedTransaction;
 	use crate::solana_transaction_status::UiCompiledInstruction;
+	use crate::solana_transaction_status::UiInstruction;
 	use crate::solana_transaction_status::UiMessage;
+	use crate::solana_transaction_status::UiParsedInstruction;
 	use crate::solana_transaction_status::UiRawMessage;
 	use crate::solana_transaction_status::UiTransaction;
 	use crate::solana_transaction_status::UiTransactionEncoding;
@@ -155,4 +158,101 @@ mod tests {
             })
         );
 	}
+
+	#[test]
+	fn json_parsed_response_with_inner_instructions() {
+		let payer = Pubkey::new_unique();
+		let recipient = Pubkey::new_unique();
+		let program = Pubkey::new_unique();
+		let nested_program = Pubkey::new_unique();
+		let system_program = Pubkey::from_str("11111111111111111111111111111111").unwrap();
+		let raw_json = serde_json::json!({
+			"jsonrpc": "2.0",
+			"id": 1,
+			"result": {
+				"slot": 430,
+				"blockTime": null,
+				"meta": {
+					"err": null,
+					"status": { "Ok": null },
+					"fee": 5000,
+					"preBalances": [10_000_000, 0, 1, 1, 1],
+					"postBalances": [8_995_000, 1_000_000, 1, 1, 1],
+					"innerInstructions": [{
+						"index": 1,
+						"instructions": [
+							{
+								"programId": nested_program.to_string(),
+								"accounts": [payer.to_string(), recipient.to_string()],
+								"data": "3Bxs4h24hBtQy9rw",
+								"stackHeight": 2
+							},
+							{
+								"program": "system",
+								"programId": system_program.to_string(),
+								"parsed": {
+									"type": "transfer",
+									"info": {
+										"source": payer.to_string(),
+										"destination": recipient.to_string(),
+										"lamports": 1_000_000
+									}
+								},
+								"stackHeight": 3
+							}
+						]
+					}]
+				},
+				"transaction": {
+					"signatures": ["2nBhEBYYvfaAe16UMNqRHre4YNSskvuYgx3M6E4JP1oDYvZEJHvoPzyUidNgNX5r9sTyN1J9UxtbCXy2rqYcuyuv"],
+					"message": {
+						"accountKeys": [
+							{ "pubkey": payer.to_string(), "writable": true, "signer": true, "source": "transaction" },
+							{ "pubkey": recipient.to_string(), "writable": true, "signer": false, "source": "transaction" },
+							{ "pubkey": program.to_string(), "writable": false, "signer": false, "source": "transaction" },
+							{ "pubkey": nested_program.to_string(), "writable": false, "signer": false, "source": "transaction" },
+							{ "pubkey": system_program.to_string(), "writable": false, "signer": false, "source": "transaction" }
+						],
+						"recentBlockhash": "mfcyqEXB3DnHXki6KjjmZck6YjmZLvpAByy2fj4nh6B",
+						"instructions": [
+							{
+								"program": "spl-memo",
+								"programId": "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr",
+								"parsed": "hello",
+								"stackHeight": null
+							},
+							{
+								"programId": program.to_string(),
+								"accounts": [payer.to_string(), recipient.to_string(), nested_program.to_string()],
+								"data": "3Bxs4h24hBtQy9rw",
+								"stackHeight": null
+							}
+						]
+					}
+				}
+			}
+		});
+
+		let response: ClientResponse<GetTransactionResponse> =
+			serde_json::from_value(raw_json).unwrap();
+		let transaction = response.result.0.unwrap().transaction;
+		let meta = transaction.meta.unwrap();
+		let inner_instructions = meta.inner_instructions_by_index();
+
+		check!(inner_instructions.keys().copied().collect::<Vec<_>>() == vec![1]);
+		let [nested, transfer] = inner_instructions[&1].as_slice() else {
+			panic!("expected two inner instructions");
+		};
+		check!(nested.stack_height() == Some(2));
+		check!(transfer.stack_height() == Some(3));
+		let UiInstruction::Parsed(UiParsedInstruction::PartiallyDecoded(nested)) = nested else {
+			panic!("expected a partially decoded instruction");
+		};
+		check!(nested.program_id == nested_program);
+		let UiInstruction::Parsed(UiParsedInstruction::Parsed(transfer)) = transfer else {
+			panic!("expected a parsed instruction");
+		};
+		check!(transfer.program == "system");
+		check!(transfer.parsed["info"]["lamports"] == 1_000_000);
+	}
 }
@@ -38,6 +38,7 @@ impl From<GetVoteAccountsResponse> for RpcVoteAccountStatus {
 #[cfg(test)]
 mod tests {
 	use assert2::check;
+	use solana_commitment_config::CommitmentConfig;
 	use solana_pubkey::pubkey;
 
 	use super::*;
@@ -72,6 +73,36 @@ mod tests {
   "###);
 	}
 
+	#[test]
+	fn request_with_delinquent_options() {
+		let request = ClientRequest::builder()
+			.method(GetVoteAccountsRequest::NAME)
+			.id(1)
+			.params(GetVoteAccountsRequest::new_with_config(
+				RpcGetVoteAccountsConfig {
+					commitment: Some(CommitmentConfig::finalized()),
+					keep_unstaked_delinquents: Some(true),
+					delinquent_slot_distance: Some(64),
+					..Default::default()
+				},
+			))
+			.build();
+		insta::assert_compact_json_snapshot!(request, @r###"
+  {
+    "jsonrpc": "2.0",
+    "id": 1,
+    "method": "getVoteAccounts",
+    "params": [
+      {
+        "commitment": "finalized",
+        "delinquentSlotDistance": 64,
+        "keepUnstakedDelinquents": true
+      }
+    ]
+  }
+  "###);
+	}
+
 	#[test]
 	fn response() {
 		let raw_json = r#"{"jsonrpc":"2.0","result":{"current":[{"commission":0,"epochVoteAccount":true,"epochCredits":[[1,64,0],[2,192,64]],"nodePubkey":"B97CCUW3AEZFGy6uUg6zUdnNYvnVq5VG8PUtb2HayTDD","lastVote":147,"activatedStake":42,"votePubkey":"3ZT31jkAGhUaw8jsy4bTknwBMP8i4Eueh52By4zXcsVw"}],"delinquent":[]},"id":1}"#;
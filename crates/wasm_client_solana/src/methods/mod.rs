@@ -141,6 +141,10 @@ pub trait WebSocketNotification: DeserializeOwned {
 
 pub trait WebSocketMethod: Serialize {
 	const SUBSCRIBE: &'static str;
+	/// The expected time between notifications, when they arrive at a regular
+	/// interval. This is used as the default for a
+	/// [`crate::streams::WatchdogConfig`].
+	const EXPECTED_INTERVAL: Option<std::time::Duration> = None;
 }
 
 macro_rules! impl_websocket_method {
@@ -149,6 +153,12 @@ macro_rules! impl_websocket_method {
 			const SUBSCRIBE: &'static str = concat!($prefix, "Subscribe");
 		}
 	};
+	($ident:ident, $prefix:literal, $expected_interval:expr) => {
+		impl $crate::methods::WebSocketMethod for $ident {
+			const EXPECTED_INTERVAL: Option<std::time::Duration> = Some($expected_interval);
+			const SUBSCRIBE: &'static str = concat!($prefix, "Subscribe");
+		}
+	};
 }
 macro_rules! impl_websocket_notification {
 	($ident:ident, $prefix:literal) => {
@@ -31,7 +31,8 @@ impl Serialize for SimulateTransactionRequest {
 		};
 
 		let serialized_encoded =
-			serialize_and_encode::<VersionedTransaction>(&self.transaction, encoding).unwrap();
+			serialize_and_encode::<VersionedTransaction>(&self.transaction, encoding)
+				.map_err(serde::ser::Error::custom)?;
 
 		let tuple = if let Some(config) = &self.config {
 			let mut tuple = serializer.serialize_tuple(2)?;
@@ -63,8 +64,8 @@ impl<'de> Deserialize<'de> for SimulateTransactionRequest {
 			None => UiTransactionEncoding::Base64,
 		};
 
-		let transaction =
-			deserialize_and_decode::<VersionedTransaction>(&inner.0, encoding).unwrap();
+		let transaction = deserialize_and_decode::<VersionedTransaction>(&inner.0, encoding)
+			.map_err(serde::de::Error::custom)?;
 
 		Ok(SimulateTransactionRequest {
 			transaction,
@@ -191,4 +192,17 @@ mod tests {
 				}
 		);
 	}
+
+	#[test]
+	fn request_with_unsupported_encoding() {
+		let request = SimulateTransactionRequest::new_with_config(
+			VersionedTransaction::default(),
+			RpcSimulateTransactionConfig {
+				encoding: Some(UiTransactionEncoding::JsonParsed),
+				..Default::default()
+			},
+		);
+
+		check!(serde_json::to_value(request).is_err());
+	}
 }
@@ -63,7 +63,7 @@ pub async fn get_account_with_commitment(
 	commitment_config: CommitmentConfig,
 ) -> Result<Account, NonceError> {
 	rpc_client
-		.get_account_with_config(
+		.get_full_account_with_config(
 			nonce_pubkey,
 			RpcAccountInfoConfig {
 				commitment: Some(commitment_config),
@@ -145,7 +145,7 @@ impl SolanaRpcClient {
 	/// Returns an error if the account is not owned by the system program or
 	/// contains no data.
 	pub async fn get_nonce_account_state(&self, nonce_pubkey: &Pubkey) -> ClientResult<State> {
-		let account = self.get_account(nonce_pubkey).await?;
+		let account = self.get_full_account(nonce_pubkey).await?;
 		account_identity_ok(&account)?;
 		let state = state_from_account(&account)?;
 
@@ -0,0 +1,132 @@
+//! Priority fee estimation for assembling transactions which adapt to the
+//! current network conditions.
+
+use serde::Deserialize;
+use serde::Serialize;
+use typed_builder::TypedBuilder;
+
+use crate::rpc_response::RpcPrioritizationFee;
+
+/// How quickly a transaction should be included in a block.
+///
+/// Each urgency maps to a percentile of the recent prioritization fees paid
+/// for the accounts written by the transaction:
+///
+/// | Urgency  | Percentile |
+/// | -------- | ---------- |
+/// | `Low`    | 25         |
+/// | `Medium` | 50         |
+/// | `High`   | 90         |
+///
+/// Use [`PriorityFeeConfig::percentile`] to override the mapping.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FeeUrgency {
+	Low,
+	#[default]
+	Medium,
+	High,
+}
+
+impl FeeUrgency {
+	/// The percentile of recent prioritization fees used for this urgency.
+	pub const fn percentile(self) -> u8 {
+		match self {
+			Self::Low => 25,
+			Self::Medium => 50,
+			Self::High => 90,
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, TypedBuilder)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PriorityFeeConfig {
+	/// The urgency used to pick the percentile of recent fees.
+	#[builder(default)]
+	pub urgency: FeeUrgency,
+	/// Override the percentile (`0..=100`) chosen by the `urgency`.
+	#[builder(default, setter(strip_option))]
+	pub percentile: Option<u8>,
+	/// The percentage of the simulated compute units used as the compute unit
+	/// limit. Defaults to `110` which leaves a 10% margin.
+	#[builder(default = 110)]
+	pub compute_unit_margin: u64,
+}
+
+impl Default for PriorityFeeConfig {
+	fn default() -> Self {
+		Self::builder().build()
+	}
+}
+
+impl PriorityFeeConfig {
+	/// The percentile of recent prioritization fees to pay.
+	pub fn fee_percentile(&self) -> u8 {
+		self.percentile
+			.unwrap_or_else(|| self.urgency.percentile())
+			.min(100)
+	}
+}
+
+/// Get the fee at the `percentile` (`0..=100`) of the recent `fees` using the
+/// nearest rank method. Returns `0` when there are no recent fees.
+pub fn fee_percentile(fees: &[RpcPrioritizationFee], percentile: u8) -> u64 {
+	let mut fees = fees
+		.iter()
+		.map(|fee| fee.prioritization_fee)
+		.collect::<Vec<_>>();
+
+	if fees.is_empty() {
+		return 0;
+	}
+
+	fees.sort_unstable();
+	let rank = (usize::from(percentile.min(100)) * fees.len()).div_ceil(100);
+
+	fees[rank.saturating_sub(1)]
+}
+
+#[cfg(test)]
+mod tests {
+	use assert2::check;
+
+	use super::*;
+
+	fn fees(values: &[u64]) -> Vec<RpcPrioritizationFee> {
+		values
+			.iter()
+			.enumerate()
+			.map(|(slot, fee)| {
+				RpcPrioritizationFee {
+					slot: slot as u64,
+					prioritization_fee: *fee,
+				}
+			})
+			.collect()
+	}
+
+	#[test]
+	fn fee_percentile_uses_nearest_rank() {
+		let fees = fees(&[500, 0, 1000, 0, 1234, 100, 200, 300, 400, 600]);
+
+		check!(fee_percentile(&fees, 0) == 0);
+		check!(fee_percentile(&fees, FeeUrgency::Low.percentile()) == 100);
+		check!(fee_percentile(&fees, FeeUrgency::Medium.percentile()) == 300);
+		check!(fee_percentile(&fees, FeeUrgency::High.percentile()) == 1000);
+		check!(fee_percentile(&fees, 100) == 1234);
+		check!(fee_percentile(&[], 50) == 0);
+	}
+
+	#[test]
+	fn percentile_override() {
+		let config = PriorityFeeConfig::builder()
+			.urgency(FeeUrgency::High)
+			.percentile(75)
+			.build();
+
+		check!(config.fee_percentile() == 75);
+		check!(PriorityFeeConfig::default().fee_percentile() == 50);
+		check!(PriorityFeeConfig::default().compute_unit_margin == 110);
+	}
+}
@@ -0,0 +1,127 @@
+//! Utilities for reading the logs emitted by programs during a transaction.
+
+use std::str::FromStr;
+
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use solana_pubkey::Pubkey;
+
+const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+/// The data emitted by a program with `sol_log_data`, which is how Anchor's
+/// `emit!` macro publishes events.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProgramDataEvent {
+	/// The program which was executing when the data was logged.
+	pub program_id: Pubkey,
+	/// The decoded event bytes.
+	pub data: Vec<u8>,
+}
+
+impl ProgramDataEvent {
+	/// The 8 byte discriminator which Anchor prefixes to each event. This is
+	/// `None` when the data is shorter than 8 bytes.
+	pub fn discriminator(&self) -> Option<[u8; 8]> {
+		self.data.get(..8)?.try_into().ok()
+	}
+
+	/// The event data without the discriminator.
+	pub fn payload(&self) -> &[u8] {
+		self.data.get(8..).unwrap_or_default()
+	}
+}
+
+/// Scan the transaction `logs` for `Program data: <base64>` lines and decode
+/// them.
+///
+/// The emitting program is tracked through the `Program <id> invoke [n]` and
+/// `Program <id> success` / `failed` lines, so events emitted from within a
+/// cross program invocation are attributed to the inner program. Lines which
+/// can't be decoded, or appear outside of any program invocation, are
+/// skipped.
+pub fn parse_program_data_events<S: AsRef<str>>(logs: &[S]) -> Vec<ProgramDataEvent> {
+	let mut stack: Vec<Pubkey> = vec![];
+	let mut events = vec![];
+
+	for log in logs {
+		let log = log.as_ref();
+
+		if let Some(encoded) = log.strip_prefix(PROGRAM_DATA_PREFIX) {
+			let Some(program_id) = stack.last() else {
+				continue;
+			};
+
+			// `sol_log_data` separates each data field with a space
+			let data = encoded
+				.split(' ')
+				.map(|field| BASE64_STANDARD.decode(field))
+				.collect::<Result<Vec<_>, _>>();
+
+			if let Ok(data) = data {
+				events.push(ProgramDataEvent {
+					program_id: *program_id,
+					data: data.concat(),
+				});
+			}
+
+			continue;
+		}
+
+		let Some(rest) = log.strip_prefix("Program ") else {
+			continue;
+		};
+		let mut parts = rest.split(' ');
+		let (Some(program_id), Some(action)) = (parts.next(), parts.next()) else {
+			continue;
+		};
+		let Ok(program_id) = Pubkey::from_str(program_id) else {
+			continue;
+		};
+
+		match action {
+			"invoke" => stack.push(program_id),
+			"success" | "failed:" => {
+				stack.pop();
+			}
+			_ => {}
+		}
+	}
+
+	events
+}
+
+#[cfg(test)]
+mod tests {
+	use assert2::check;
+	use solana_pubkey::pubkey;
+
+	use super::*;
+
+	const OUTER: Pubkey = pubkey!("6YFRs1Hwvr8e4LUmN9xPdQvWEuwsLsm1ph31AxeTGKiH");
+	const INNER: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGqPFXCWuBvf9Ss623VQ5DA");
+
+	#[test]
+	fn parse_program_data_logs() {
+		let logs = [
+			format!("Program {OUTER} invoke [1]"),
+			"Program log: Instruction: Transfer".to_string(),
+			format!("Program {INNER} invoke [2]"),
+			"Program data: AQID".to_string(),
+			format!("Program {INNER} consumed 4645 of 188526 compute units"),
+			format!("Program {INNER} success"),
+			"Program data: AQIDBAUGBwgJCg== Cw==".to_string(),
+			"Program data: not base64!".to_string(),
+			format!("Program {OUTER} consumed 16000 of 200000 compute units"),
+			format!("Program {OUTER} success"),
+		];
+		let events = parse_program_data_events(&logs);
+
+		check!(events.len() == 2);
+		check!(events[0].program_id == INNER);
+		check!(events[0].data == vec![1, 2, 3]);
+		check!(events[0].discriminator() == None);
+		check!(events[1].program_id == OUTER);
+		check!(events[1].discriminator() == Some([1, 2, 3, 4, 5, 6, 7, 8]));
+		check!(events[1].payload() == [9, 10, 11]);
+	}
+}
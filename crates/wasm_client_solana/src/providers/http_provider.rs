@@ -1,38 +1,159 @@
+use std::pin::pin;
+use std::time::Duration;
+
 use async_trait::async_trait;
+use futures::Future;
+use futures::future::Either;
+use futures::future::select;
+use futures_timer::Delay;
+use serde::Serialize;
 use serde_json::Value;
+#[cfg(all(feature = "ssr", not(target_arch = "wasm32")))]
+pub use ssr_http_provider::HttpPoolConfig;
 #[cfg(feature = "ssr")]
 pub use ssr_http_provider::HttpProvider;
 #[cfg(not(feature = "ssr"))]
 pub use wasm_http_provider::HttpProvider;
 
+use crate::ClientError;
 use crate::ClientRequest;
 use crate::ClientResult;
-use crate::DEFAULT_ERROR_CODE;
 use crate::RpcError;
-use crate::RpcErrorDetails;
 
 #[async_trait]
 pub trait RpcProvider {
 	/// Send the request.
-	async fn send(&self, method: &'static str, request: Value) -> ClientResult<Value>;
+	async fn send(&self, method: &str, request: Value) -> ClientResult<Value>;
+	/// Send the `requests` as a single JSON-RPC batch. The responses are
+	/// returned in the same order as the `requests`.
+	///
+	/// The default implementation sends each request separately.
+	async fn send_batch(&self, requests: Vec<(&str, Value)>) -> ClientResult<Vec<Value>> {
+		let mut responses = Vec::with_capacity(requests.len());
+
+		for (method, request) in requests {
+			responses.push(self.send(method, request).await?);
+		}
+
+		Ok(responses)
+	}
 	/// Get the URL represented by this sender.
 	fn url(&self) -> String;
 }
 
+/// The status code of a rate limited response.
+const TOO_MANY_REQUESTS: u16 = 429;
+
+/// Parse the `Retry-After` header, which is the number of seconds to wait.
+/// The http date form isn't used by rpc nodes and is ignored.
+fn parse_retry_after(value: &str) -> Option<Duration> {
+	value.trim().parse().ok().map(Duration::from_secs)
+}
+
+/// Fail with [`ClientError::Timeout`] when the `request` doesn't complete
+/// within the `timeout`.
+async fn with_timeout<T>(
+	request: impl Future<Output = ClientResult<T>>,
+	timeout: Option<Duration>,
+) -> ClientResult<T> {
+	let Some(timeout) = timeout else {
+		return request.await;
+	};
+
+	match select(pin!(request), Delay::new(timeout)).await {
+		Either::Left((result, _)) => result,
+		Either::Right(_) => {
+			Err(ClientError::Timeout(format!(
+				"the request did not complete within {}ms",
+				timeout.as_millis()
+			)))
+		}
+	}
+}
+
+/// Create the body of a JSON-RPC batch request. Each request is given its
+/// index as the `id`.
+fn batch_request_body(requests: Vec<(&str, Value)>) -> Vec<ClientRequest> {
+	requests
+		.into_iter()
+		.enumerate()
+		.map(|(id, (method, params))| {
+			ClientRequest::builder()
+				.method(method)
+				.id(id as u32)
+				.params(params)
+				.build()
+		})
+		.collect()
+}
+
+/// Order the responses of a JSON-RPC batch by their `id`, since the node is
+/// free to respond in any order.
+fn sort_batch_response(response: Value, len: usize) -> ClientResult<Vec<Value>> {
+	let Value::Array(mut responses) = response else {
+		return Err(RpcError::new("the batch response is not an array").into());
+	};
+
+	if responses.len() != len {
+		return Err(RpcError::new(format!(
+			"expected {len} batch responses but received {}",
+			responses.len()
+		))
+		.into());
+	}
+
+	responses.sort_by_key(|response| response["id"].as_u64());
+
+	Ok(responses)
+}
+
 #[cfg(feature = "ssr")]
 mod ssr_http_provider {
 	use reqwest::Client;
+	use reqwest::Response;
 	use reqwest::header::CONTENT_TYPE;
 	use reqwest::header::HeaderMap;
+	use reqwest::header::HeaderName;
+	use reqwest::header::HeaderValue;
+	use reqwest::header::RETRY_AFTER;
+	#[cfg(not(target_arch = "wasm32"))]
+	use typed_builder::TypedBuilder;
 
 	use super::*;
-	use crate::ClientError;
+	use crate::DEFAULT_ERROR_CODE;
+
+	/// The connection pool settings for the native http client.
+	///
+	/// Connections are kept alive and reused between requests, which avoids a
+	/// new connection and TLS handshake for every call. In the browser the
+	/// connection pool is managed by `fetch` and can't be configured.
+	#[cfg(not(target_arch = "wasm32"))]
+	#[derive(Debug, Clone, Copy, PartialEq, Eq, TypedBuilder)]
+	pub struct HttpPoolConfig {
+		/// The maximum number of idle connections kept open for each host.
+		#[builder(default = usize::MAX)]
+		pub max_idle_per_host: usize,
+		/// How long an idle connection is kept open. Set to `None` to keep
+		/// idle connections open indefinitely.
+		#[builder(default = Some(Duration::from_secs(90)))]
+		pub idle_timeout: Option<Duration>,
+	}
 
+	#[cfg(not(target_arch = "wasm32"))]
+	impl Default for HttpPoolConfig {
+		fn default() -> Self {
+			Self::builder().build()
+		}
+	}
+
+	/// The `client` is cheap to clone and shares its connection pool between
+	/// clones, so it is only created once per provider.
 	#[derive(Debug, Clone)]
 	pub struct HttpProvider {
 		client: Client,
 		headers: HeaderMap,
 		url: String,
+		timeout: Option<Duration>,
 	}
 
 	#[async_trait]
@@ -41,22 +162,104 @@ mod ssr_http_provider {
 			self.url.clone()
 		}
 
-		async fn send(&self, method: &'static str, request: Value) -> ClientResult<Value> {
+		async fn send(&self, method: &str, request: Value) -> ClientResult<Value> {
 			let client_request = ClientRequest::builder()
 				.method(method)
 				.id(1)
 				.params(request)
 				.build();
+
+			with_timeout(self.post(&client_request), self.timeout).await
+		}
+
+		async fn send_batch(&self, requests: Vec<(&str, Value)>) -> ClientResult<Vec<Value>> {
+			let len = requests.len();
+			let body = batch_request_body(requests);
+			let result = with_timeout(self.post(&body), self.timeout).await?;
+
+			sort_batch_response(result, len)
+		}
+	}
+
+	impl HttpProvider {
+		pub fn new(url: impl Into<String>) -> Self {
+			Self::new_with_client(url, Client::new())
+		}
+
+		/// Create a provider with custom connection pool settings. An error is
+		/// returned when the http client can't be created.
+		#[cfg(not(target_arch = "wasm32"))]
+		pub fn new_with_pool_config(
+			url: impl Into<String>,
+			pool_config: HttpPoolConfig,
+		) -> ClientResult<Self> {
+			let client = Client::builder()
+				.pool_max_idle_per_host(pool_config.max_idle_per_host)
+				.pool_idle_timeout(pool_config.idle_timeout)
+				.build()
+				.map_err(|error| {
+					ClientError::Other(format!("Failed to create the http client: {error}"))
+				})?;
+
+			Ok(Self::new_with_client(url, client))
+		}
+
+		/// Create a provider which sends requests with the provided `client`.
+		pub fn new_with_client(url: impl Into<String>, client: Client) -> Self {
+			let url = url.into();
+			let mut headers = HeaderMap::new();
+			headers.append(CONTENT_TYPE, "application/json".parse().unwrap());
+
+			Self {
+				client,
+				headers,
+				url,
+				timeout: None,
+			}
+		}
+
+		/// Send the `headers` with every request, such as the api key header
+		/// required by some rpc providers. An error is returned when a header
+		/// name or value is invalid.
+		pub fn with_headers<K: AsRef<str>, V: AsRef<str>>(
+			mut self,
+			headers: impl IntoIterator<Item = (K, V)>,
+		) -> ClientResult<Self> {
+			for (name, value) in headers {
+				let (name, value) = (name.as_ref(), value.as_ref());
+				let header_name = HeaderName::from_bytes(name.as_bytes()).map_err(|error| {
+					ClientError::Other(format!("Invalid header {name}: {error}"))
+				})?;
+				let header_value = HeaderValue::from_str(value).map_err(|error| {
+					ClientError::Other(format!("Invalid header {name}: {error}"))
+				})?;
+
+				self.headers.insert(header_name, header_value);
+			}
+
+			Ok(self)
+		}
+
+		/// Fail requests with [`ClientError::Timeout`] when they don't
+		/// complete within the `timeout`.
+		#[must_use]
+		pub fn with_timeout(mut self, timeout: Duration) -> Self {
+			self.timeout = Some(timeout);
+			self
+		}
+
+		async fn post(&self, body: &(impl Serialize + Sync)) -> ClientResult<Value> {
 			#[cfg(not(target_arch = "wasm32"))]
-			let result: Value = self
-				.client
-				.post(&self.url)
-				.headers(self.headers.clone())
-				.json(&client_request)
-				.send()
-				.await?
-				.json()
-				.await?;
+			let result: Value = check_rate_limit(
+				self.client
+					.post(&self.url)
+					.headers(self.headers.clone())
+					.json(body)
+					.send()
+					.await?,
+			)?
+			.json()
+			.await?;
 
 			#[cfg(target_arch = "wasm32")]
 			let result: Value = {
@@ -64,43 +267,41 @@ mod ssr_http_provider {
 					.client
 					.post(&self.url)
 					.headers(self.headers.clone())
-					.json(&client_request)
+					.json(body)
 					.send();
 				let wrapped_request = send_wrapper::SendWrapper::new(request);
-				let response = wrapped_request.await?.json();
+				let response = check_rate_limit(wrapped_request.await?)?.json();
 				let wrapped_response = send_wrapper::SendWrapper::new(response);
-				let result = wrapped_response.await?;
-				result
+				wrapped_response.await?
 			};
 
 			Ok(result)
 		}
 	}
 
-	impl HttpProvider {
-		pub fn new(url: impl Into<String>) -> Self {
-			let client = Client::new();
-			let url = url.into();
-			let mut headers = HeaderMap::new();
-			headers.append(CONTENT_TYPE, "application/json".parse().unwrap());
-
-			Self {
-				client,
-				headers,
-				url,
-			}
+	/// Return a [`ClientError::RateLimited`] error for `429` responses.
+	fn check_rate_limit(response: Response) -> ClientResult<Response> {
+		if response.status().as_u16() != TOO_MANY_REQUESTS {
+			return Ok(response);
 		}
+
+		let retry_after = response
+			.headers()
+			.get(RETRY_AFTER)
+			.and_then(|value| value.to_str().ok())
+			.and_then(parse_retry_after);
+
+		Err(ClientError::RateLimited { retry_after })
 	}
 
 	impl From<reqwest::Error> for RpcError {
 		fn from(error: reqwest::Error) -> Self {
-			let message = error.to_string();
-			let code = i32::from(error.status().map_or(DEFAULT_ERROR_CODE, |s| s.as_u16()));
-			let error = RpcErrorDetails { code, message };
+			let code = error.status().map_or(DEFAULT_ERROR_CODE, |s| s.as_u16());
 
-			RpcError {
-				error,
-				..Default::default()
+			RpcError::Custom {
+				code: code.into(),
+				message: error.to_string(),
+				data: None,
 			}
 		}
 	}
@@ -119,7 +320,6 @@ mod wasm_http_provider {
 	use std::task::Context;
 	use std::task::Poll;
 
-	use futures::Future;
 	use pin_project::pin_project;
 	use pin_project::pinned_drop;
 	use send_wrapper::SendWrapper;
@@ -127,7 +327,6 @@ mod wasm_http_provider {
 	use web_sys::AbortController;
 
 	use super::*;
-	use crate::ClientError;
 
 	#[pin_project(PinnedDrop)]
 	struct AbortableRequest<F: Future<Output = Result<gloo_net::http::Response, gloo_net::Error>>> {
@@ -179,43 +378,113 @@ mod wasm_http_provider {
 	}
 
 	#[derive(Debug, Clone)]
-	pub struct HttpProvider(String);
+	pub struct HttpProvider {
+		url: String,
+		headers: Vec<(String, String)>,
+		timeout: Option<Duration>,
+	}
 
 	#[async_trait]
 	impl RpcProvider for HttpProvider {
 		fn url(&self) -> String {
-			self.0.clone()
+			self.url.clone()
 		}
 
-		async fn send(&self, method: &'static str, request: Value) -> ClientResult<Value> {
+		async fn send(&self, method: &str, request: Value) -> ClientResult<Value> {
 			let client_request = ClientRequest::builder()
 				.method(method)
 				.id(0)
 				.params(request)
 				.build();
+			let value = SendWrapper::new(self.post(&client_request));
 
-			let future = async move {
-				let controller = AbortController::new().unwrap_throw();
-				let signal = controller.signal();
-				let request = gloo_net::http::Request::post(&self.0)
-					.abort_signal(Some(&signal))
-					.json(&client_request)?;
-				let response = AbortableRequest::new(request.send(), controller).await?;
-				let value = response.json().await?;
-
-				Ok::<Value, ClientError>(value)
-			};
+			with_timeout(value, self.timeout).await
+		}
 
-			let value = SendWrapper::new(future).await?;
+		async fn send_batch(&self, requests: Vec<(&str, Value)>) -> ClientResult<Vec<Value>> {
+			let len = requests.len();
+			let body = batch_request_body(requests);
+			let value = with_timeout(SendWrapper::new(self.post(&body)), self.timeout).await?;
 
-			Ok(value)
+			sort_batch_response(value, len)
 		}
 	}
 
 	impl HttpProvider {
 		pub fn new(url: impl Into<String>) -> Self {
-			Self(url.into())
+			Self {
+				url: url.into(),
+				headers: vec![],
+				timeout: None,
+			}
+		}
+
+		/// Send the `headers` with every request, such as the api key header
+		/// required by some rpc providers. An error is returned when a header
+		/// name or value is invalid.
+		pub fn with_headers<K: AsRef<str>, V: AsRef<str>>(
+			mut self,
+			headers: impl IntoIterator<Item = (K, V)>,
+		) -> ClientResult<Self> {
+			for (name, value) in headers {
+				let (name, value) = (name.as_ref(), value.as_ref());
+				let valid_name = !name.is_empty()
+					&& name.bytes().all(|byte| {
+						byte.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&byte)
+					});
+				let valid_value = value
+					.bytes()
+					.all(|byte| byte == b'\t' || (b' '..=b'~').contains(&byte));
+
+				if !valid_name || !valid_value {
+					return Err(ClientError::Other(format!("Invalid header {name}")));
+				}
+
+				self.headers.push((name.to_string(), value.to_string()));
+			}
+
+			Ok(self)
+		}
+
+		/// Fail requests with [`ClientError::Timeout`] when they don't
+		/// complete within the `timeout`.
+		#[must_use]
+		pub fn with_timeout(mut self, timeout: Duration) -> Self {
+			self.timeout = Some(timeout);
+			self
+		}
+
+		async fn post(&self, body: &impl Serialize) -> ClientResult<Value> {
+			let controller = AbortController::new().unwrap_throw();
+			let signal = controller.signal();
+			let mut request = gloo_net::http::Request::post(&self.url).abort_signal(Some(&signal));
+
+			for (name, value) in &self.headers {
+				request = request.header(name, value);
+			}
+
+			let response = AbortableRequest::new(request.json(body)?.send(), controller).await?;
+			let value = check_rate_limit(response)?.json().await?;
+
+			Ok(value)
+		}
+	}
+
+	/// Return a [`ClientError::RateLimited`] error for `429` responses.
+	fn check_rate_limit(
+		response: gloo_net::http::Response,
+	) -> ClientResult<gloo_net::http::Response> {
+		if response.status() != TOO_MANY_REQUESTS {
+			return Ok(response);
 		}
+
+		let retry_after = response
+			.headers()
+			.get("retry-after")
+			.as_deref()
+			.and_then(parse_retry_after);
+
+		Err(ClientError::RateLimited { retry_after })
 	}
 
 	impl From<serde_wasm_bindgen::Error> for ClientError {
@@ -245,3 +514,197 @@ pub const TESTNET: &str = "https://api.testnet.solana.com";
 pub const MAINNET: &str = "https://api.mainnet-beta.solana.com";
 pub const LOCALNET: &str = "http://127.0.0.1:8899";
 pub const DEBUG: &str = "http://34.90.18.145:8899";
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+	use std::io::BufRead;
+	use std::io::BufReader;
+	use std::io::Read;
+	use std::io::Write;
+	use std::net::TcpListener;
+	use std::net::TcpStream;
+	use std::thread::JoinHandle;
+
+	use assert2::check;
+	use serde_json::json;
+
+	use super::*;
+
+	/// An empty json rpc result.
+	const NULL_RESULT: &str = r#"{"jsonrpc":"2.0","id":1,"result":null}"#;
+
+	/// A raw http response with the `status` line, extra `headers` and `body`.
+	fn http_response(status: &str, headers: &str, body: &str) -> String {
+		format!(
+			"HTTP/1.1 {status}\r\n{headers}content-length: {}\r\n\r\n{body}",
+			body.len()
+		)
+	}
+
+	/// Serve the first connection to a local port by writing the raw http
+	/// `response` to every request, or by never responding when it is `None`.
+	///
+	/// The handle returns the lowercased request lines and headers which were
+	/// received once the connection is closed. Any other connection is left
+	/// unanswered.
+	fn fake_server(response: Option<String>) -> anyhow::Result<(String, JoinHandle<String>)> {
+		let listener = TcpListener::bind("127.0.0.1:0")?;
+		let url = format!("http://{}", listener.local_addr()?);
+		let handle = std::thread::spawn(move || {
+			let mut received = String::new();
+
+			if let Ok((stream, _)) = listener.accept() {
+				serve(stream, response.as_deref(), &mut received).ok();
+			}
+
+			received
+		});
+
+		Ok((url, handle))
+	}
+
+	fn serve(
+		stream: TcpStream,
+		response: Option<&str>,
+		received: &mut String,
+	) -> std::io::Result<()> {
+		let mut reader = BufReader::new(stream.try_clone()?);
+		let mut stream = stream;
+
+		loop {
+			let mut content_length = 0;
+
+			loop {
+				let mut line = String::new();
+
+				if reader.read_line(&mut line)? == 0 {
+					return Ok(());
+				}
+
+				let line = line.trim_end().to_lowercase();
+
+				if line.is_empty() {
+					break;
+				}
+
+				if let Some(("content-length", value)) = line.split_once(':') {
+					content_length = value.trim().parse().unwrap_or_default();
+				}
+
+				received.push_str(&line);
+				received.push('\n');
+			}
+
+			reader.read_exact(&mut vec![0; content_length])?;
+
+			if let Some(response) = response {
+				stream.write_all(response.as_bytes())?;
+			}
+		}
+	}
+
+	/// Wait for the `server` without blocking the runtime, which needs to run
+	/// to close the connections of a dropped [`HttpProvider`].
+	async fn received(server: JoinHandle<String>) -> anyhow::Result<String> {
+		tokio::task::spawn_blocking(move || server.join())
+			.await?
+			.map_err(|_| anyhow::anyhow!("the fake server panicked"))
+	}
+
+	#[tokio::test]
+	async fn repeated_requests_reuse_the_connection() -> anyhow::Result<()> {
+		let response = http_response("200 OK", "content-type: application/json\r\n", NULL_RESULT);
+		let (url, server) = fake_server(Some(response))?;
+		// a second connection is never answered, so fail instead of hanging
+		let provider = HttpProvider::new_with_pool_config(url, HttpPoolConfig::default())?
+			.with_timeout(Duration::from_secs(5));
+
+		for _ in 0..3 {
+			let result = provider.send("getSlot", json!([])).await?;
+			check!(result["result"].is_null());
+		}
+
+		drop(provider);
+		let received = received(server).await?;
+
+		check!(received.matches("post / http/1.1").count() == 3);
+
+		Ok(())
+	}
+
+	#[test]
+	fn batch_responses_are_ordered_by_id() -> anyhow::Result<()> {
+		let response = json!([
+			{ "jsonrpc": "2.0", "id": 1, "result": 300 },
+			{ "jsonrpc": "2.0", "id": 0, "result": 350 }
+		]);
+		let responses = sort_batch_response(response, 2)?;
+
+		check!(responses[0]["result"] == 350);
+		check!(responses[1]["result"] == 300);
+		check!(sort_batch_response(json!({}), 2).is_err());
+		check!(sort_batch_response(json!([]), 2).is_err());
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn rate_limited_response_has_retry_after() -> anyhow::Result<()> {
+		let response = http_response(
+			"429 Too Many Requests",
+			"retry-after: 3\r\n",
+			"Too Many Requests",
+		);
+		let (url, _) = fake_server(Some(response))?;
+		let provider = HttpProvider::new(url);
+		let result = provider.send("getSlot", json!([])).await;
+
+		let Err(ClientError::RateLimited { retry_after }) = result else {
+			panic!("expected a rate limited error: {result:?}");
+		};
+
+		check!(retry_after == Some(Duration::from_secs(3)));
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn requests_include_the_custom_headers() -> anyhow::Result<()> {
+		let response = http_response("200 OK", "content-type: application/json\r\n", NULL_RESULT);
+		let (url, server) = fake_server(Some(response))?;
+		let provider = HttpProvider::new(url).with_headers([("X-Api-Key", "secret")])?;
+		provider.send("getSlot", json!([])).await?;
+
+		drop(provider);
+		let received = received(server).await?;
+		let headers = received.lines().collect::<Vec<_>>();
+
+		check!(headers.contains(&"x-api-key: secret"));
+		check!(headers.contains(&"content-type: application/json"));
+		check!(
+			HttpProvider::new("http://localhost")
+				.with_headers([("", "value")])
+				.is_err()
+		);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn requests_fail_after_the_timeout() -> anyhow::Result<()> {
+		let (url, _) = fake_server(None)?;
+		let provider = HttpProvider::new(url).with_timeout(Duration::from_millis(100));
+		let result = provider.send("getSlot", json!([])).await;
+
+		check!(let Err(ClientError::Timeout(_)) = result);
+
+		Ok(())
+	}
+
+	#[test]
+	fn retry_after_header_values() {
+		check!(parse_retry_after("120") == Some(Duration::from_secs(120)));
+		check!(parse_retry_after(" 1 ") == Some(Duration::from_secs(1)));
+		check!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT").is_none());
+	}
+}
@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use serde_json::Value;
+use serde_json::json;
+use solana_commitment_config::CommitmentConfig;
+
+use crate::ClientError;
+use crate::ClientResult;
+use crate::LOCALNET;
+use crate::RpcProvider;
+use crate::SolanaRpcClient;
+
+type Handler = Box<dyn Fn(&Value) -> Value + Send + Sync>;
+
+/// An [`RpcProvider`] for unit tests which responds to each method with the
+/// value returned by the registered handler and records every request.
+#[derive(Clone, Default)]
+pub(crate) struct MockRpcProvider {
+	handlers: Arc<Mutex<HashMap<String, Handler>>>,
+	requests: Arc<Mutex<Vec<(String, Value)>>>,
+	batches: Arc<Mutex<usize>>,
+}
+
+impl MockRpcProvider {
+	pub(crate) fn new() -> Self {
+		Self::default()
+	}
+
+	/// Respond to the `method` with the `result` returned from the handler.
+	/// The handler receives the params of the request.
+	pub(crate) fn on(
+		self,
+		method: &str,
+		handler: impl Fn(&Value) -> Value + Send + Sync + 'static,
+	) -> Self {
+		self.handlers.lock().unwrap().insert(
+			method.to_string(),
+			Box::new(move |params| json!({ "jsonrpc": "2.0", "id": 0, "result": handler(params) })),
+		);
+
+		self
+	}
+
+	/// Respond to the `method` with the json rpc `error`.
+	pub(crate) fn on_error(self, method: &str, error: Value) -> Self {
+		self.handlers.lock().unwrap().insert(
+			method.to_string(),
+			Box::new(move |_| json!({ "jsonrpc": "2.0", "id": 0, "error": error })),
+		);
+
+		self
+	}
+
+	/// The number of times the `method` was called.
+	pub(crate) fn count(&self, method: &str) -> usize {
+		self.requests
+			.lock()
+			.unwrap()
+			.iter()
+			.filter(|(name, _)| name == method)
+			.count()
+	}
+
+	/// The number of batch requests sent.
+	pub(crate) fn batch_count(&self) -> usize {
+		*self.batches.lock().unwrap()
+	}
+
+	pub(crate) fn to_rpc_client(&self) -> SolanaRpcClient {
+		SolanaRpcClient::new_with_provider(Arc::new(self.clone()), CommitmentConfig::confirmed())
+	}
+}
+
+#[async_trait]
+impl RpcProvider for MockRpcProvider {
+	fn url(&self) -> String {
+		LOCALNET.to_string()
+	}
+
+	async fn send(&self, method: &str, request: Value) -> ClientResult<Value> {
+		self.requests
+			.lock()
+			.unwrap()
+			.push((method.to_string(), request.clone()));
+
+		let handlers = self.handlers.lock().unwrap();
+		let handler = handlers
+			.get(method)
+			.ok_or_else(|| ClientError::Other(format!("no mock handler for `{method}`")))?;
+
+		Ok(handler(&request))
+	}
+
+	async fn send_batch(&self, requests: Vec<(&str, Value)>) -> ClientResult<Vec<Value>> {
+		*self.batches.lock().unwrap() += 1;
+		let mut responses = Vec::with_capacity(requests.len());
+
+		for (method, request) in requests {
+			responses.push(self.send(method, request).await?);
+		}
+
+		Ok(responses)
+	}
+}
@@ -1,5 +1,9 @@
 pub use http_provider::*;
+#[cfg(all(test, feature = "ssr"))]
+pub(crate) use mock_provider::*;
 pub use websocket_provider::*;
 
 mod http_provider;
+#[cfg(all(test, feature = "ssr"))]
+mod mock_provider;
 mod websocket_provider;
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::hash::Hash;
 use std::marker::PhantomData;
 use std::pin::Pin;
@@ -26,6 +27,7 @@ use self::websocket_provider_reqwest::*;
 use self::websocket_provider_wasm::*;
 use crate::ClientRequest;
 use crate::ClientWebSocketError;
+use crate::MethodNameTransform;
 use crate::SubscriptionId;
 use crate::SubscriptionResponse;
 use crate::SubscriptionResult;
@@ -62,6 +64,8 @@ pub struct WebSocketProvider {
 	sender: Arc<Mutex<SplitSink<WebSocketStream, Value>>>,
 	#[debug(skip)]
 	receiver: Forked<SplitStream<WebSocketStream>>,
+	/// Renames the subscribe and unsubscribe methods before they are sent.
+	method_name: MethodNameTransform,
 }
 
 impl WebSocketProvider {
@@ -78,9 +82,18 @@ impl WebSocketProvider {
 			id: Arc::new(std::sync::Mutex::new(1000)),
 			sender,
 			receiver,
+			method_name: MethodNameTransform::default(),
 		}
 	}
 
+	/// Rename the subscribe and unsubscribe methods with the `transform`
+	/// and expect notifications with the renamed notification method.
+	#[must_use]
+	pub fn with_method_name_transform(mut self, transform: MethodNameTransform) -> Self {
+		self.method_name = transform;
+		self
+	}
+
 	pub fn url(&self) -> &str {
 		&self.url
 	}
@@ -93,7 +106,7 @@ impl WebSocketProvider {
 	) -> Result<(u32, SubscriptionId), ClientWebSocketError> {
 		let id = self.next_id()?;
 		let request = ClientRequest::builder()
-			.method(T::SUBSCRIBE)
+			.method(self.method_name.apply(T::SUBSCRIBE))
 			.params(params)
 			.id(id)
 			.build()
@@ -151,6 +164,9 @@ pub struct Unsubscription {
 	pub(crate) id: u32,
 	/// The `subscription_id` used to unsubscribe.
 	pub(crate) subscription_id: SubscriptionId,
+	/// Renames the `method` before it is sent.
+	#[builder(default)]
+	pub(crate) method_name: MethodNameTransform,
 }
 
 impl PartialEq for Unsubscription {
@@ -172,7 +188,7 @@ impl Unsubscription {
 	pub async fn run(self) -> Result<(), ClientWebSocketError> {
 		let request = ClientRequest::builder()
 			.id(self.id)
-			.method(self.method)
+			.method(self.method_name.apply(self.method))
 			.params(serde_json::json!([self.subscription_id]))
 			.build()
 			.try_to_value()?;
@@ -221,6 +237,12 @@ pub struct Subscription<T: DeserializeOwned + WebSocketNotification> {
 	pub(crate) creator_id: u32,
 	/// The subscription `id` used to unsubscribe.
 	pub(crate) id: SubscriptionId,
+	/// Renames the unsubscribe method before it is sent.
+	#[builder(default)]
+	pub(crate) method_name: MethodNameTransform,
+	/// The name of the notification method, after it was renamed.
+	#[builder(default = Cow::Borrowed(T::NOTIFICATION))]
+	pub(crate) notification: Cow<'static, str>,
 	// pub(crate) unsubscription: Unsubscription,
 }
 
@@ -231,6 +253,8 @@ impl<T: DeserializeOwned + WebSocketNotification> Subscription<T> {
 			.sender(ws.sender.clone())
 			.creator_id(id)
 			.id(subscription_id)
+			.method_name(ws.method_name.clone())
+			.notification(ws.method_name.apply(T::NOTIFICATION))
 			.build()
 	}
 
@@ -276,6 +300,7 @@ impl<T: DeserializeOwned + WebSocketNotification> Subscription<T> {
 			.receiver(self.receiver.clone())
 			.id(self.creator_id)
 			.subscription_id(self.id)
+			.method_name(self.method_name.clone())
 			.build()
 	}
 
@@ -320,7 +345,7 @@ impl<T: DeserializeOwned + WebSocketNotification> Stream for Subscription<T> {
 			return Poll::Pending;
 		};
 
-		if json.method != T::NOTIFICATION || json.params.subscription != subscription_id {
+		if json.method != *this.notification || json.params.subscription != subscription_id {
 			return Poll::Pending;
 		}
 
@@ -328,6 +353,95 @@ impl<T: DeserializeOwned + WebSocketNotification> Stream for Subscription<T> {
 	}
 }
 
+/// Several [`Subscription`]s merged into a single stream. Every notification
+/// is tagged with the key of the subscription which received it.
+///
+/// Unlike [`Subscription`], dropping this unsubscribes from all the merged
+/// subscriptions in a background task spawned with [`crate::spawn_local`], so
+/// with the `ssr` feature it must be dropped within a tokio `LocalSet`. Use
+/// [`MergedSubscription::unsubscribe`] to wait for the unsubscriptions and
+/// check their results.
+pub struct MergedSubscription<K, T: DeserializeOwned + WebSocketNotification> {
+	subscriptions: Vec<(K, Pin<Box<Subscription<T>>>)>,
+	/// The index of the subscription to poll first, so that a busy
+	/// subscription can't starve the others.
+	next: usize,
+}
+
+impl<K, T: DeserializeOwned + WebSocketNotification> MergedSubscription<K, T> {
+	pub fn new(subscriptions: impl IntoIterator<Item = (K, Subscription<T>)>) -> Self {
+		Self {
+			subscriptions: subscriptions
+				.into_iter()
+				.map(|(key, subscription)| (key, Box::pin(subscription)))
+				.collect(),
+			next: 0,
+		}
+	}
+
+	/// The unsubscriptions for each of the merged subscriptions.
+	pub fn get_unsubscriptions(&self) -> Vec<Unsubscription> {
+		self.subscriptions
+			.iter()
+			.map(|(_, subscription)| subscription.get_unsubscription())
+			.collect()
+	}
+
+	/// Unsubscribe from all the merged subscriptions. Every unsubscription is
+	/// attempted and the first error is returned.
+	pub async fn unsubscribe(mut self) -> Result<(), ClientWebSocketError> {
+		let unsubscriptions = self.get_unsubscriptions();
+		// nothing is left to unsubscribe when this is dropped
+		self.subscriptions.clear();
+
+		let results = future::join_all(unsubscriptions.into_iter().map(Unsubscription::run)).await;
+
+		results.into_iter().collect()
+	}
+}
+
+impl<K, T: DeserializeOwned + WebSocketNotification> Drop for MergedSubscription<K, T> {
+	fn drop(&mut self) {
+		for unsubscription in self.get_unsubscriptions() {
+			crate::spawn_local(async move {
+				unsubscription.run().await.ok();
+			});
+		}
+	}
+}
+
+impl<K: Clone + Unpin, T: DeserializeOwned + WebSocketNotification> Stream
+	for MergedSubscription<K, T>
+{
+	type Item = (K, SubscriptionResponse<T>);
+
+	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		let this = &mut *self;
+		let total = this.subscriptions.len();
+		let mut ended = 0;
+
+		for offset in 0..total {
+			let index = (this.next + offset) % total;
+			let (key, subscription) = &mut this.subscriptions[index];
+
+			match subscription.as_mut().poll_next(cx) {
+				Poll::Ready(Some(notification)) => {
+					this.next = (index + 1) % total;
+					return Poll::Ready(Some((key.clone(), notification)));
+				}
+				Poll::Ready(None) => ended += 1,
+				Poll::Pending => {}
+			}
+		}
+
+		if ended == total {
+			Poll::Ready(None)
+		} else {
+			Poll::Pending
+		}
+	}
+}
+
 #[cfg(feature = "ssr")]
 mod websocket_provider_reqwest {
 	use std::future::Future;
@@ -593,3 +707,84 @@ mod websocket_provider_wasm {
 		}
 	}
 }
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+	use std::net::TcpListener;
+
+	use assert2::check;
+	use async_tungstenite::tungstenite;
+	use futures::channel::mpsc;
+	use serde_json::json;
+
+	use super::*;
+	use crate::rpc_config::LogsSubscribeRequest;
+	use crate::rpc_config::RpcTransactionLogsFilter;
+	use crate::rpc_response::LogsNotificationResponse;
+
+	/// Accept a single websocket connection which responds to every
+	/// subscription and forwards the `params` of every unsubscription.
+	fn fake_websocket_server() -> anyhow::Result<(String, mpsc::UnboundedReceiver<Value>)> {
+		let listener = TcpListener::bind("127.0.0.1:0")?;
+		let url = format!("ws://{}", listener.local_addr()?);
+		let (sender, receiver) = mpsc::unbounded();
+
+		std::thread::spawn(move || -> anyhow::Result<()> {
+			let (stream, _) = listener.accept()?;
+			let mut socket = tungstenite::accept(stream)?;
+			let mut subscription_id = 0;
+
+			loop {
+				let message = socket.read()?;
+				let Ok(request) = serde_json::from_str::<Value>(message.to_text()?) else {
+					continue;
+				};
+
+				let result = if request["method"] == "logsUnsubscribe" {
+					sender.unbounded_send(request["params"].clone())?;
+					json!(true)
+				} else {
+					subscription_id += 1;
+					json!(subscription_id)
+				};
+
+				let response = json!({ "jsonrpc": "2.0", "result": result, "id": request["id"] });
+				socket.send(tungstenite::Message::text(response.to_string()))?;
+			}
+		});
+
+		Ok((url, receiver))
+	}
+
+	#[tokio::test]
+	async fn dropping_a_merged_subscription_unsubscribes() -> anyhow::Result<()> {
+		let (url, mut unsubscriptions) = fake_websocket_server()?;
+		let ws = WebSocketProvider::new(url);
+		let mut subscriptions = vec![];
+
+		for key in 0..2 {
+			let request = LogsSubscribeRequest::builder()
+				.filter(RpcTransactionLogsFilter::All)
+				.build();
+			let (id, subscription_id) = ws.create_subscription(request).await?;
+			let subscription =
+				Subscription::<LogsNotificationResponse>::new(&ws, id, subscription_id);
+			subscriptions.push((key, subscription));
+		}
+
+		let local = tokio::task::LocalSet::new();
+		let received = local
+			.run_until(async move {
+				drop(MergedSubscription::new(subscriptions));
+
+				let first = unsubscriptions.next().await;
+				let second = unsubscriptions.next().await;
+				(first, second)
+			})
+			.await;
+
+		check!(received == (Some(json!([1])), Some(json!([2]))));
+
+		Ok(())
+	}
+}
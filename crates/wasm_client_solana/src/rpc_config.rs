@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use base64::Engine;
 use base64::prelude::BASE64_STANDARD;
 use bincode::serialize;
@@ -9,6 +11,7 @@ use serde::de::DeserializeOwned;
 use serde_with::DisplayFromStr;
 use serde_with::serde_as;
 use serde_with::skip_serializing_none;
+use solana_clock::DEFAULT_MS_PER_SLOT;
 use solana_clock::Epoch;
 use solana_clock::Slot;
 use solana_commitment_config::CommitmentConfig;
@@ -23,6 +26,7 @@ use crate::ClientError;
 use crate::ClientResult;
 use crate::RpcError;
 use crate::SolanaRpcClient;
+use crate::WebSocketMethod;
 use crate::impl_websocket_method;
 use crate::nonce_utils;
 use crate::solana_account_decoder::UiAccount;
@@ -30,6 +34,7 @@ use crate::solana_account_decoder::UiAccountEncoding;
 use crate::solana_account_decoder::UiDataSliceConfig;
 use crate::solana_transaction_status::TransactionDetails;
 use crate::solana_transaction_status::UiTransactionEncoding;
+use crate::streams::WatchdogConfig;
 
 #[serde_as]
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, TypedBuilder)]
@@ -158,6 +163,13 @@ where
 	Ok(encoded)
 }
 
+/// Decode and deserialize the `content` which was encoded with the provided
+/// `encoding`.
+///
+/// When `T` is a
+/// [`VersionedTransaction`](solana_transaction::versioned::VersionedTransaction)
+/// both legacy and versioned messages are supported. The version prefix of the
+/// message is used to determine how the account keys should be read.
 pub fn deserialize_and_decode<T: DeserializeOwned>(
 	content: &str,
 	encoding: UiTransactionEncoding,
@@ -391,6 +403,27 @@ pub struct ProgramSubscribeRequest {
 
 impl_websocket_method!(ProgramSubscribeRequest, "program");
 
+impl From<Pubkey> for ProgramSubscribeRequest {
+	fn from(program_id: Pubkey) -> Self {
+		Self::builder().program_id(program_id).build()
+	}
+}
+
+impl From<&Pubkey> for ProgramSubscribeRequest {
+	fn from(program_id: &Pubkey) -> Self {
+		Self::builder().program_id(*program_id).build()
+	}
+}
+
+impl From<(Pubkey, RpcProgramAccountsConfig)> for ProgramSubscribeRequest {
+	fn from((program_id, config): (Pubkey, RpcProgramAccountsConfig)) -> Self {
+		Self::builder()
+			.program_id(program_id)
+			.config(config)
+			.build()
+	}
+}
+
 impl Serialize for ProgramSubscribeRequest {
 	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
 	where
@@ -432,6 +465,108 @@ impl<'de> Deserialize<'de> for ProgramSubscribeRequest {
 	}
 }
 
+/// The request for a `signatureSubscribe` subscription. The node sends a
+/// single notification once the `signature` reaches the commitment of the
+/// `config` and then removes the subscription.
+#[derive(Debug, Clone, Default, PartialEq, Eq, TypedBuilder)]
+pub struct SignatureSubscribeRequest {
+	pub signature: Signature,
+	#[builder(default, setter(strip_option(fallback = config_opt)))]
+	pub config: Option<RpcSignatureSubscribeConfig>,
+}
+
+impl_websocket_method!(SignatureSubscribeRequest, "signature");
+
+impl Serialize for SignatureSubscribeRequest {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		#[serde_as]
+		#[skip_serializing_none]
+		#[derive(Serialize)]
+		#[serde(rename = "SignatureSubscribeRequest")]
+		struct Inner<'a>(
+			#[serde_as(as = "DisplayFromStr")] &'a Signature,
+			&'a Option<RpcSignatureSubscribeConfig>,
+		);
+
+		let inner = Inner(&self.signature, &self.config);
+		Serialize::serialize(&inner, serde_tuple::Serializer(serializer))
+	}
+}
+
+impl<'de> Deserialize<'de> for SignatureSubscribeRequest {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		#[serde_as]
+		#[skip_serializing_none]
+		#[derive(Deserialize)]
+		#[serde(rename = "SignatureSubscribeRequest")]
+		struct Inner(
+			#[serde_as(as = "DisplayFromStr")] Signature,
+			Option<RpcSignatureSubscribeConfig>,
+		);
+
+		let inner: Inner = Deserialize::deserialize(serde_tuple::Deserializer(deserializer))?;
+		Ok(SignatureSubscribeRequest {
+			signature: inner.0,
+			config: inner.1,
+		})
+	}
+}
+
+/// The request for a `slotSubscribe` subscription, which has no parameters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlotSubscribeRequest;
+
+impl_websocket_method!(
+	SlotSubscribeRequest,
+	"slot",
+	Duration::from_millis(DEFAULT_MS_PER_SLOT)
+);
+
+/// The request for a `rootSubscribe` subscription, which has no parameters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RootSubscribeRequest;
+
+impl_websocket_method!(RootSubscribeRequest, "root");
+
+/// A subscription which is recreated on a new connection when no
+/// notification is received for longer than the `watchdog` allows. See
+/// [`SolanaRpcClient::watched_subscribe`].
+///
+/// The `watchdog` defaults to the [`WebSocketMethod::EXPECTED_INTERVAL`] of
+/// the `request`. It is enabled for `slotSubscribe`, which notifies every
+/// slot, and disabled for subscriptions such as `accountSubscribe` which may
+/// legitimately stay silent for any amount of time.
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use wasm_client_solana::rpc_config::SlotSubscribeRequest;
+/// use wasm_client_solana::rpc_config::WatchedSubscribeRequest;
+/// use wasm_client_solana::streams::WatchdogConfig;
+///
+/// let request = WatchedSubscribeRequest::builder()
+/// 	.request(SlotSubscribeRequest)
+/// 	.watchdog(
+/// 		WatchdogConfig::builder()
+/// 			.expected_interval(Duration::from_millis(400))
+/// 			.multiple(25)
+/// 			.build(),
+/// 	)
+/// 	.build();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, TypedBuilder)]
+pub struct WatchedSubscribeRequest<R: WebSocketMethod> {
+	pub request: R,
+	#[builder(default = R::EXPECTED_INTERVAL.map(WatchdogConfig::new), setter(into))]
+	pub watchdog: Option<WatchdogConfig>,
+}
+
 #[serde_as]
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -580,7 +715,7 @@ pub struct RpcContextConfig {
 	pub min_context_slot: Option<Slot>,
 }
 
-#[derive(Debug, Default)]
+#[derive(Clone, Copy, Debug, Default)]
 pub struct GetConfirmedSignaturesForAddress2Config {
 	pub before: Option<Signature>,
 	pub until: Option<Signature>,
@@ -596,6 +731,20 @@ pub struct RpcTransactionLogsConfig {
 	pub commitment: Option<CommitmentConfig>,
 }
 
+impl From<CommitmentConfig> for RpcTransactionLogsConfig {
+	fn from(commitment: CommitmentConfig) -> Self {
+		Self {
+			commitment: Some(commitment),
+		}
+	}
+}
+
+impl From<CommitmentLevel> for RpcTransactionLogsConfig {
+	fn from(commitment: CommitmentLevel) -> Self {
+		CommitmentConfig { commitment }.into()
+	}
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum RpcTransactionLogsFilter {
@@ -688,6 +837,27 @@ impl<'de> Deserialize<'de> for BlockSubscribeRequest {
 	}
 }
 
+/// The request for a [`logsSubscribe`] subscription.
+///
+/// The `config` accepts a commitment directly:
+///
+/// ```rust
+/// use solana_commitment_config::CommitmentLevel;
+/// use wasm_client_solana::rpc_config::LogsSubscribeRequest;
+/// use wasm_client_solana::rpc_config::RpcTransactionLogsFilter;
+///
+/// let request = LogsSubscribeRequest::builder()
+/// 	.filter(RpcTransactionLogsFilter::All)
+/// 	.config(CommitmentLevel::Processed)
+/// 	.build();
+/// ```
+///
+/// `processed` logs arrive the fastest, which suits a live debugger, but may
+/// belong to a transaction which is dropped and never confirms. Use
+/// `confirmed` to only receive logs from transactions which were confirmed,
+/// or reconcile `processed` logs with the signature statuses.
+///
+/// [`logsSubscribe`]: https://docs.solana.com/api/websocket#logssubscribe
 #[derive(Debug, Clone, PartialEq, Eq, TypedBuilder)]
 pub struct LogsSubscribeRequest {
 	pub filter: RpcTransactionLogsFilter,
@@ -730,3 +900,172 @@ impl<'de> Deserialize<'de> for LogsSubscribeRequest {
 		})
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use assert2::check;
+	use solana_message::AddressLookupTableAccount;
+	use solana_message::Message;
+	use solana_message::VersionedMessage;
+	use solana_message::v0;
+	use solana_signer::Signer;
+	use solana_system_interface::instruction::transfer;
+	use solana_transaction::versioned::VersionedTransaction;
+	use test_utils_keypairs::get_wallet_keypair;
+
+	use super::*;
+	use crate::ClientRequest;
+	use crate::VersionedMessageExtension;
+	use crate::VersionedTransactionExtension;
+	use crate::rpc_filter::Memcmp;
+
+	#[test]
+	fn deserialize_legacy_transaction() -> anyhow::Result<()> {
+		let keypair = get_wallet_keypair();
+		let payer = keypair.pubkey();
+		let recipient = Pubkey::new_unique();
+		let instruction = transfer(&payer, &recipient, 1_000);
+		let message = Message::new_with_blockhash(&[instruction], Some(&payer), &Hash::default());
+		let transaction =
+			VersionedTransaction::try_new(VersionedMessage::Legacy(message), &[&keypair])?;
+		let encoded = serialize_and_encode(&transaction, UiTransactionEncoding::Base64)?;
+
+		let decoded: VersionedTransaction =
+			deserialize_and_decode(&encoded, UiTransactionEncoding::Base64)?;
+
+		check!(decoded.message_version() == None);
+		check!(decoded.message.message_version() == None);
+		check!(
+			decoded.message.static_account_keys()
+				== [payer, recipient, solana_sdk_ids::system_program::ID]
+		);
+		check!(decoded == transaction);
+
+		Ok(())
+	}
+
+	#[test]
+	fn deserialize_v0_transaction() -> anyhow::Result<()> {
+		let keypair = get_wallet_keypair();
+		let payer = keypair.pubkey();
+		let recipient = Pubkey::new_unique();
+		let lookup_table = AddressLookupTableAccount {
+			key: Pubkey::new_unique(),
+			addresses: vec![recipient],
+		};
+		let instruction = transfer(&payer, &recipient, 1_000);
+		let message =
+			v0::Message::try_compile(&payer, &[instruction], &[lookup_table], Hash::default())?;
+		let transaction =
+			VersionedTransaction::try_new(VersionedMessage::V0(message), &[&keypair])?;
+		let encoded = serialize_and_encode(&transaction, UiTransactionEncoding::Base58)?;
+		let bytes = bs58::decode(&encoded).into_vec()?;
+
+		// one signature length byte and a single signature precede the message
+		check!(bytes[65] == 0x80);
+
+		let decoded: VersionedTransaction =
+			deserialize_and_decode(&encoded, UiTransactionEncoding::Base58)?;
+
+		check!(decoded.message_version() == Some(0));
+		check!(decoded.message.message_version() == Some(0));
+		check!(
+			decoded.message.static_account_keys() == [payer, solana_sdk_ids::system_program::ID]
+		);
+		let lookups = decoded.message.address_table_lookups().unwrap_or_default();
+		check!(lookups.len() == 1);
+		check!(lookups[0].writable_indexes == [0]);
+		check!(decoded == transaction);
+
+		Ok(())
+	}
+
+	#[test]
+	fn logs_subscribe_request_with_commitment() -> anyhow::Result<()> {
+		let request = LogsSubscribeRequest::builder()
+			.filter(RpcTransactionLogsFilter::All)
+			.config(CommitmentLevel::Processed)
+			.build();
+
+		check!(
+			serde_json::to_value(&request)?
+				== serde_json::json!(["all", { "commitment": "processed" }])
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn program_subscribe_request_with_filters_and_encoding() -> anyhow::Result<()> {
+		let program_id = Pubkey::new_from_array([1; 32]);
+		let config = RpcProgramAccountsConfig::builder()
+			.filters(vec![
+				RpcFilterType::DataSize(165),
+				RpcFilterType::Memcmp(Memcmp::new_base58_encoded(32, &[2; 32])),
+			])
+			.account_config(
+				RpcAccountInfoConfig::builder()
+					.encoding(UiAccountEncoding::JsonParsed)
+					.build(),
+			)
+			.build();
+		let request = ProgramSubscribeRequest::from((program_id, config));
+
+		check!(
+			serde_json::to_value(&request)?
+				== serde_json::json!([
+					program_id.to_string(),
+					{
+						"filters": [
+							{ "dataSize": 165 },
+							{
+								"memcmp": {
+									"offset": 32,
+									"bytes": bs58::encode([2; 32]).into_string(),
+									"encoding": "base58"
+								}
+							}
+						],
+						"encoding": "jsonParsed"
+					}
+				])
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn signature_subscribe_request_with_commitment() -> anyhow::Result<()> {
+		let signature = Signature::from([1; 64]);
+		let request = SignatureSubscribeRequest::builder()
+			.signature(signature)
+			.config(
+				RpcSignatureSubscribeConfig::builder()
+					.commitment(CommitmentConfig::confirmed())
+					.build(),
+			)
+			.build();
+		let value = serde_json::to_value(&request)?;
+
+		check!(value == serde_json::json!([signature.to_string(), { "commitment": "confirmed" }]));
+		check!(serde_json::from_value::<SignatureSubscribeRequest>(value)? == request);
+
+		Ok(())
+	}
+
+	#[test]
+	fn root_subscribe_request_has_no_params() -> anyhow::Result<()> {
+		let request = ClientRequest::builder()
+			.method(RootSubscribeRequest::SUBSCRIBE)
+			.id(1)
+			.params(RootSubscribeRequest)
+			.build();
+
+		check!(
+			serde_json::to_value(&request)?
+				== serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": "rootSubscribe" })
+		);
+
+		Ok(())
+	}
+}
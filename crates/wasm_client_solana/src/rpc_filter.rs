@@ -11,6 +11,9 @@ use spl_token_2022_interface::generic_token_account::GenericTokenAccount;
 use spl_token_2022_interface::state::Account;
 use thiserror::Error;
 
+use crate::rpc_config::RpcKeyedAccount;
+use crate::solana_account_decoder::UiAccountData;
+
 const MAX_DATA_SIZE: usize = 128;
 const MAX_DATA_BASE58_SIZE: usize = 175;
 const MAX_DATA_BASE64_SIZE: usize = 172;
@@ -361,6 +364,44 @@ pub enum TokenAccountsFilter {
 	ProgramId(Pubkey),
 }
 
+/// A client side filter applied to the token accounts returned by
+/// `getTokenAccountsByOwner`.
+///
+/// The RPC node has no way to filter by balance, so all accounts are fetched
+/// and then filtered after the response is received. Accounts which weren't
+/// returned as `jsonParsed` data are treated as having a non-zero balance.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TokenAccountBalanceFilter {
+	/// Keep every account.
+	#[default]
+	All,
+	/// Omit the empty accounts, which is what wallets usually display.
+	NonZero,
+	/// Only keep the empty accounts. These still hold their rent exempt
+	/// lamports, which can be reclaimed by closing them.
+	ZeroBalanceOnly,
+}
+
+impl TokenAccountBalanceFilter {
+	/// Check whether the keyed token account should be kept.
+	pub fn matches(&self, keyed_account: &RpcKeyedAccount) -> bool {
+		match self {
+			Self::All => true,
+			Self::NonZero => !is_zero_balance(keyed_account),
+			Self::ZeroBalanceOnly => is_zero_balance(keyed_account),
+		}
+	}
+}
+
+fn is_zero_balance(keyed_account: &RpcKeyedAccount) -> bool {
+	let UiAccountData::Json(ref parsed_account) = keyed_account.account.data else {
+		return false;
+	};
+
+	parsed_account.parsed["info"]["tokenAmount"]["amount"].as_str() == Some("0")
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -28,6 +28,7 @@ use thiserror::Error;
 
 use crate::Context;
 use crate::impl_websocket_notification;
+pub use crate::rpc_config::RpcKeyedAccount;
 use crate::solana_account_decoder::UiAccount;
 use crate::solana_account_decoder::parse_token::UiTokenAmount;
 use crate::solana_transaction_status::ConfirmedTransactionStatusWithSignature;
@@ -234,15 +235,6 @@ pub struct RpcInflationRate {
 	pub epoch: Epoch,
 }
 
-#[serde_as]
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
-#[serde(rename_all = "camelCase")]
-pub struct RpcKeyedAccount {
-	#[serde_as(as = "DisplayFromStr")]
-	pub pubkey: Pubkey,
-	pub account: UiAccount,
-}
-
 #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
 pub struct SlotInfo {
 	pub slot: Slot,
@@ -250,6 +242,15 @@ pub struct SlotInfo {
 	pub root: Slot,
 }
 
+impl_websocket_notification!(SlotInfo, "slot");
+
+/// The slot which was rooted, from a `rootSubscribe` subscription.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct RootNotification(pub Slot);
+
+impl_websocket_notification!(RootNotification, "root");
+
 #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct SlotTransactionStats {
@@ -335,6 +336,26 @@ pub struct LogsNotificationResponse {
 
 impl_websocket_notification!(LogsNotificationResponse, "logs");
 
+/// The notification received by a `programSubscribe` subscription. The
+/// `value` has the same shape as each account returned by
+/// `getProgramAccounts`, so both can be handled in the same way.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct ProgramNotificationResponse {
+	pub context: Context,
+	pub value: RpcKeyedAccount,
+}
+
+impl_websocket_notification!(ProgramNotificationResponse, "program");
+
+/// The notification received by a `signatureSubscribe` subscription.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct SignatureNotificationResponse {
+	pub context: Context,
+	pub value: RpcSignatureResult,
+}
+
+impl_websocket_notification!(SignatureNotificationResponse, "signature");
+
 #[skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
@@ -462,6 +483,20 @@ impl fmt::Display for RpcVersionInfo {
 	}
 }
 
+/// The health and version of an rpc node, as returned by
+/// [`crate::SolanaRpcClient::node_status`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeStatus {
+	/// Whether the node is up to date with the cluster.
+	pub healthy: bool,
+	/// The number of slots the node is behind the cluster, when it's
+	/// unhealthy and reports it.
+	pub num_slots_behind: Option<u64>,
+	/// The software version of the node.
+	pub version: RpcVersionInfo,
+}
+
 #[serde_as]
 #[skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
@@ -699,3 +734,147 @@ pub struct RpcPrioritizationFee {
 	pub slot: Slot,
 	pub prioritization_fee: u64,
 }
+
+/// The upgrade details of a program deployed with the upgradeable loader.
+#[serde_as]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgramInfo {
+	/// The authority which can upgrade the program.
+	#[serde_as(as = "Option<DisplayFromStr>")]
+	pub authority: Option<Pubkey>,
+	/// The slot the program was last deployed.
+	pub last_deployed_slot: Slot,
+	/// The length of the program executable in bytes.
+	pub data_len: usize,
+	/// Whether the program can no longer be upgraded, since it has no
+	/// upgrade authority.
+	pub is_immutable: bool,
+}
+
+/// The voting health of a validator, computed against the current slot.
+#[serde_as]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorHealth {
+	/// Whether the cluster considers the validator to be delinquent.
+	pub is_delinquent: bool,
+	/// The most recent slot voted on by the validator.
+	pub last_vote: Slot,
+	/// The number of slots between the current slot and the last vote.
+	pub slots_behind: u64,
+	/// The stake, in lamports, delegated to the vote account.
+	pub activated_stake: u64,
+	/// The commission percentage taken from rewards.
+	pub commission: u8,
+}
+
+/// A compact profile of the resources used by a transaction.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceUsage {
+	/// The fee paid, in lamports.
+	pub fee: u64,
+	/// The compute units consumed. This is `None` for older transactions
+	/// which were processed before it was recorded.
+	pub compute_units_consumed: Option<u64>,
+	/// The number of top level instructions.
+	pub num_instructions: usize,
+	/// The number of accounts, including those loaded from address lookup
+	/// tables.
+	pub num_accounts: usize,
+	/// The size of the serialized transaction.
+	pub size_bytes: usize,
+}
+
+/// The inflation rewards credited to a stake account over a range of epochs.
+#[serde_as]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct StakeRewardHistory {
+	#[serde_as(as = "DisplayFromStr")]
+	pub stake_account: Pubkey,
+	/// The rewards in ascending epoch order. Epochs where the account earned
+	/// nothing are skipped.
+	pub rewards: Vec<RpcInflationReward>,
+}
+
+impl StakeRewardHistory {
+	/// The sum of all the rewards, in lamports.
+	pub fn total_amount(&self) -> u64 {
+		self.rewards.iter().map(|reward| reward.amount).sum()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use assert2::check;
+
+	use super::*;
+	use crate::ClientResponse;
+	use crate::SubscriptionResponse;
+	use crate::methods::GetProgramAccountsResponse;
+	use crate::solana_account_decoder::UiAccountData;
+
+	const KEYED_ACCOUNT: &str = r#"{"account":{"data":["11116bv5nS2h3y12kD1yUKeMZvGcKLSjQgX6BeV7u1FrjeJcKfsHPXHRDEHrBesJhZyqnnq9qJeUuF7WHxiuLuL5twc38w2TXNLxnDbjmuR","base58"],"executable":false,"lamports":33594,"owner":"11111111111111111111111111111111","rentEpoch":636,"space":80},"pubkey":"H4vnBqifaSACnKa7acsxstsY1iV1bvJNxsCY7enrd1hq"}"#;
+
+	#[test]
+	fn keyed_account_from_rpc_and_notification() {
+		let rpc_json = format!(r#"{{"jsonrpc":"2.0","result":[{KEYED_ACCOUNT}],"id":1}}"#);
+		let notification_json = format!(
+			r#"{{"jsonrpc":"2.0","method":"programNotification","params":{{"result":{{"context":{{"slot":5208469}},"value":{KEYED_ACCOUNT}}},"subscription":24040}}}}"#
+		);
+
+		let rpc: ClientResponse<GetProgramAccountsResponse> =
+			serde_json::from_str(&rpc_json).unwrap();
+		let notification: SubscriptionResponse<ProgramNotificationResponse> =
+			serde_json::from_str(&notification_json).unwrap();
+		let keyed_account = &rpc.result.keyed_accounts().unwrap()[0];
+
+		check!(keyed_account == &notification.params.result.value);
+		check!(keyed_account.pubkey.to_string() == "H4vnBqifaSACnKa7acsxstsY1iV1bvJNxsCY7enrd1hq");
+		check!(notification.params.result.context.slot == 5_208_469);
+	}
+
+	#[test]
+	fn signature_notification_with_error() {
+		let notification_json = r#"{"jsonrpc":"2.0","method":"signatureNotification","params":{"result":{"context":{"slot":5207624},"value":{"err":{"InstructionError":[0,{"Custom":1}]}}},"subscription":24006}}"#;
+		let notification: SubscriptionResponse<SignatureNotificationResponse> =
+			serde_json::from_str(notification_json).unwrap();
+
+		check!(
+			notification.params.result.value
+				== RpcSignatureResult::ProcessedSignature(ProcessedSignatureResult {
+					err: Some(TransactionError::InstructionError(
+						0,
+						solana_instruction::error::InstructionError::Custom(1)
+					)),
+				})
+		);
+		check!(notification.params.result.context.slot == 5_207_624);
+	}
+
+	#[test]
+	fn json_parsed_program_notification() {
+		let notification_json = r#"{"jsonrpc":"2.0","method":"programNotification","params":{"result":{"context":{"slot":5208469},"value":{"pubkey":"H4vnBqifaSACnKa7acsxstsY1iV1bvJNxsCY7enrd1hq","account":{"data":{"program":"nonce","parsed":{"type":"initialized","info":{"authority":"Bbqg1M4YVVfbhEzwA9SpC9FhsaG83YMTYoR4a8oTDLX","blockhash":"LUaQTmM7WbMRiATdMMHaRGakPtCkc2GHtH57STKXs6k","feeCalculator":{"lamportsPerSignature":"5000"}}},"space":80},"executable":false,"lamports":33594,"owner":"11111111111111111111111111111111","rentEpoch":636,"space":80}}},"subscription":24040}}"#;
+		let notification: SubscriptionResponse<ProgramNotificationResponse> =
+			serde_json::from_str(notification_json).unwrap();
+		let keyed_account = notification.params.result.value;
+		let UiAccountData::Json(parsed) = keyed_account.account.data else {
+			panic!("expected a json parsed account");
+		};
+
+		check!(keyed_account.pubkey.to_string() == "H4vnBqifaSACnKa7acsxstsY1iV1bvJNxsCY7enrd1hq");
+		check!(parsed.program == "nonce");
+		check!(parsed.parsed["info"]["feeCalculator"]["lamportsPerSignature"] == "5000");
+	}
+
+	#[test]
+	fn root_notification() {
+		let notification_json = r#"{"jsonrpc":"2.0","method":"rootNotification","params":{"result":42,"subscription":0}}"#;
+		let notification: SubscriptionResponse<RootNotification> =
+			serde_json::from_str(notification_json).unwrap();
+
+		check!(notification.params.result == RootNotification(42));
+	}
+}
@@ -0,0 +1,147 @@
+//! Sign in with Solana (SIWS) messages for authenticating with a wallet
+//! off-chain.
+
+use serde::Deserialize;
+use serde::Serialize;
+use serde_with::DisplayFromStr;
+use serde_with::serde_as;
+use solana_pubkey::Pubkey;
+use solana_signature::Signature;
+use typed_builder::TypedBuilder;
+use wallet_standard::SolanaSignInInput;
+use wallet_standard::WalletResult;
+use wallet_standard::create_sign_in_message_text;
+
+/// A sign in with Solana message.
+///
+/// The message text follows the [EIP-4361] format used by the wallet standard
+/// `solana:signIn` feature, so the same bytes are produced when the message is
+/// signed in the wallet and when it is verified here.
+///
+/// [EIP-4361]: https://eips.ethereum.org/EIPS/eip-4361
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+pub struct SiwsMessage {
+	/// The domain requesting the sign in.
+	#[builder(setter(into))]
+	pub domain: String,
+	/// The address of the account signing in.
+	#[serde_as(as = "DisplayFromStr")]
+	pub address: Pubkey,
+	/// A human readable statement shown to the user.
+	#[builder(default, setter(into, strip_option))]
+	pub statement: Option<String>,
+	#[builder(default, setter(into, strip_option))]
+	pub uri: Option<String>,
+	#[builder(default, setter(into, strip_option))]
+	pub version: Option<String>,
+	#[builder(default, setter(into, strip_option))]
+	pub chain_id: Option<String>,
+	/// A random value used to prevent replay attacks.
+	#[builder(default, setter(into, strip_option))]
+	pub nonce: Option<String>,
+	/// The ISO 8601 datetime when the message was created.
+	#[builder(default, setter(into, strip_option))]
+	pub issued_at: Option<String>,
+	#[builder(default, setter(into, strip_option))]
+	pub expiration_time: Option<String>,
+	#[builder(default, setter(into, strip_option))]
+	pub not_before: Option<String>,
+	#[builder(default, setter(into, strip_option))]
+	pub request_id: Option<String>,
+	#[builder(default, setter(into, strip_option))]
+	pub resources: Option<Vec<String>>,
+}
+
+impl SiwsMessage {
+	/// The text which is signed by the wallet.
+	pub fn text(&self) -> WalletResult<String> {
+		create_sign_in_message_text(&self.into())
+	}
+
+	/// The bytes which are signed by the wallet.
+	pub fn to_bytes(&self) -> WalletResult<Vec<u8>> {
+		Ok(self.text()?.into_bytes())
+	}
+
+	/// Check that the `signature` was created by the `address` signing this
+	/// message.
+	pub fn verify(&self, signature: &Signature) -> WalletResult<bool> {
+		Ok(signature.verify(self.address.as_ref(), &self.to_bytes()?))
+	}
+}
+
+impl From<&SiwsMessage> for SolanaSignInInput {
+	fn from(message: &SiwsMessage) -> Self {
+		Self {
+			domain: Some(message.domain.clone()),
+			address: Some(message.address.to_string()),
+			statement: message.statement.clone(),
+			uri: message.uri.clone(),
+			version: message.version.clone(),
+			chain_id: message.chain_id.clone(),
+			nonce: message.nonce.clone(),
+			issued_at: message.issued_at.clone(),
+			expiration_time: message.expiration_time.clone(),
+			not_before: message.not_before.clone(),
+			request_id: message.request_id.clone(),
+			resources: message.resources.clone(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use assert2::check;
+	use solana_keypair::Keypair;
+	use solana_signer::Signer;
+
+	use super::*;
+
+	fn message(keypair: &Keypair) -> SiwsMessage {
+		SiwsMessage::builder()
+			.domain("example.com")
+			.address(keypair.pubkey())
+			.statement("Sign in to example.com")
+			.nonce("32891756")
+			.issued_at("2024-01-01T00:00:00.000Z")
+			.build()
+	}
+
+	#[test]
+	fn message_text() -> anyhow::Result<()> {
+		let keypair = Keypair::new_from_array([7; 32]);
+		let text = message(&keypair).text()?;
+
+		insta::assert_snapshot!(text, @r"
+		example.com wants you to sign in with your Solana account:
+		GmaDrppBC7P5ARKV8g3djiwP89vz1jLK23V2GBjuAEGB
+
+		Sign in to example.com
+
+		Nonce: 32891756
+		Issued At: 2024-01-01T00:00:00.000Z
+		");
+
+		Ok(())
+	}
+
+	#[test]
+	fn verify_signature() -> anyhow::Result<()> {
+		let keypair = Keypair::new_from_array([7; 32]);
+		let message = message(&keypair);
+		let signature = keypair.sign_message(&message.to_bytes()?);
+
+		insta::assert_snapshot!(signature, @"46opVzxPfi6k6jaq7yxP2EjTjpKzQ772kiVL5zGmBStcdUVScmHQbwEhMjjaZcNqSdbKesDcQevAkS7xkMfHwCbV");
+		check!(message.verify(&signature)?);
+
+		let other = SiwsMessage {
+			nonce: Some("00000000".into()),
+			..message
+		};
+		check!(!other.verify(&signature)?);
+
+		Ok(())
+	}
+}
@@ -0,0 +1,194 @@
+//! Estimate the wall clock time of recent slots without a request per slot.
+
+use std::pin::pin;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use futures::StreamExt;
+use futures::future::Either;
+use futures::future::select;
+use futures_timer::Delay;
+use solana_clock::DEFAULT_MS_PER_SLOT;
+use solana_clock::Slot;
+use solana_clock::UnixTimestamp;
+
+use crate::ClientResult;
+use crate::SolanaRpcClient;
+use crate::rpc_response::SlotInfo;
+
+/// The weight given to each new slot duration measurement. Smaller values
+/// smooth out skipped slots and irregular block times.
+const SLOT_DURATION_SMOOTHING: f64 = 0.2;
+
+/// A running estimate of the wall clock time of each slot.
+///
+/// The clock is anchored to a slot with a known block time, and extrapolates
+/// to other slots using the average slot duration measured between anchors.
+/// Until two block times have been recorded, the target slot duration of
+/// [`DEFAULT_MS_PER_SLOT`] is used.
+///
+/// The times are estimates: block times are only accurate to the second and
+/// slot durations vary with network conditions. The estimate improves as more
+/// block times are recorded, which [`SlotClock::run`] does periodically.
+///
+/// The clock is cheap to clone and all clones share the same estimate, so it
+/// can be updated in a background task while being read while rendering.
+#[derive(Clone, Debug, Default)]
+pub struct SlotClock(Arc<Mutex<SlotClockState>>);
+
+#[derive(Debug, Default)]
+struct SlotClockState {
+	anchor: Option<(Slot, UnixTimestamp)>,
+	slot_duration_ms: Option<f64>,
+	latest_slot: Option<Slot>,
+	latest_root: Option<Slot>,
+}
+
+impl SlotClock {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// The latest slot received by the clock.
+	pub fn latest_slot(&self) -> Option<Slot> {
+		self.0.lock().unwrap().latest_slot
+	}
+
+	/// The estimated duration of a slot.
+	pub fn slot_duration(&self) -> Duration {
+		let slot_duration_ms = self.0.lock().unwrap().slot_duration_ms;
+
+		slot_duration_ms.map_or(Duration::from_millis(DEFAULT_MS_PER_SLOT), |ms| {
+			Duration::from_secs_f64(ms / 1000.0)
+		})
+	}
+
+	/// Record a slot notification.
+	pub fn record_slot(&self, slot: Slot, root: Slot) {
+		let mut state = self.0.lock().unwrap();
+
+		state.latest_slot = state.latest_slot.max(Some(slot));
+		state.latest_root = state.latest_root.max(Some(root));
+	}
+
+	/// Record the block time of a `slot`, which corrects the drift of the
+	/// estimate.
+	pub fn record_block_time(&self, slot: Slot, timestamp: UnixTimestamp) {
+		let mut state = self.0.lock().unwrap();
+
+		if let Some((anchor_slot, anchor_timestamp)) = state.anchor {
+			if slot <= anchor_slot {
+				return;
+			}
+
+			let elapsed_ms = (timestamp - anchor_timestamp) as f64 * 1000.0;
+			let measured = elapsed_ms / (slot - anchor_slot) as f64;
+
+			if measured > 0.0 {
+				state.slot_duration_ms = Some(state.slot_duration_ms.map_or(measured, |current| {
+					current + (measured - current) * SLOT_DURATION_SMOOTHING
+				}));
+			}
+		}
+
+		state.anchor = Some((slot, timestamp));
+	}
+
+	/// The approximate unix timestamp of the `slot`. Returns `None` until a
+	/// block time has been recorded.
+	pub fn slot_to_approx_time(&self, slot: Slot) -> Option<UnixTimestamp> {
+		let state = self.0.lock().unwrap();
+		let (anchor_slot, anchor_timestamp) = state.anchor?;
+		let slot_duration_ms = state.slot_duration_ms.unwrap_or(DEFAULT_MS_PER_SLOT as f64);
+		let offset_ms = (i128::from(slot) - i128::from(anchor_slot)) as f64 * slot_duration_ms;
+
+		Some(anchor_timestamp + (offset_ms / 1000.0).round() as UnixTimestamp)
+	}
+
+	/// Keep the clock up to date with a `slotSubscribe` subscription and fetch
+	/// the block time of the latest root every `refresh_interval`.
+	///
+	/// This only returns once the subscription ends, so it should be spawned
+	/// as a background task with [`crate::spawn_local`].
+	pub async fn run(&self, rpc: &SolanaRpcClient, refresh_interval: Duration) -> ClientResult<()> {
+		let slot = rpc.get_slot().await?;
+		self.record_slot(slot, slot);
+		self.refresh(rpc, slot).await;
+
+		let mut slots = pin!(rpc.slot_subscribe().await?);
+		let mut refresh = Delay::new(refresh_interval);
+
+		loop {
+			match select(slots.next(), &mut refresh).await {
+				Either::Left((Some(response), _)) => {
+					let SlotInfo { slot, root, .. } = response.params.result;
+					self.record_slot(slot, root);
+				}
+				Either::Left((None, _)) => return Ok(()),
+				Either::Right(((), _)) => {
+					refresh.reset(refresh_interval);
+					let root = self.0.lock().unwrap().latest_root;
+
+					if let Some(root) = root {
+						self.refresh(rpc, root).await;
+					}
+				}
+			}
+		}
+	}
+
+	/// Record the block time of the `slot`. Block times can be missing for
+	/// skipped slots, in which case the estimate is left unchanged.
+	async fn refresh(&self, rpc: &SolanaRpcClient, slot: Slot) {
+		if let Ok(Some(timestamp)) = rpc.get_block_time(slot).await {
+			self.record_block_time(slot, timestamp);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use assert2::check;
+
+	use super::*;
+
+	#[test]
+	fn estimate_requires_a_block_time() {
+		let clock = SlotClock::new();
+		clock.record_slot(100, 68);
+
+		check!(clock.slot_to_approx_time(100).is_none());
+		check!(clock.latest_slot() == Some(100));
+		check!(clock.slot_duration() == Duration::from_millis(400));
+	}
+
+	#[test]
+	fn estimate_extrapolates_from_the_anchor() {
+		let clock = SlotClock::new();
+		clock.record_block_time(1000, 1_700_000_000);
+
+		check!(clock.slot_to_approx_time(1000) == Some(1_700_000_000));
+		check!(clock.slot_to_approx_time(1010) == Some(1_700_000_004));
+		check!(clock.slot_to_approx_time(990) == Some(1_699_999_996));
+	}
+
+	#[test]
+	fn block_times_correct_the_slot_duration() {
+		let clock = SlotClock::new();
+		clock.record_block_time(1000, 1_700_000_000);
+		// 100 slots in 50 seconds is 500ms per slot
+		clock.record_block_time(1100, 1_700_000_050);
+
+		check!(clock.slot_duration() == Duration::from_millis(500));
+		check!(clock.slot_to_approx_time(1120) == Some(1_700_000_060));
+
+		// older block times are ignored
+		clock.record_block_time(900, 1_600_000_000);
+		check!(clock.slot_to_approx_time(1100) == Some(1_700_000_050));
+
+		// 100 slots in 40 seconds moves the estimate towards 400ms per slot
+		clock.record_block_time(1200, 1_700_000_090);
+		check!(clock.slot_duration() == Duration::from_millis(480));
+	}
+}
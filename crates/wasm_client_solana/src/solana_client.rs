@@ -1,37 +1,90 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::pin::pin;
+use std::str::FromStr;
 use std::sync::Arc;
+use std::sync::Mutex;
 use std::time::Duration;
 
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use futures::Stream;
+use futures::StreamExt;
+use futures::TryStreamExt;
+use futures::future;
+use futures::future::Either;
+use futures::future::select;
+use futures::stream;
 use futures_timer::Delay;
 use serde::de::DeserializeOwned;
+use serde_json::Value;
 use solana_account::Account;
+use solana_address_lookup_table_interface::state::AddressLookupTable;
+use solana_clock::Clock;
+use solana_clock::DEFAULT_MS_PER_SLOT;
 use solana_clock::Epoch;
 use solana_clock::Slot;
 use solana_clock::UnixTimestamp;
 use solana_commitment_config::CommitmentConfig;
 use solana_commitment_config::CommitmentLevel;
+use solana_compute_budget_interface::ComputeBudgetInstruction;
 use solana_epoch_info::EpochInfo;
 use solana_epoch_schedule::EpochSchedule;
 use solana_hash::Hash;
-use solana_message::Message;
+use solana_instruction::Instruction;
+use solana_instruction::error::InstructionError;
+use solana_loader_v3_interface::state::UpgradeableLoaderState;
+use solana_message::AddressLookupTableAccount;
+use solana_message::VersionedMessage;
+use solana_message::v0;
+use solana_message::v0::LoadedAddresses;
 use solana_pubkey::Pubkey;
+use solana_rent::Rent;
+use solana_sdk_ids::bpf_loader_upgradeable;
+use solana_sdk_ids::sysvar;
 use solana_signature::Signature;
+use solana_slot_hashes::SlotHashes;
+use solana_stake_interface::stake_history::StakeHistory;
+use solana_stake_interface::state::NEW_WARMUP_COOLDOWN_RATE;
+use solana_stake_interface::state::StakeStateV2;
 use solana_transaction::versioned::VersionedTransaction;
+use solana_transaction_error::TransactionResult;
+use typed_builder::TypedBuilder;
 
+use crate::COMPUTE_UNIT_MAX_LIMIT;
 use crate::ClientError;
 use crate::ClientResponse;
 use crate::ClientResult;
+use crate::DEFAULT_MULTIPLE_ACCOUNTS_CONCURRENCY;
 use crate::HttpProvider;
+use crate::LookupTableError;
+use crate::MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS;
+use crate::MAX_INFLATION_REWARD_ADDRESSES;
+use crate::MAX_MULTIPLE_ACCOUNTS;
+use crate::MAX_PRIORITIZATION_FEE_ACCOUNTS;
 use crate::MAX_RETRIES;
+use crate::MAX_SIGNATURES_FOR_ADDRESS_LIMIT;
+use crate::MergedSubscription;
+use crate::MethodNameTransform;
+use crate::RpcBatch;
 use crate::RpcError;
+use crate::RpcErrorResponse;
 use crate::RpcProvider;
 use crate::SLEEP_MS;
 use crate::Subscription;
+use crate::SubscriptionResponse;
+use crate::UNPARSABLE_ACCOUNT_PROGRAM;
 use crate::WebSocketProvider;
+use crate::extensions::VersionedTransactionExtension;
+use crate::extensions::wasm_safe_instruction_clone;
 use crate::methods::*;
+use crate::priority_fees::PriorityFeeConfig;
+use crate::priority_fees::fee_percentile;
 use crate::rpc_config::BlockSubscribeRequest;
 use crate::rpc_config::GetConfirmedSignaturesForAddress2Config;
 use crate::rpc_config::LogsSubscribeRequest;
 use crate::rpc_config::ProgramSubscribeRequest;
+use crate::rpc_config::RootSubscribeRequest;
 use crate::rpc_config::RpcAccountInfoConfig;
 use crate::rpc_config::RpcBlockConfig;
 use crate::rpc_config::RpcBlockProductionConfig;
@@ -43,16 +96,31 @@ use crate::rpc_config::RpcLargestAccountsConfig;
 use crate::rpc_config::RpcLeaderScheduleConfig;
 use crate::rpc_config::RpcProgramAccountsConfig;
 use crate::rpc_config::RpcSendTransactionConfig;
+use crate::rpc_config::RpcSignatureStatusConfig;
+use crate::rpc_config::RpcSignatureSubscribeConfig;
 use crate::rpc_config::RpcSignaturesForAddressConfig;
 use crate::rpc_config::RpcSimulateTransactionConfig;
 use crate::rpc_config::RpcSupplyConfig;
 use crate::rpc_config::RpcTokenAccountsFilter;
 use crate::rpc_config::RpcTransactionConfig;
+use crate::rpc_config::RpcTransactionLogsConfig;
+use crate::rpc_config::RpcTransactionLogsFilter;
+use crate::rpc_config::SignatureSubscribeRequest;
+use crate::rpc_config::SlotSubscribeRequest;
+use crate::rpc_config::WatchedSubscribeRequest;
+use crate::rpc_filter::RpcFilterType;
+use crate::rpc_filter::TokenAccountBalanceFilter;
 use crate::rpc_filter::TokenAccountsFilter;
 use crate::rpc_response::BlockNotificationResponse;
 use crate::rpc_response::LogsNotificationResponse;
+use crate::rpc_response::NodeStatus;
+use crate::rpc_response::ProgramInfo;
+use crate::rpc_response::ProgramNotificationResponse;
+use crate::rpc_response::ResourceUsage;
+use crate::rpc_response::RootNotification;
 use crate::rpc_response::RpcAccountBalance;
 use crate::rpc_response::RpcBlockProduction;
+use crate::rpc_response::RpcBlockhash;
 use crate::rpc_response::RpcConfirmedTransactionStatusWithSignature;
 use crate::rpc_response::RpcInflationGovernor;
 use crate::rpc_response::RpcInflationRate;
@@ -60,21 +128,43 @@ use crate::rpc_response::RpcInflationReward;
 use crate::rpc_response::RpcLeaderSchedule;
 use crate::rpc_response::RpcPerfSample;
 use crate::rpc_response::RpcPrioritizationFee;
+use crate::rpc_response::RpcSignatureResult;
 use crate::rpc_response::RpcSupply;
 use crate::rpc_response::RpcVersionInfo;
 use crate::rpc_response::RpcVoteAccountStatus;
+use crate::rpc_response::SignatureNotificationResponse;
+use crate::rpc_response::SlotInfo;
+use crate::rpc_response::StakeRewardHistory;
+use crate::rpc_response::ValidatorHealth;
+use crate::solana_account_decoder::UiAccount;
 use crate::solana_account_decoder::UiAccountData;
 use crate::solana_account_decoder::UiAccountEncoding;
+use crate::solana_account_decoder::UiDataSliceConfig;
+use crate::solana_account_decoder::parse_account_data::AccountAdditionalDataV3;
+use crate::solana_account_decoder::parse_account_data::ParseAccountError;
+use crate::solana_account_decoder::parse_account_data::ParsedAccount;
+use crate::solana_account_decoder::parse_account_data::SplTokenAdditionalDataV2;
+use crate::solana_account_decoder::parse_account_data::parse_account_data_v3;
 use crate::solana_account_decoder::parse_address_lookup_table::LookupTableAccountType;
 use crate::solana_account_decoder::parse_address_lookup_table::parse_address_lookup_table;
+use crate::solana_account_decoder::parse_sysvar::SysvarAccountType;
+use crate::solana_account_decoder::parse_sysvar::parse_sysvar;
 use crate::solana_account_decoder::parse_token::TokenAccountType;
 use crate::solana_account_decoder::parse_token::UiTokenAccount;
 use crate::solana_account_decoder::parse_token::UiTokenAmount;
+use crate::solana_account_decoder::parse_token::get_token_account_mint;
+use crate::solana_account_decoder::parse_token::is_known_spl_token_id;
 use crate::solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;
 use crate::solana_transaction_status::TransactionConfirmationStatus;
+use crate::solana_transaction_status::TransactionDetails;
 use crate::solana_transaction_status::TransactionStatus;
 use crate::solana_transaction_status::UiConfirmedBlock;
 use crate::solana_transaction_status::UiTransactionEncoding;
+use crate::stake_cooldown::StakeCooldownEstimate;
+use crate::stake_cooldown::estimate_cooldown_epochs;
+use crate::streams::ReconnectConfig;
+use crate::streams::SolanaStreamExt;
+use crate::streams::SubscriptionEvent;
 
 /// A client of a remote Solana node.
 ///
@@ -133,6 +223,9 @@ pub struct SolanaRpcClient {
 	#[debug(skip)]
 	provider: Arc<dyn RpcProvider + Send + Sync + 'static>,
 	ws: WebSocketProvider,
+	method_name: MethodNameTransform,
+	data_slice: Option<UiDataSliceConfig>,
+	rent_cache: Arc<Mutex<Option<Rent>>>,
 }
 
 impl<S: Into<String>> From<S> for SolanaRpcClient {
@@ -147,6 +240,147 @@ impl From<&SolanaRpcClient> for SolanaRpcClient {
 	}
 }
 
+/// How [`SolanaRpcClient::send_and_confirm_transaction_with_rebroadcast`]
+/// resends a signed transaction until it is confirmed or its blockhash
+/// expires.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, TypedBuilder)]
+pub struct RebroadcastPolicy {
+	/// The time to wait for the transaction before checking its status and
+	/// sending it again.
+	#[builder(default = Duration::from_millis(2_000))]
+	pub interval: Duration,
+	/// The maximum number of times the transaction is sent again. When
+	/// reached, the client keeps waiting for the confirmation without
+	/// resending. Rebroadcasts until the blockhash expires when `None`.
+	#[builder(default, setter(strip_option))]
+	pub max_rebroadcasts: Option<usize>,
+}
+
+impl Default for RebroadcastPolicy {
+	fn default() -> Self {
+		Self::builder().build()
+	}
+}
+
+/// How [`SolanaRpcClient::confirm_transaction_with_config`] waits for a
+/// transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, TypedBuilder)]
+pub struct ConfirmTransactionConfig {
+	/// The commitment the transaction must reach. The client commitment is
+	/// used when `None`.
+	#[builder(default, setter(strip_option))]
+	pub commitment: Option<CommitmentConfig>,
+	/// The maximum time to poll the signature status before giving up with a
+	/// [`ClientError::ConfirmationTimeout`].
+	#[builder(default = Duration::from_millis(SLEEP_MS * MAX_RETRIES as u64))]
+	pub timeout: Duration,
+}
+
+impl Default for ConfirmTransactionConfig {
+	fn default() -> Self {
+		Self::builder().build()
+	}
+}
+
+/// Build a [`SolanaRpcClient`] for an rpc provider which needs more than an
+/// endpoint, such as an api key header.
+///
+/// The websocket endpoint is inferred from the http endpoint unless it's
+/// set, with `http` replaced by `ws` and `https` by `wss`. The headers and
+/// timeout only apply to http requests.
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use solana_commitment_config::CommitmentConfig;
+/// use wasm_client_solana::SolanaRpcClient;
+///
+/// # fn run() -> wasm_client_solana::ClientResult<()> {
+/// let rpc = SolanaRpcClient::builder("https://rpc.example.com")
+/// 	.ws_endpoint("wss://ws.example.com")
+/// 	.header("x-api-key", "<api key>")
+/// 	.commitment(CommitmentConfig::finalized())
+/// 	.timeout(Duration::from_secs(10))
+/// 	.build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SolanaRpcClientBuilder {
+	http_endpoint: String,
+	ws_endpoint: Option<String>,
+	headers: Vec<(String, String)>,
+	commitment_config: CommitmentConfig,
+	timeout: Option<Duration>,
+}
+
+impl SolanaRpcClientBuilder {
+	pub fn new(http_endpoint: impl Into<String>) -> Self {
+		Self {
+			http_endpoint: http_endpoint.into(),
+			ws_endpoint: None,
+			headers: vec![],
+			commitment_config: CommitmentConfig::confirmed(),
+			timeout: None,
+		}
+	}
+
+	/// Connect to the `ws_endpoint` for subscriptions, rather than inferring
+	/// it from the http endpoint.
+	#[must_use]
+	pub fn ws_endpoint(mut self, ws_endpoint: impl Into<String>) -> Self {
+		self.ws_endpoint = Some(ws_endpoint.into());
+		self
+	}
+
+	/// Send the header with every http request.
+	#[must_use]
+	pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+		self.headers.push((name.into(), value.into()));
+		self
+	}
+
+	/// The commitment used by every method which doesn't specify its own.
+	#[must_use]
+	pub fn commitment(mut self, commitment_config: CommitmentConfig) -> Self {
+		self.commitment_config = commitment_config;
+		self
+	}
+
+	/// Fail http requests with [`ClientError::Timeout`] when they don't
+	/// complete within the `timeout`.
+	#[must_use]
+	pub fn timeout(mut self, timeout: Duration) -> Self {
+		self.timeout = Some(timeout);
+		self
+	}
+
+	/// Create the client. An error is returned when an endpoint isn't a valid
+	/// url or a header is invalid.
+	pub fn build(self) -> ClientResult<SolanaRpcClient> {
+		validate_endpoint(&self.http_endpoint, &["http", "https"])?;
+
+		if let Some(ws_endpoint) = &self.ws_endpoint {
+			validate_endpoint(ws_endpoint, &["ws", "wss"])?;
+		}
+
+		let mut provider = HttpProvider::new(&self.http_endpoint).with_headers(self.headers)?;
+
+		if let Some(timeout) = self.timeout {
+			provider = provider.with_timeout(timeout);
+		}
+
+		Ok(SolanaRpcClient {
+			provider: Arc::new(provider),
+			commitment_config: self.commitment_config,
+			ws: WebSocketProvider::new(self.ws_endpoint.unwrap_or(self.http_endpoint)),
+			method_name: MethodNameTransform::default(),
+			data_slice: None,
+			rent_cache: Arc::default(),
+		})
+	}
+}
+
 impl SolanaRpcClient {
 	/// Create an HTTP `SolanaRpcClient`.
 	///
@@ -162,6 +396,9 @@ impl SolanaRpcClient {
 			provider: Arc::new(HttpProvider::new(endpoint)),
 			commitment_config: CommitmentConfig::confirmed(),
 			ws: WebSocketProvider::new(endpoint),
+			method_name: MethodNameTransform::default(),
+			data_slice: None,
+			rent_cache: Arc::default(),
 		}
 	}
 
@@ -181,6 +418,9 @@ impl SolanaRpcClient {
 			provider: Arc::new(HttpProvider::new(endpoint)),
 			commitment_config,
 			ws: WebSocketProvider::new(endpoint),
+			method_name: MethodNameTransform::default(),
+			data_slice: None,
+			rent_cache: Arc::default(),
 		}
 	}
 
@@ -193,9 +433,17 @@ impl SolanaRpcClient {
 			provider: Arc::new(HttpProvider::new(http_endpoint)),
 			commitment_config,
 			ws: WebSocketProvider::new(ws_endpoint),
+			method_name: MethodNameTransform::default(),
+			data_slice: None,
+			rent_cache: Arc::default(),
 		}
 	}
 
+	/// Create a [`SolanaRpcClientBuilder`] for the `http_endpoint`.
+	pub fn builder(http_endpoint: impl Into<String>) -> SolanaRpcClientBuilder {
+		SolanaRpcClientBuilder::new(http_endpoint)
+	}
+
 	/// Create a new rpc from a custom provider.
 	pub fn new_with_provider(
 		provider: Arc<dyn RpcProvider + Send + Sync + 'static>,
@@ -206,6 +454,107 @@ impl SolanaRpcClient {
 			provider,
 			commitment_config,
 			ws: WebSocketProvider::new(endpoint),
+			method_name: MethodNameTransform::default(),
+			data_slice: None,
+			rent_cache: Arc::default(),
+		}
+	}
+
+	/// Rename every json rpc method with the `transform` before it is sent.
+	///
+	/// This allows the client to be used with forks and other solana
+	/// compatible clusters which expose the same rpc under a namespace or
+	/// with renamed methods. It applies to the http methods as well as the
+	/// websocket subscribe, unsubscribe and notification methods.
+	///
+	/// ```rust
+	/// use wasm_client_solana::SolanaRpcClient;
+	///
+	/// let rpc = SolanaRpcClient::new("https://rpc.example.com")
+	/// 	.with_method_name_transform(|method| format!("svm_{method}"));
+	/// ```
+	#[must_use]
+	pub fn with_method_name_transform(
+		mut self,
+		transform: impl Fn(&str) -> String + Send + Sync + 'static,
+	) -> Self {
+		let transform = MethodNameTransform::new(transform);
+		self.ws = self.ws.with_method_name_transform(transform.clone());
+		self.method_name = transform;
+		self
+	}
+
+	/// Only fetch the `data_slice` of each account, unless a request
+	/// specifies its own data slice.
+	///
+	/// This reduces the bandwidth used when only the start of large accounts
+	/// is needed, such as a discriminator and a few fields. It applies to
+	/// [`SolanaRpcClient::get_account`],
+	/// [`SolanaRpcClient::get_multiple_accounts`],
+	/// [`SolanaRpcClient::get_program_accounts`] and their variants. Methods
+	/// which decode accounts for you, such as
+	/// [`SolanaRpcClient::get_nonce_account_state`], always fetch the full
+	/// account data.
+	///
+	/// Parsing a slice of account data usually fails, so the default data
+	/// slice is ignored for requests with the
+	/// [`UiAccountEncoding::JsonParsed`] encoding.
+	///
+	/// ```rust
+	/// use wasm_client_solana::SolanaRpcClient;
+	/// use wasm_client_solana::solana_account_decoder::UiDataSliceConfig;
+	///
+	/// let rpc = SolanaRpcClient::new("https://rpc.example.com").with_data_slice(UiDataSliceConfig {
+	/// 	offset: 0,
+	/// 	length: 8,
+	/// });
+	/// ```
+	#[must_use]
+	pub fn with_data_slice(mut self, data_slice: UiDataSliceConfig) -> Self {
+		self.data_slice = Some(data_slice);
+		self
+	}
+
+	/// A clone of the client with a different default commitment, which is
+	/// used by every method that isn't given an explicit commitment.
+	///
+	/// The clone is cheap and shares the http provider and websocket
+	/// connection of this client.
+	///
+	/// ```rust
+	/// use solana_commitment_config::CommitmentConfig;
+	/// use wasm_client_solana::SolanaRpcClient;
+	///
+	/// let rpc = SolanaRpcClient::new("https://rpc.example.com");
+	/// let finalized_rpc = rpc.with_commitment(CommitmentConfig::finalized());
+	/// ```
+	#[must_use]
+	pub fn with_commitment(&self, commitment_config: CommitmentConfig) -> Self {
+		Self {
+			commitment_config,
+			..self.clone()
+		}
+	}
+
+	/// The default data slice of account fetches, see
+	/// [`SolanaRpcClient::with_data_slice`].
+	pub fn data_slice(&self) -> Option<UiDataSliceConfig> {
+		self.data_slice
+	}
+
+	/// Use the client data slice when the `config` doesn't specify one and
+	/// isn't `jsonParsed`.
+	pub(crate) fn with_default_data_slice(
+		&self,
+		config: RpcAccountInfoConfig,
+	) -> RpcAccountInfoConfig {
+		if config.data_slice.is_some() || config.encoding == Some(UiAccountEncoding::JsonParsed) {
+			return config;
+		}
+
+		RpcAccountInfoConfig {
+			data_slice: self.data_slice,
+			..config
 		}
 	}
 
@@ -225,28 +574,69 @@ impl SolanaRpcClient {
 	async fn send<T: HttpMethod, R: DeserializeOwned>(&self, request: T) -> ClientResult<R> {
 		let result = self
 			.provider
-			.send(
-				T::NAME,
-				serde_json::to_value(request)
-					.map_err(|error| ClientError::Other(error.to_string()))?,
-			)
+			.send(&self.method_name.apply(T::NAME), request_params(request)?)
 			.await?;
 
-		match serde_json::from_value::<R>(result.clone()) {
-			Ok(response) => Ok(response),
-			_ => {
-				match serde_json::from_value::<RpcError>(result) {
-					Ok(error) => Err(error.into()),
-					Err(error) => Err(ClientError::Other(error.to_string())),
-				}
-			}
-		}
+		parse_response(result)
+	}
+
+	/// Send the `requests` in a single JSON-RPC batch. The responses are in
+	/// the same order as the `requests`.
+	pub(crate) async fn send_batch(
+		&self,
+		requests: Vec<(&'static str, Value)>,
+	) -> ClientResult<Vec<Value>> {
+		let method_names: Vec<_> = requests
+			.iter()
+			.map(|(name, _)| self.method_name.apply(name))
+			.collect();
+		let requests = method_names
+			.iter()
+			.zip(requests)
+			.map(|(method, (_, params))| (method.as_ref(), params))
+			.collect();
+
+		self.provider.send_batch(requests).await
+	}
+
+	/// Start a batch of requests which are sent to the node in a single
+	/// JSON-RPC batch.
+	///
+	/// ```rust
+	/// use solana_pubkey::pubkey;
+	/// use wasm_client_solana::SolanaRpcClient;
+	///
+	/// # async fn run() -> wasm_client_solana::ClientResult<()> {
+	/// let rpc = SolanaRpcClient::new("https://rpc.example.com");
+	/// let mut batch = rpc.batch();
+	/// let account = batch.add_get_account(&pubkey!("SysvarC1ock11111111111111111111111111111111"));
+	/// let balance = batch.add_get_balance(&pubkey!("11111111111111111111111111111111"));
+	/// let responses = batch.send().await?;
+	///
+	/// let account = responses.get(account)?;
+	/// let balance = responses.get(balance)?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn batch(&self) -> RpcBatch<'_> {
+		RpcBatch::new(self)
 	}
 
 	pub async fn get_account_with_config(
 		&self,
 		pubkey: &Pubkey,
 		config: RpcAccountInfoConfig,
+	) -> ClientResult<Option<Account>> {
+		self.get_full_account_with_config(pubkey, self.with_default_data_slice(config))
+			.await
+	}
+
+	/// The same as [`SolanaRpcClient::get_account_with_config`] without the
+	/// default data slice of the client.
+	pub(crate) async fn get_full_account_with_config(
+		&self,
+		pubkey: &Pubkey,
+		config: RpcAccountInfoConfig,
 	) -> ClientResult<Option<Account>> {
 		let request = GetAccountInfoRequest::builder()
 			.pubkey(*pubkey)
@@ -289,6 +679,154 @@ impl SolanaRpcClient {
 		Ok(self.get_account(pubkey).await?.data)
 	}
 
+	/// Get the account with all of its data, even when the client has a
+	/// default data slice. Used by the methods which decode the account.
+	pub(crate) async fn get_full_account(&self, pubkey: &Pubkey) -> ClientResult<Account> {
+		self.get_full_account_with_config(
+			pubkey,
+			RpcAccountInfoConfig {
+				commitment: Some(self.commitment_config()),
+				encoding: Some(UiAccountEncoding::Base64),
+				..Default::default()
+			},
+		)
+		.await?
+		.ok_or_else(|| RpcError::new(format!("Account {pubkey} not found.")).into())
+	}
+
+	/// Get the upgrade authority and last deployed slot of a program deployed
+	/// with the upgradeable loader.
+	///
+	/// This fetches the program account and then the program data account it
+	/// points to.
+	pub async fn get_program_info(&self, program_id: &Pubkey) -> ClientResult<ProgramInfo> {
+		let program = self.get_full_account(program_id).await?;
+
+		if program.owner != bpf_loader_upgradeable::ID {
+			return Err(ClientError::Other(format!(
+				"Program {program_id} is not owned by the upgradeable loader"
+			)));
+		}
+
+		let Ok(UpgradeableLoaderState::Program {
+			programdata_address,
+		}) = bincode::deserialize(&program.data)
+		else {
+			return Err(ClientError::Other(format!(
+				"Account {program_id} is not an upgradeable program"
+			)));
+		};
+
+		let program_data = self.get_full_account(&programdata_address).await?;
+		let Ok(UpgradeableLoaderState::ProgramData {
+			slot,
+			upgrade_authority_address,
+		}) = bincode::deserialize(&program_data.data)
+		else {
+			return Err(ClientError::Other(format!(
+				"Account {programdata_address} is not program data"
+			)));
+		};
+
+		Ok(ProgramInfo {
+			authority: upgrade_authority_address,
+			last_deployed_slot: slot,
+			data_len: program_data
+				.data
+				.len()
+				.saturating_sub(UpgradeableLoaderState::size_of_programdata_metadata()),
+			is_immutable: upgrade_authority_address.is_none(),
+		})
+	}
+
+	/// Wait for the account to be available at the provided commitment.
+	///
+	/// Freshly created accounts can take some time to propagate to the node
+	/// which is being queried. This polls the account every `SLEEP_MS`
+	/// milliseconds and returns as soon as it is found. A
+	/// [`ClientError::Timeout`] is returned when the account is still missing
+	/// after the `timeout`.
+	pub async fn get_account_await(
+		&self,
+		pubkey: &Pubkey,
+		commitment_config: CommitmentConfig,
+		timeout: Duration,
+	) -> ClientResult<Account> {
+		let poll_account = pin!(async {
+			loop {
+				if let Some(account) = self
+					.get_account_with_commitment(pubkey, commitment_config)
+					.await?
+				{
+					return Ok(account);
+				}
+
+				Delay::new(Duration::from_millis(SLEEP_MS)).await;
+			}
+		});
+
+		match select(poll_account, Delay::new(timeout)).await {
+			Either::Left((result, _)) => result,
+			Either::Right(_) => {
+				Err(ClientError::Timeout(format!(
+					"Account {pubkey} not found after {}ms.",
+					timeout.as_millis()
+				)))
+			}
+		}
+	}
+
+	/// Stream the account at increasing commitment levels, for showing
+	/// optimistic state which is upgraded once it is confirmed.
+	///
+	/// The `processed` view of the account is yielded first, followed by the
+	/// `confirmed` and `finalized` views. The stream ends early once a view is
+	/// identical to the previous one, since the account has converged. Each
+	/// view is a separate `getAccountInfo` request, so this can cost up to
+	/// three requests instead of one. The stream ends after the first error.
+	pub fn get_account_progressive(
+		&self,
+		pubkey: &Pubkey,
+	) -> impl Stream<Item = ClientResult<(CommitmentConfig, Option<UiAccount>)>> + '_ {
+		const LEVELS: [CommitmentLevel; 3] = [
+			CommitmentLevel::Processed,
+			CommitmentLevel::Confirmed,
+			CommitmentLevel::Finalized,
+		];
+		let pubkey = *pubkey;
+
+		stream::unfold((0, None), move |(index, previous)| {
+			async move {
+				let commitment_config = CommitmentConfig {
+					commitment: *LEVELS.get(index)?,
+				};
+				let request = GetAccountInfoRequest::builder()
+					.pubkey(pubkey)
+					.config(RpcAccountInfoConfig {
+						commitment: Some(commitment_config),
+						encoding: Some(UiAccountEncoding::Base64),
+						..Default::default()
+					})
+					.build();
+				let response: ClientResponse<GetAccountInfoResponse> =
+					match self.send(request).await {
+						Ok(response) => response,
+						Err(error) => return Some((Err(error), (LEVELS.len(), previous))),
+					};
+				let account = response.result.value;
+
+				if previous.as_ref() == Some(&account) {
+					return None;
+				}
+
+				Some((
+					Ok((commitment_config, account.clone())),
+					(index + 1, Some(account)),
+				))
+			}
+		})
+	}
+
 	pub async fn get_balance_with_commitment(
 		&self,
 		pubkey: &Pubkey,
@@ -313,41 +851,117 @@ impl SolanaRpcClient {
 		Ok(response.result.into())
 	}
 
+	/// The statuses of the `signatures` in the same order, which are `None`
+	/// for signatures which aren't in the recent status cache of the node.
+	///
+	/// At most [`MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS`] signatures can be
+	/// requested at once.
 	pub async fn get_signature_statuses(
 		&self,
 		signatures: &[Signature],
 	) -> ClientResult<Vec<Option<TransactionStatus>>> {
+		check_signature_statuses_len(signatures)?;
+
 		let request = GetSignatureStatusesRequest::new(signatures.into());
 		let response: ClientResponse<GetSignatureStatusesResponse> = self.send(request).await?;
 
 		Ok(response.result.value)
 	}
 
+	/// The same as [`SolanaRpcClient::get_signature_statuses`] but the full
+	/// transaction history of the node is searched, so older signatures are
+	/// also found.
+	pub async fn get_signature_statuses_with_history(
+		&self,
+		signatures: &[Signature],
+	) -> ClientResult<Vec<Option<TransactionStatus>>> {
+		check_signature_statuses_len(signatures)?;
+
+		let request = GetSignatureStatusesRequest::new_with_config(
+			signatures.into(),
+			RpcSignatureStatusConfig {
+				search_transaction_history: true,
+			},
+		);
+		let response: ClientResponse<GetSignatureStatusesResponse> = self.send(request).await?;
+
+		Ok(response.result.value)
+	}
+
+	/// Get the confirmed transaction with the `signature` and its status
+	/// meta, which is `None` when the node doesn't know the signature.
+	///
+	/// With the `jsonParsed` encoding the instructions are parsed by the node.
+	/// Set `max_supported_transaction_version` to fetch versioned
+	/// transactions, which the node rejects otherwise.
 	pub async fn get_transaction_with_config(
 		&self,
 		signature: &Signature,
 		config: RpcTransactionConfig,
-	) -> ClientResult<EncodedConfirmedTransactionWithStatusMeta> {
+	) -> ClientResult<Option<EncodedConfirmedTransactionWithStatusMeta>> {
 		let request = GetTransactionRequest::new_with_config(*signature, config);
 		let response: ClientResponse<GetTransactionResponse> = self.send(request).await?;
 
-		match response.result.into() {
-			Some(result) => Ok(result),
-			None => Err(RpcError::new(format!("Signature {signature} not found.")).into()),
-		}
+		Ok(response.result.into())
 	}
 
+	/// Get the confirmed transaction with the `signature` in the `json`
+	/// encoding with the client commitment, including versioned
+	/// transactions.
 	pub async fn get_transaction(
 		&self,
 		signature: &Signature,
-	) -> ClientResult<EncodedConfirmedTransactionWithStatusMeta> {
-		let request = GetTransactionRequest::new(*signature);
-		let response: ClientResponse<GetTransactionResponse> = self.send(request).await?;
+	) -> ClientResult<Option<EncodedConfirmedTransactionWithStatusMeta>> {
+		self.get_transaction_with_config(
+			signature,
+			RpcTransactionConfig {
+				encoding: Some(UiTransactionEncoding::Json),
+				commitment: Some(self.commitment_config()),
+				max_supported_transaction_version: Some(0),
+			},
+		)
+		.await
+	}
 
-		match response.result.into() {
-			Some(result) => Ok(result),
-			None => Err(RpcError::new(format!("Signature {signature} not found.")).into()),
-		}
+	/// Get the fee, compute units and size of the confirmed transaction with
+	/// the `signature`.
+	pub async fn transaction_resource_usage(
+		&self,
+		signature: &Signature,
+	) -> ClientResult<ResourceUsage> {
+		let confirmed = self
+			.get_transaction_with_config(
+				signature,
+				RpcTransactionConfig {
+					encoding: Some(UiTransactionEncoding::Base64),
+					commitment: Some(self.commitment_config()),
+					max_supported_transaction_version: Some(0),
+				},
+			)
+			.await?
+			.ok_or_else(|| RpcError::new(format!("Signature {signature} not found.")))?;
+		let meta = confirmed
+			.transaction
+			.meta
+			.ok_or_else(|| RpcError::new(format!("Transaction {signature} has no status meta.")))?;
+		let transaction = confirmed.transaction.transaction.decode().ok_or_else(|| {
+			RpcError::new(format!("Transaction {signature} could not be decoded."))
+		})?;
+		let size_bytes = bincode::serialized_size(&transaction)
+			.map_err(|error| ClientError::Other(error.to_string()))?;
+		let num_loaded_accounts = meta
+			.loaded_addresses
+			.as_ref()
+			.map_or(0, |loaded| loaded.writable.len() + loaded.readonly.len());
+
+		Ok(ResourceUsage {
+			fee: meta.fee,
+			compute_units_consumed: meta.compute_units_consumed,
+			num_instructions: transaction.message.instructions().len(),
+			num_accounts: transaction.message.static_account_keys().len() + num_loaded_accounts,
+			size_bytes: usize::try_from(size_bytes)
+				.map_err(|error| ClientError::Other(error.to_string()))?,
+		})
 	}
 
 	pub async fn get_latest_blockhash_with_config(
@@ -379,6 +993,17 @@ impl SolanaRpcClient {
 		Ok(result.0)
 	}
 
+	/// The latest blockhash together with the last block height at which it
+	/// is valid, using the client commitment. A transaction signed with the
+	/// blockhash can no longer land once the block height passes
+	/// `last_valid_block_height`.
+	pub async fn get_latest_blockhash_with_expiry(&self) -> ClientResult<RpcBlockhash> {
+		let request = GetLatestBlockhashRequest::new_with_config(self.commitment_config());
+		let response: ClientResponse<GetLatestBlockhashResponse> = self.send(request).await?;
+
+		Ok(response.result.value)
+	}
+
 	pub async fn is_blockhash_valid(
 		&self,
 		blockhash: &Hash,
@@ -407,13 +1032,82 @@ impl SolanaRpcClient {
 		Ok(response.result.into())
 	}
 
-	pub async fn get_fee_for_message(&self, message: &Message) -> ClientResult<u64> {
-		let request = GetFeeForMessageRequest::new(message.to_owned());
+	/// Get the rent parameters of the cluster from the rent sysvar.
+	///
+	/// The parameters rarely change, so the result can be kept and used to
+	/// calculate rent exempt balances locally with [`Rent::minimum_balance`].
+	pub async fn get_rent(&self) -> ClientResult<Rent> {
+		let account = self.get_full_account(&sysvar::rent::ID).await?;
+		let Ok(SysvarAccountType::Rent(rent)) = parse_sysvar(&account.data, &sysvar::rent::ID)
+		else {
+			return Err(ClientError::Other(format!(
+				"Account {} is not the rent sysvar",
+				sysvar::rent::ID
+			)));
+		};
+		let lamports_per_byte_year = rent
+			.lamports_per_byte_year
+			.parse()
+			.map_err(|error: std::num::ParseIntError| ClientError::Other(error.to_string()))?;
+
+		Ok(Rent {
+			lamports_per_byte_year,
+			exemption_threshold: rent.exemption_threshold,
+			burn_percent: rent.burn_percent,
+		})
+	}
+
+	/// The minimum balance for an account with `data_len` bytes of data to
+	/// be rent exempt, calculated locally from rent parameters which are
+	/// fetched once with [`SolanaRpcClient::get_rent`].
+	///
+	/// The parameters are shared by the clones of the client. Use
+	/// [`SolanaRpcClient::invalidate_rent_cache`] to fetch them again.
+	pub async fn minimum_balance_for_rent_exemption_cached(
+		&self,
+		data_len: usize,
+	) -> ClientResult<u64> {
+		let cached = self.rent_cache.lock().unwrap().clone();
+		let rent = if let Some(rent) = cached {
+			rent
+		} else {
+			let rent = self.get_rent().await?;
+			*self.rent_cache.lock().unwrap() = Some(rent.clone());
+			rent
+		};
+
+		Ok(rent.minimum_balance(data_len))
+	}
+
+	/// Clear the rent parameters cached by
+	/// [`SolanaRpcClient::minimum_balance_for_rent_exemption_cached`].
+	pub fn invalidate_rent_cache(&self) {
+		*self.rent_cache.lock().unwrap() = None;
+	}
+
+	/// Get the fee in lamports the network charges for the `message`, for
+	/// both legacy and versioned messages. `None` is returned when the
+	/// blockhash of the message has expired.
+	pub async fn get_fee_for_message_with_commitment(
+		&self,
+		message: &VersionedMessage,
+		commitment_config: CommitmentConfig,
+	) -> ClientResult<Option<u64>> {
+		let request =
+			GetFeeForMessageRequest::new_with_config(message.to_owned(), commitment_config);
 		let response: ClientResponse<GetFeeForMessageResponse> = self.send(request).await?;
 
 		Ok(response.result.into())
 	}
 
+	pub async fn get_fee_for_message(
+		&self,
+		message: &VersionedMessage,
+	) -> ClientResult<Option<u64>> {
+		self.get_fee_for_message_with_commitment(message, self.commitment_config())
+			.await
+	}
+
 	pub async fn send_transaction_with_config(
 		&self,
 		transaction: &VersionedTransaction,
@@ -455,10 +1149,56 @@ impl SolanaRpcClient {
 		.await
 	}
 
+	/// Wait for the transaction `signature` to reach the `commitment_config`
+	/// and return whether it succeeded.
+	///
+	/// With the `js` feature a [`SolanaRpcClient::signature_subscribe`]
+	/// subscription is used, which resolves as soon as the node notifies the
+	/// client. The signature status is polled instead when the subscription
+	/// can't be created.
 	pub async fn confirm_transaction_with_commitment(
 		&self,
 		signature: &Signature,
 		commitment_config: CommitmentConfig,
+	) -> ClientResult<bool> {
+		#[cfg(feature = "js")]
+		if let Some(is_success) = self
+			.confirm_transaction_with_subscription(signature, commitment_config)
+			.await
+		{
+			return Ok(is_success);
+		}
+
+		self.poll_transaction_confirmation(signature, commitment_config)
+			.await
+	}
+
+	/// Wait for the signature notification for as long as polling would
+	/// wait. `None` is returned when the subscription fails so the caller can
+	/// fall back to polling.
+	#[cfg(feature = "js")]
+	async fn confirm_transaction_with_subscription(
+		&self,
+		signature: &Signature,
+		commitment_config: CommitmentConfig,
+	) -> Option<bool> {
+		let timeout = Delay::new(Duration::from_millis(SLEEP_MS * MAX_RETRIES as u64));
+		let notification = pin!(self.wait_for_signature(signature, commitment_config));
+
+		match select(notification, timeout).await {
+			Either::Left((Ok(result), _)) => Some(result.is_ok()),
+			Either::Left((Err(error), _)) => {
+				log::warn!("signature subscription failed, polling instead: {error}");
+				None
+			}
+			Either::Right(_) => Some(false),
+		}
+	}
+
+	async fn poll_transaction_confirmation(
+		&self,
+		signature: &Signature,
+		commitment_config: CommitmentConfig,
 	) -> ClientResult<bool> {
 		let mut is_success = false;
 
@@ -495,6 +1235,60 @@ impl SolanaRpcClient {
 		Ok(is_success)
 	}
 
+	/// Poll the status of the transaction `signature` until it reaches the
+	/// commitment of the `config` and return whether it succeeded.
+	///
+	/// Once the `timeout` elapses the full transaction history of the node is
+	/// searched, so a transaction which is no longer in the recent status
+	/// cache is still found. A [`ClientError::ConfirmationTimeout`] is
+	/// returned when the transaction isn't found at the commitment, which is
+	/// distinct from a transaction which failed on chain.
+	pub async fn confirm_transaction_with_config(
+		&self,
+		signature: &Signature,
+		config: ConfirmTransactionConfig,
+	) -> ClientResult<bool> {
+		let commitment_config = config.commitment.unwrap_or(self.commitment_config());
+		let poll_status = pin!(async {
+			loop {
+				let status = self
+					.get_signature_statuses(&[*signature])
+					.await?
+					.pop()
+					.flatten();
+
+				if let Some(status) = status {
+					if status.satisfies_commitment(commitment_config) {
+						return Ok(status.err.is_none());
+					}
+				}
+
+				Delay::new(Duration::from_millis(SLEEP_MS)).await;
+			}
+		});
+
+		if let Either::Left((result, _)) = select(poll_status, Delay::new(config.timeout)).await {
+			return result;
+		}
+
+		let status = self
+			.get_signature_statuses_with_history(&[*signature])
+			.await?
+			.pop()
+			.flatten();
+
+		match status {
+			Some(status) if status.satisfies_commitment(commitment_config) => {
+				Ok(status.err.is_none())
+			}
+			_ => {
+				Err(ClientError::ConfirmationTimeout {
+					signature: *signature,
+				})
+			}
+		}
+	}
+
 	pub async fn confirm_transaction(&self, signature: &Signature) -> ClientResult<bool> {
 		self.confirm_transaction_with_commitment(signature, self.commitment_config())
 			.await
@@ -516,15 +1310,89 @@ impl SolanaRpcClient {
 		Ok(tx_hash)
 	}
 
-	pub async fn send_and_confirm_transaction_with_commitment(
+	/// Send the `transaction` and send it again after every `interval` of the
+	/// `policy` until it reaches the `commitment_config` or its blockhash
+	/// expires. Transactions are often dropped on congested clusters, and the
+	/// same signed transaction can safely be sent more than once.
+	///
+	/// The `config` is used for the first send. The rebroadcasts skip the
+	/// preflight and ask the node not to retry, since the client does.
+	///
+	/// This fails with [`ClientError::TransactionFailed`] when the confirmed
+	/// transaction failed and with [`ClientError::ConfirmationTimeout`] when
+	/// the blockhash expired first, so it can't be used with a durable nonce.
+	pub async fn send_and_confirm_transaction_with_rebroadcast(
 		&self,
 		transaction: &VersionedTransaction,
 		commitment_config: CommitmentConfig,
+		config: RpcSendTransactionConfig,
+		policy: RebroadcastPolicy,
 	) -> ClientResult<Signature> {
-		self.send_and_confirm_transaction_with_config(
-			transaction,
-			commitment_config,
-			RpcSendTransactionConfig {
+		let blockhash = *transaction.message.recent_blockhash();
+		let signature = self
+			.send_transaction_with_config(transaction, config)
+			.await?;
+		let rebroadcast_config = RpcSendTransactionConfig {
+			skip_preflight: true,
+			max_retries: Some(0),
+			..config
+		};
+		let mut rebroadcasts = 0;
+
+		loop {
+			Delay::new(policy.interval).await;
+
+			// checked before the status so a transaction which lands just
+			// before the blockhash expires is still found
+			let is_blockhash_valid = self
+				.is_blockhash_valid(&blockhash, commitment_config)
+				.await?;
+			let status = self
+				.get_signature_statuses(&[signature])
+				.await?
+				.pop()
+				.flatten();
+
+			if let Some(status) = status {
+				if !status.satisfies_commitment(commitment_config) {
+					continue;
+				}
+
+				return match status.err {
+					None => Ok(signature),
+					Some(error) => Err(ClientError::TransactionFailed { signature, error }),
+				};
+			}
+
+			if !is_blockhash_valid {
+				return Err(ClientError::ConfirmationTimeout { signature });
+			}
+
+			if policy
+				.max_rebroadcasts
+				.is_none_or(|max_rebroadcasts| rebroadcasts < max_rebroadcasts)
+			{
+				rebroadcasts += 1;
+
+				if let Err(error) = self
+					.send_transaction_with_config(transaction, rebroadcast_config)
+					.await
+				{
+					log::warn!("failed to rebroadcast transaction {signature}: {error}");
+				}
+			}
+		}
+	}
+
+	pub async fn send_and_confirm_transaction_with_commitment(
+		&self,
+		transaction: &VersionedTransaction,
+		commitment_config: CommitmentConfig,
+	) -> ClientResult<Signature> {
+		self.send_and_confirm_transaction_with_config(
+			transaction,
+			commitment_config,
+			RpcSendTransactionConfig {
 				preflight_commitment: Some(commitment_config.commitment),
 				encoding: Some(UiTransactionEncoding::Base64),
 				..Default::default()
@@ -546,19 +1414,7 @@ impl SolanaRpcClient {
 		pubkey: &Pubkey,
 		config: RpcProgramAccountsConfig,
 	) -> ClientResult<Vec<(Pubkey, Account)>> {
-		let commitment = config
-			.account_config
-			.commitment
-			.unwrap_or_else(|| self.commitment_config());
-		let account_config = RpcAccountInfoConfig {
-			commitment: Some(commitment),
-			..config.account_config
-		};
-		let config = RpcProgramAccountsConfig {
-			account_config,
-			..config
-		};
-
+		let config = self.with_default_commitment(config);
 		let request = GetProgramAccountsRequest::new_with_config(*pubkey, config);
 		let response: ClientResponse<GetProgramAccountsResponse> = self.send(request).await?;
 
@@ -580,6 +1436,66 @@ impl SolanaRpcClient {
 		Ok(pubkey_accounts)
 	}
 
+	/// Stream the accounts owned by the `program_id` with their data
+	/// deserialized with `bincode`.
+	///
+	/// The response is still fetched in a single request, but each account is
+	/// only decoded and deserialized once it is polled. Only a single `T` is
+	/// held in memory at a time, unless the consumer collects them.
+	pub fn get_program_accounts_stream_as<T: DeserializeOwned>(
+		&self,
+		program_id: &Pubkey,
+		config: RpcProgramAccountsConfig,
+	) -> impl Stream<Item = ClientResult<(Pubkey, T)>> + '_ {
+		let request = GetProgramAccountsRequest::new_with_config(
+			*program_id,
+			self.with_default_commitment(config),
+		);
+
+		stream::once(async move {
+			let response: ClientResponse<GetProgramAccountsResponse> = self.send(request).await?;
+			let accounts = response
+				.result
+				.into_keyed_accounts()
+				.ok_or_else(|| RpcError::new("Program account doesn't exist."))?;
+
+			ClientResult::Ok(
+				stream::iter(accounts).map(|RpcKeyedAccount { pubkey, account }| {
+					let account: Account = account
+						.decode()
+						.ok_or_else(|| RpcError::new(format!("Unable to decode {pubkey}")))?;
+					let value = bincode::deserialize(&account.data).map_err(|error| {
+						ClientError::Other(format!("Unable to deserialize {pubkey}: {error}"))
+					})?;
+
+					Ok((pubkey, value))
+				}),
+			)
+		})
+		.try_flatten()
+	}
+
+	/// Use the client commitment and data slice when the `config` doesn't
+	/// specify them.
+	fn with_default_commitment(
+		&self,
+		config: RpcProgramAccountsConfig,
+	) -> RpcProgramAccountsConfig {
+		let commitment = config
+			.account_config
+			.commitment
+			.unwrap_or_else(|| self.commitment_config());
+		let account_config = self.with_default_data_slice(RpcAccountInfoConfig {
+			commitment: Some(commitment),
+			..config.account_config
+		});
+
+		RpcProgramAccountsConfig {
+			account_config,
+			..config
+		}
+	}
+
 	pub async fn get_program_accounts(
 		&self,
 		pubkey: &Pubkey,
@@ -597,6 +1513,32 @@ impl SolanaRpcClient {
 		.await
 	}
 
+	/// Get the accounts owned by the `program_id` which match every one of the
+	/// `filters`, such as [`RpcFilterType::DataSize`] and
+	/// [`RpcFilterType::Memcmp`].
+	///
+	/// The accounts are fetched with base64 encoding and decoded. An empty
+	/// list of `filters` returns every account owned by the program, the same
+	/// as [`SolanaRpcClient::get_program_accounts`].
+	pub async fn get_program_accounts_with_filters(
+		&self,
+		program_id: &Pubkey,
+		filters: Vec<RpcFilterType>,
+	) -> ClientResult<Vec<(Pubkey, Account)>> {
+		self.get_program_accounts_with_config(
+			program_id,
+			RpcProgramAccountsConfig {
+				filters: (!filters.is_empty()).then_some(filters),
+				account_config: RpcAccountInfoConfig {
+					encoding: Some(UiAccountEncoding::Base64),
+					..RpcAccountInfoConfig::default()
+				},
+				..RpcProgramAccountsConfig::default()
+			},
+		)
+		.await
+	}
+
 	pub async fn get_slot_with_commitment(
 		&self,
 		commitment_config: CommitmentConfig,
@@ -612,15 +1554,43 @@ impl SolanaRpcClient {
 			.await
 	}
 
+	/// Get the block at the `slot`, which is `None` when the slot was skipped
+	/// or its block isn't available on the node.
+	///
+	/// The `config` controls the `transaction_details` and `rewards` of the
+	/// block. With the `jsonParsed` encoding the instructions of the
+	/// transactions are parsed by the node.
 	pub async fn get_block_with_config(
 		&self,
 		slot: Slot,
 		config: RpcBlockConfig,
-	) -> ClientResult<UiConfirmedBlock> {
+	) -> ClientResult<Option<UiConfirmedBlock>> {
 		let request = GetBlockRequest::new_with_config(slot, config);
-		let response: ClientResponse<GetBlockResponse> = self.send(request).await?;
 
-		Ok(response.result.into())
+		match self
+			.send::<_, ClientResponse<GetBlockResponse>>(request)
+			.await
+		{
+			Ok(response) => Ok(Some(response.result.into())),
+			Err(ClientError::Rpc(error)) if error.is_missing_block() => Ok(None),
+			Err(error) => Err(error),
+		}
+	}
+
+	/// Get the block at the `slot` with full transaction details and rewards,
+	/// supporting versioned transactions.
+	pub async fn get_block(&self, slot: Slot) -> ClientResult<Option<UiConfirmedBlock>> {
+		self.get_block_with_config(
+			slot,
+			RpcBlockConfig {
+				encoding: Some(UiTransactionEncoding::Json),
+				transaction_details: Some(TransactionDetails::Full),
+				rewards: Some(true),
+				commitment: Some(self.commitment_config()),
+				max_supported_transaction_version: Some(0),
+			},
+		)
+		.await
 	}
 
 	pub async fn get_version(&self) -> ClientResult<RpcVersionInfo> {
@@ -636,14 +1606,19 @@ impl SolanaRpcClient {
 		Ok(response.result.into())
 	}
 
-	pub async fn get_block_time(&self, slot: Slot) -> ClientResult<UnixTimestamp> {
+	/// Get the estimated production time of the block at the `slot`, which is
+	/// `None` when the node doesn't have the time of the block, such as for
+	/// skipped slots.
+	pub async fn get_block_time(&self, slot: Slot) -> ClientResult<Option<UnixTimestamp>> {
 		let request = GetBlockTimeRequest::new(slot);
-		let response: ClientResponse<GetBlockTimeResponse> = self.send(request).await?;
 
-		let maybe_timestamp: Option<UnixTimestamp> = response.result.into();
-		match maybe_timestamp {
-			Some(timestamp) => Ok(timestamp),
-			None => Err(RpcError::new(format!("Block Not Found: slot={slot}")).into()),
+		match self
+			.send::<_, ClientResponse<GetBlockTimeResponse>>(request)
+			.await
+		{
+			Ok(response) => Ok(response.result.into()),
+			Err(ClientError::Rpc(error)) if error.is_missing_block() => Ok(None),
+			Err(error) => Err(error),
 		}
 	}
 
@@ -662,6 +1637,28 @@ impl SolanaRpcClient {
 			.await
 	}
 
+	/// Get the current slot and block height in a single batch request.
+	///
+	/// Both values are read by the same node in one round trip, which avoids
+	/// the skew of reading them with two separate requests.
+	pub async fn get_slot_and_block_height(
+		&self,
+		commitment_config: CommitmentConfig,
+	) -> ClientResult<(Slot, u64)> {
+		let mut batch = self.batch();
+		let slot = batch.add::<_, ClientResponse<GetSlotResponse>>(
+			GetSlotRequest::new_with_config(commitment_config),
+		);
+		let block_height = batch.add::<_, ClientResponse<GetBlockHeightResponse>>(
+			GetBlockHeightRequest::new_with_config(commitment_config),
+		);
+		let responses = batch.send().await?;
+		let slot = responses.get(slot)?;
+		let block_height = responses.get(block_height)?;
+
+		Ok((slot.result.into(), block_height.result.into()))
+	}
+
 	pub async fn get_genesis_hash(&self) -> ClientResult<Hash> {
 		let request = GetGenesisHashRequest;
 		let response: ClientResponse<GetGenesisHashResponse> = self.send(request).await?;
@@ -727,6 +1724,94 @@ impl SolanaRpcClient {
 		Ok(response.result.into())
 	}
 
+	/// Estimate the compute unit price, in micro-lamports, needed to land a
+	/// transaction which writes to the `addresses`.
+	///
+	/// This is the fee at the `percentile` (`0..=100`) of the recent
+	/// prioritization fees, see [`fee_percentile`]. When no `addresses` are
+	/// provided the fees paid across the whole cluster are used.
+	pub async fn estimate_prioritization_fee(
+		&self,
+		addresses: &[Pubkey],
+		percentile: u8,
+	) -> ClientResult<u64> {
+		let fees = self
+			.get_recent_prioritization_fees_with_accounts(addresses.to_vec())
+			.await?;
+
+		Ok(fee_percentile(&fees, percentile))
+	}
+
+	/// Prepend the `SetComputeUnitPrice` and `SetComputeUnitLimit`
+	/// instructions to the provided `instructions`, returning the instructions
+	/// which are ready to be compiled into a transaction.
+	///
+	/// The compute unit price is picked from the recent prioritization fees
+	/// paid for the writable accounts of the `instructions` at the percentile
+	/// of the [`PriorityFeeConfig`]. The compute unit limit is the units
+	/// consumed when simulating the instructions with the compute unit
+	/// margin applied.
+	pub async fn with_priority_fee_instructions(
+		&self,
+		payer: &Pubkey,
+		instructions: &[Instruction],
+		config: PriorityFeeConfig,
+	) -> ClientResult<Vec<Instruction>> {
+		let writable_accounts = instructions
+			.iter()
+			.flat_map(|instruction| &instruction.accounts)
+			.filter(|meta| meta.is_writable)
+			.map(|meta| meta.pubkey)
+			.collect::<HashSet<_>>()
+			.into_iter()
+			.take(MAX_PRIORITIZATION_FEE_ACCOUNTS)
+			.collect::<Vec<_>>();
+		let fees = self
+			.get_recent_prioritization_fees_with_accounts(writable_accounts)
+			.await?;
+		let compute_unit_price = ComputeBudgetInstruction::set_compute_unit_price(fee_percentile(
+			&fees,
+			config.fee_percentile(),
+		));
+
+		let mut simulated_instructions = vec![
+			ComputeBudgetInstruction::set_compute_unit_limit(COMPUTE_UNIT_MAX_LIMIT as u32),
+			wasm_safe_instruction_clone(&compute_unit_price),
+		];
+		simulated_instructions.extend(instructions.iter().map(wasm_safe_instruction_clone));
+		let transaction = VersionedTransaction::new_unsigned_v0(
+			payer,
+			&simulated_instructions,
+			&[],
+			Hash::default(),
+		)?;
+		let simulation = self.simulate_transaction(&transaction).await?.value;
+
+		if let Some(error) = simulation.err {
+			return Err(ClientError::Other(format!(
+				"Simulating the transaction failed: {error}"
+			)));
+		}
+
+		let Some(units_consumed) = simulation.units_consumed else {
+			return Err(ClientError::Other(
+				"Could not calculate the optimal compute units".into(),
+			));
+		};
+		let compute_unit_limit = units_consumed
+			.saturating_mul(config.compute_unit_margin)
+			.div_ceil(100)
+			.min(COMPUTE_UNIT_MAX_LIMIT as u64);
+
+		let mut result = vec![
+			compute_unit_price,
+			ComputeBudgetInstruction::set_compute_unit_limit(compute_unit_limit as u32),
+		];
+		result.extend(instructions.iter().map(wasm_safe_instruction_clone));
+
+		Ok(result)
+	}
+
 	pub async fn get_blocks_with_limit_and_commitment(
 		&self,
 		start_slot: Slot,
@@ -829,25 +1914,133 @@ impl SolanaRpcClient {
 			.await
 	}
 
+	/// Get the accounts for each of the `pubkeys`. The returned accounts are
+	/// in the same order as the `pubkeys` with `None` for missing accounts.
+	///
+	/// Any number of `pubkeys` can be provided. They are split into chunks of
+	/// [`MAX_MULTIPLE_ACCOUNTS`] which are fetched with up to
+	/// [`DEFAULT_MULTIPLE_ACCOUNTS_CONCURRENCY`] concurrent requests.
 	pub async fn get_multiple_accounts_with_config(
 		&self,
 		pubkeys: &[Pubkey],
 		config: RpcAccountInfoConfig,
 	) -> ClientResult<Vec<Option<Account>>> {
-		let config = RpcAccountInfoConfig {
-			commitment: config.commitment,
-			..config
-		};
+		self.get_multiple_accounts_with_concurrency(
+			pubkeys,
+			config,
+			DEFAULT_MULTIPLE_ACCOUNTS_CONCURRENCY,
+		)
+		.await
+	}
+
+	/// The same as [`SolanaRpcClient::get_multiple_accounts_with_config`] with
+	/// a custom limit on the number of concurrent requests. Use a lower
+	/// `concurrency` to avoid overwhelming rate limited endpoints.
+	pub async fn get_multiple_accounts_with_concurrency(
+		&self,
+		pubkeys: &[Pubkey],
+		config: RpcAccountInfoConfig,
+		concurrency: usize,
+	) -> ClientResult<Vec<Option<Account>>> {
+		self.get_multiple_full_accounts(pubkeys, self.with_default_data_slice(config), concurrency)
+			.await
+	}
+
+	/// The same as [`SolanaRpcClient::get_multiple_accounts_with_concurrency`]
+	/// without the default data slice of the client.
+	async fn get_multiple_full_accounts(
+		&self,
+		pubkeys: &[Pubkey],
+		config: RpcAccountInfoConfig,
+		concurrency: usize,
+	) -> ClientResult<Vec<Option<Account>>> {
+		let accounts = self
+			.get_multiple_ui_accounts(pubkeys, config, concurrency)
+			.await?;
 
-		let request = GetMultipleAccountsRequest::new_with_config(pubkeys.to_vec(), config);
-		let response: ClientResponse<GetMultipleAccountsResponse> = self.send(request).await?;
+		Ok(accounts
+			.into_iter()
+			.map(|account| account.and_then(|account| account.decode()))
+			.collect())
+	}
 
-		Ok(response
-			.result
-			.value
+	/// Fetch the `pubkeys` in chunks of [`MAX_MULTIPLE_ACCOUNTS`] with up to
+	/// `concurrency` requests in flight. The accounts are returned in the
+	/// same order as the `pubkeys`.
+	async fn get_multiple_ui_accounts(
+		&self,
+		pubkeys: &[Pubkey],
+		config: RpcAccountInfoConfig,
+		concurrency: usize,
+	) -> ClientResult<Vec<Option<UiAccount>>> {
+		let requests = pubkeys.chunks(MAX_MULTIPLE_ACCOUNTS).map(|chunk| {
+			let request =
+				GetMultipleAccountsRequest::new_with_config(chunk.to_vec(), config.clone());
+
+			async move {
+				let response: ClientResponse<GetMultipleAccountsResponse> =
+					self.send(request).await?;
+
+				ClientResult::Ok(response.result.value)
+			}
+		});
+		let chunks = stream::iter(requests)
+			.buffered(concurrency.max(1))
+			.try_collect::<Vec<_>>()
+			.await?;
+
+		Ok(chunks.into_iter().flatten().collect())
+	}
+
+	/// Get the accounts with their data parsed into json on the client by
+	/// [`crate::solana_account_decoder::parse_account_data::parse_account_data_v3`].
+	/// The accounts are fetched with the base64 encoding in chunks of
+	/// [`MAX_MULTIPLE_ACCOUNTS`] and are returned in the same order as the
+	/// `pubkeys`, with `None` for missing accounts. Token accounts are parsed
+	/// with the decimals of their mint, the same as
+	/// [`SolanaRpcClient::get_multiple_parsed_accounts`].
+	///
+	/// The `data_slice` only fetches part of the data of each account, which
+	/// is useful to only fetch the discriminator of each account. Sliced data
+	/// usually can't be parsed. Any account which can't be parsed is returned
+	/// with the [`UNPARSABLE_ACCOUNT_PROGRAM`] as the `program` of its data,
+	/// the same as [`SolanaRpcClient::get_account_auto_parsed`], so the
+	/// encoding of the data never changes.
+	pub async fn get_multiple_accounts_parsed(
+		&self,
+		pubkeys: &[Pubkey],
+		data_slice: Option<UiDataSliceConfig>,
+	) -> ClientResult<Vec<Option<UiAccount>>> {
+		let config = RpcAccountInfoConfig {
+			encoding: Some(UiAccountEncoding::Base64),
+			data_slice,
+			commitment: Some(self.commitment_config()),
+			..RpcAccountInfoConfig::default()
+		};
+		let ui_accounts = self
+			.get_multiple_ui_accounts(pubkeys, config, DEFAULT_MULTIPLE_ACCOUNTS_CONCURRENCY)
+			.await?;
+		let accounts = ui_accounts
 			.iter()
-			.filter(|maybe_acc| maybe_acc.is_some())
-			.map(|acc| acc.clone().unwrap().decode())
+			.map(|account| account.as_ref().and_then(UiAccount::decode))
+			.collect::<Vec<Option<Account>>>();
+		let parsed_accounts = self.parse_accounts(pubkeys, &accounts).await?;
+
+		Ok(ui_accounts
+			.into_iter()
+			.zip(accounts)
+			.zip(parsed_accounts)
+			.map(|((ui_account, account), parsed_account)| {
+				let (ui_account, account, parsed_account) =
+					(ui_account?, account?, parsed_account?);
+				let parsed_account =
+					parsed_account.unwrap_or_else(|_| unparsable_account(&account));
+
+				Some(UiAccount {
+					data: UiAccountData::Json(parsed_account),
+					..ui_account
+				})
+			})
 			.collect())
 	}
 
@@ -874,85 +2067,517 @@ impl SolanaRpcClient {
 			.await
 	}
 
-	pub async fn get_cluster_nodes(&self) -> ClientResult<Vec<RpcContactInfoWasm>> {
-		let response: ClientResponse<GetClusterNodesResponse> =
-			self.send(GetClusterNodesRequest).await?;
-
-		Ok(response.result.into())
-	}
-
-	pub async fn get_vote_accounts_with_config(
+	/// Derive the program derived address and bump of each of the
+	/// `seed_sets` for the `program_id` and fetch all of their accounts with
+	/// batched `getMultipleAccounts` requests.
+	///
+	/// The results are returned in the same order as the `seed_sets`, with
+	/// `None` for the addresses which don't have an account yet.
+	pub async fn get_pda_accounts(
 		&self,
-		config: RpcGetVoteAccountsConfig,
-	) -> ClientResult<RpcVoteAccountStatus> {
-		let request = GetVoteAccountsRequest::new_with_config(config);
-		let response: ClientResponse<GetVoteAccountsResponse> = self.send(request).await?;
+		program_id: &Pubkey,
+		seed_sets: &[Vec<Vec<u8>>],
+	) -> ClientResult<Vec<(Pubkey, u8, Option<UiAccount>)>> {
+		let addresses = seed_sets
+			.iter()
+			.map(|seeds| {
+				let seeds = seeds.iter().map(Vec::as_slice).collect::<Vec<_>>();
+				Pubkey::find_program_address(&seeds, program_id)
+			})
+			.collect::<Vec<_>>();
+		let pubkeys = addresses
+			.iter()
+			.map(|(pubkey, _)| *pubkey)
+			.collect::<Vec<_>>();
+		let config = RpcAccountInfoConfig {
+			encoding: Some(UiAccountEncoding::Base64),
+			commitment: Some(self.commitment_config()),
+			..RpcAccountInfoConfig::default()
+		};
+		let accounts = self
+			.get_multiple_ui_accounts(
+				&pubkeys,
+				self.with_default_data_slice(config),
+				DEFAULT_MULTIPLE_ACCOUNTS_CONCURRENCY,
+			)
+			.await?;
 
-		Ok(response.result.into())
+		Ok(addresses
+			.into_iter()
+			.zip(accounts)
+			.map(|((pubkey, bump), account)| (pubkey, bump, account))
+			.collect())
 	}
 
-	pub async fn get_vote_accounts_with_commitment(
-		&self,
-		commitment_config: CommitmentConfig,
-	) -> ClientResult<RpcVoteAccountStatus> {
-		self.get_vote_accounts_with_config(RpcGetVoteAccountsConfig {
-			commitment: Some(commitment_config),
-			..Default::default()
-		})
-		.await
+	/// Get the account with its data parsed into json, the same as the
+	/// `jsonParsed` encoding. `None` is returned when the account doesn't
+	/// exist, and an error when it can't be parsed.
+	///
+	/// Token accounts need the decimals of their mint to calculate the
+	/// `uiAmount`. The mint, along with the clock sysvar for interest bearing
+	/// and scaled ui amount mints, is fetched in a single additional request.
+	pub async fn get_account_parsed(&self, pubkey: &Pubkey) -> ClientResult<Option<ParsedAccount>> {
+		let account = self
+			.parse_multiple_accounts(&[*pubkey])
+			.await?
+			.into_iter()
+			.next()
+			.flatten();
+
+		account
+			.map(|(_, parsed_account)| {
+				parsed_account.map_err(|error| {
+					ClientError::Other(format!("Account {pubkey} could not be parsed: {error}"))
+				})
+			})
+			.transpose()
 	}
 
-	pub async fn get_vote_accounts(&self) -> ClientResult<RpcVoteAccountStatus> {
-		self.get_vote_accounts_with_commitment(self.commitment_config())
-			.await
+	/// Get the accounts with their data parsed into json, keeping the parse
+	/// error of each account which couldn't be parsed.
+	///
+	/// Unlike [`SolanaRpcClient::get_account_parsed`], an account which can't
+	/// be parsed doesn't fail the call. Only failing to fetch the accounts
+	/// returns an error. Token accounts are parsed with the decimals of their
+	/// mint. The mints are fetched in a single additional request, and each
+	/// mint is only fetched once even when it's shared by multiple token
+	/// accounts.
+	pub async fn get_multiple_parsed_accounts(
+		&self,
+		pubkeys: &[Pubkey],
+	) -> ClientResult<Vec<Result<Option<ParsedAccount>, ParseAccountError>>> {
+		Ok(self
+			.parse_multiple_accounts(pubkeys)
+			.await?
+			.into_iter()
+			.map(|account| {
+				account
+					.map(|(_, parsed_account)| parsed_account)
+					.transpose()
+			})
+			.collect())
 	}
 
-	pub async fn get_epoch_schedule(&self) -> ClientResult<EpochSchedule> {
-		let response: ClientResponse<GetEpochScheduleResponse> =
-			self.send(GetEpochScheduleRequest).await?;
+	/// Get the account parsed by the program which owns it, whatever the
+	/// account is. `None` is returned when the account doesn't exist.
+	///
+	/// Accounts owned by the programs in [`PARSABLE_PROGRAM_IDS`] are parsed
+	/// the same as [`SolanaRpcClient::get_account_parsed`]. Any other account,
+	/// or an account which fails to parse, is returned with the
+	/// [`UNPARSABLE_ACCOUNT_PROGRAM`] as the `program` and the owner and
+	/// base64 encoded data as the `parsed` value:
+	///
+	/// ```json
+	/// { "owner": "<pubkey>", "data": ["<base64>", "base64"] }
+	/// ```
+	///
+	/// [`PARSABLE_PROGRAM_IDS`]: crate::solana_account_decoder::parse_account_data::PARSABLE_PROGRAM_IDS
+	pub async fn get_account_auto_parsed(
+		&self,
+		pubkey: &Pubkey,
+	) -> ClientResult<Option<ParsedAccount>> {
+		let account = self
+			.parse_multiple_accounts(&[*pubkey])
+			.await?
+			.into_iter()
+			.next()
+			.flatten();
 
-		Ok(response.result.into())
+		Ok(account.map(|(account, parsed_account)| {
+			parsed_account.unwrap_or_else(|_| unparsable_account(&account))
+		}))
 	}
 
-	pub async fn get_signatures_for_address_with_config(
+	/// Fetch and parse the accounts, along with the mints needed to parse
+	/// token accounts.
+	async fn parse_multiple_accounts(
 		&self,
-		address: &Pubkey,
-		config: GetConfirmedSignaturesForAddress2Config,
-	) -> ClientResult<Vec<RpcConfirmedTransactionStatusWithSignature>> {
-		let config = RpcSignaturesForAddressConfig {
-			before: config.before,
-			until: config.until,
-			limit: config.limit,
-			commitment: config.commitment,
-			min_context_slot: None,
+		pubkeys: &[Pubkey],
+	) -> ClientResult<Vec<Option<(Account, Result<ParsedAccount, ParseAccountError>)>>> {
+		let config = RpcAccountInfoConfig {
+			encoding: Some(UiAccountEncoding::Base64),
+			commitment: Some(self.commitment_config()),
+			..Default::default()
 		};
+		let accounts = self
+			.get_multiple_full_accounts(pubkeys, config, DEFAULT_MULTIPLE_ACCOUNTS_CONCURRENCY)
+			.await?;
+		let parsed_accounts = self.parse_accounts(pubkeys, &accounts).await?;
 
-		let request = GetSignaturesForAddressRequest::new_with_config(*address, config);
-		let response: ClientResponse<GetSignaturesForAddressResponse> = self.send(request).await?;
-
-		Ok(response.result.into())
+		Ok(accounts
+			.into_iter()
+			.zip(parsed_accounts)
+			.map(|(account, parsed_account)| Some((account?, parsed_account?)))
+			.collect())
 	}
 
-	pub async fn minimum_ledger_slot(&self) -> ClientResult<Slot> {
-		let response: ClientResponse<MinimumLedgerSlotResponse> =
-			self.send(MinimumLedgerSlotRequest).await?;
-
-		Ok(response.result.into())
+	/// Parse the `accounts` of the `pubkeys`, fetching the mints needed to
+	/// parse token accounts.
+	async fn parse_accounts(
+		&self,
+		pubkeys: &[Pubkey],
+		accounts: &[Option<Account>],
+	) -> ClientResult<Vec<Option<Result<ParsedAccount, ParseAccountError>>>> {
+		let mints = accounts
+			.iter()
+			.flatten()
+			.filter_map(token_account_mint)
+			.collect::<HashSet<_>>()
+			.into_iter()
+			.collect::<Vec<_>>();
+		let mint_data = self.get_mint_additional_data(mints).await?;
+		let parsed_accounts = accounts
+			.iter()
+			.zip(pubkeys)
+			.map(|(account, pubkey)| {
+				let account = account.as_ref()?;
+				let additional_data = AccountAdditionalDataV3 {
+					spl_token_additional_data: token_account_mint(account)
+						.and_then(|mint| mint_data.get(&mint).copied()),
+				};
+
+				Some(parse_account_data_v3(
+					pubkey,
+					&account.owner,
+					&account.data,
+					Some(additional_data),
+				))
+			})
+			.collect();
+
+		Ok(parsed_accounts)
 	}
 
-	pub async fn get_blocks_with_commitment(
+	/// Fetch the data needed to parse token accounts of the `mints`, along
+	/// with the clock sysvar, in a single request.
+	async fn get_mint_additional_data(
 		&self,
-		start_slot: Slot,
-		end_slot: Option<Slot>,
-		commitment_config: CommitmentConfig,
-	) -> ClientResult<Vec<Slot>> {
-		let request = GetBlocksRequest::new_with_config(start_slot, end_slot, commitment_config);
-		let response: ClientResponse<GetBlocksResponse> = self.send(request).await?;
+		mut mints: Vec<Pubkey>,
+	) -> ClientResult<HashMap<Pubkey, SplTokenAdditionalDataV2>> {
+		let mut mint_data = HashMap::new();
 
-		Ok(response.result.into())
+		if mints.is_empty() {
+			return Ok(mint_data);
+		}
+
+		let config = RpcAccountInfoConfig {
+			encoding: Some(UiAccountEncoding::Base64),
+			commitment: Some(self.commitment_config()),
+			..Default::default()
+		};
+		mints.push(sysvar::clock::ID);
+		let mut mint_accounts = self
+			.get_multiple_full_accounts(&mints, config, DEFAULT_MULTIPLE_ACCOUNTS_CONCURRENCY)
+			.await?;
+		let unix_timestamp = mint_accounts
+			.pop()
+			.flatten()
+			.and_then(|account| bincode::deserialize::<Clock>(&account.data).ok())
+			.map(|clock| clock.unix_timestamp)
+			.unwrap_or_default();
+
+		for (mint, account) in mints.iter().zip(mint_accounts) {
+			if let Some(data) = account.and_then(|account| {
+				SplTokenAdditionalDataV2::from_mint_data(&account.data, unix_timestamp)
+			}) {
+				mint_data.insert(*mint, data);
+			}
+		}
+
+		Ok(mint_data)
 	}
 
-	pub async fn get_blocks(
+	/// Parse account notifications which fell back to binary data on the
+	/// client, so that the stream consistently yields `jsonParsed` data.
+	///
+	/// A `jsonParsed` account subscription falls back to base64 data when the
+	/// node can't parse an update, for example a token account whose mint
+	/// decimals aren't known. Each of these notifications is parsed the same
+	/// as [`SolanaRpcClient::get_account_parsed`]. The first fallback for each
+	/// mint costs one additional request to fetch the mint and the clock
+	/// sysvar. The mint data is then cached for the lifetime of the stream.
+	///
+	/// Notifications which can't be parsed on the client either are yielded
+	/// unchanged. The `pubkey` must be the account the `notifications` are
+	/// subscribed to.
+	///
+	/// ```rust
+	/// # use wasm_client_solana::DEVNET;
+	/// # use wasm_client_solana::SolanaRpcClient;
+	/// # use solana_pubkey::pubkey;
+	/// # use std::pin::pin;
+	/// # use futures::StreamExt;
+	///
+	/// # async fn run() -> anyhow::Result<()> {
+	/// let pubkey = pubkey!("99P8ZgtJYe1buSK8JXkvpLh8xPsCFuLYhz9hQFNw93WJ");
+	/// let client = SolanaRpcClient::new(DEVNET);
+	/// let subscription = client.account_subscribe(&pubkey).await?;
+	/// let mut notifications = pin!(client.reparse_account_notifications(pubkey, subscription));
+	///
+	/// while let Some(notification) = notifications.next().await {
+	/// 	println!("Notification: {notification:?}");
+	/// }
+	///
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn reparse_account_notifications<S>(
+		&self,
+		pubkey: Pubkey,
+		notifications: S,
+	) -> impl Stream<Item = SubscriptionResponse<GetAccountInfoResponse>> + use<S>
+	where
+		S: Stream<Item = SubscriptionResponse<GetAccountInfoResponse>>,
+	{
+		let rpc = self.clone();
+		let mint_data = Arc::new(Mutex::new(HashMap::new()));
+
+		notifications.then(move |mut notification| {
+			let rpc = rpc.clone();
+			let mint_data = mint_data.clone();
+
+			async move {
+				if let Some(ui_account) = notification.params.result.value.as_mut()
+					&& let Some(parsed) = rpc
+						.reparse_ui_account(&pubkey, ui_account, &mint_data)
+						.await
+				{
+					ui_account.data = UiAccountData::Json(parsed);
+				}
+
+				notification
+			}
+		})
+	}
+
+	/// Parse a `ui_account` which has binary data, using the cached
+	/// `mint_data` when possible.
+	async fn reparse_ui_account(
+		&self,
+		pubkey: &Pubkey,
+		ui_account: &UiAccount,
+		mint_data: &Mutex<HashMap<Pubkey, SplTokenAdditionalDataV2>>,
+	) -> Option<ParsedAccount> {
+		if matches!(ui_account.data, UiAccountData::Json(_)) {
+			return None;
+		}
+
+		let account: Account = ui_account.decode()?;
+		let mint = token_account_mint(&account);
+		let mut spl_token_additional_data = None;
+
+		if let Some(mint) = mint {
+			let cached = mint_data.lock().unwrap().get(&mint).copied();
+			let data = if let Some(data) = cached {
+				data
+			} else {
+				let data = self
+					.get_mint_additional_data(vec![mint])
+					.await
+					.ok()?
+					.remove(&mint)?;
+				mint_data.lock().unwrap().insert(mint, data);
+				data
+			};
+
+			spl_token_additional_data = Some(data);
+		}
+
+		let additional_data = AccountAdditionalDataV3 {
+			spl_token_additional_data,
+		};
+
+		parse_account_data_v3(pubkey, &account.owner, &account.data, Some(additional_data)).ok()
+	}
+
+	/// Get the contact information of every node in the cluster, which can
+	/// be used to discover rpc endpoints. Addresses and versions which a node
+	/// doesn't advertise are `None`.
+	pub async fn get_cluster_nodes(&self) -> ClientResult<Vec<RpcContactInfoWasm>> {
+		let response: ClientResponse<GetClusterNodesResponse> =
+			self.send(GetClusterNodesRequest).await?;
+
+		Ok(response.result.into())
+	}
+
+	/// Get the number of gossip nodes running each software version, sorted
+	/// with the most popular version first. See
+	/// [`cluster_version_distribution`].
+	pub async fn get_cluster_version_distribution(&self) -> ClientResult<Vec<(String, usize)>> {
+		let nodes = self.get_cluster_nodes().await?;
+
+		Ok(cluster_version_distribution(&nodes))
+	}
+
+	pub async fn get_vote_accounts_with_config(
+		&self,
+		config: RpcGetVoteAccountsConfig,
+	) -> ClientResult<RpcVoteAccountStatus> {
+		let request = GetVoteAccountsRequest::new_with_config(config);
+		let response: ClientResponse<GetVoteAccountsResponse> = self.send(request).await?;
+
+		Ok(response.result.into())
+	}
+
+	pub async fn get_vote_accounts_with_commitment(
+		&self,
+		commitment_config: CommitmentConfig,
+	) -> ClientResult<RpcVoteAccountStatus> {
+		self.get_vote_accounts_with_config(RpcGetVoteAccountsConfig {
+			commitment: Some(commitment_config),
+			..Default::default()
+		})
+		.await
+	}
+
+	pub async fn get_vote_accounts(&self) -> ClientResult<RpcVoteAccountStatus> {
+		self.get_vote_accounts_with_commitment(self.commitment_config())
+			.await
+	}
+
+	/// Check whether the validator with the `vote_pubkey` is voting.
+	///
+	/// The vote account is compared against the current slot and
+	/// [`ClientError::ValidatorNotFound`] is returned when it is neither a
+	/// current nor a delinquent vote account.
+	pub async fn validator_health(&self, vote_pubkey: &Pubkey) -> ClientResult<ValidatorHealth> {
+		let config = RpcGetVoteAccountsConfig {
+			vote_pubkey: Some(*vote_pubkey),
+			commitment: Some(self.commitment_config()),
+			keep_unstaked_delinquents: Some(true),
+			..Default::default()
+		};
+		let (status, slot) =
+			future::try_join(self.get_vote_accounts_with_config(config), self.get_slot()).await?;
+		let current = status.current.into_iter().map(|info| (false, info));
+		let delinquent = status.delinquent.into_iter().map(|info| (true, info));
+
+		let Some((is_delinquent, info)) = current
+			.chain(delinquent)
+			.find(|(_, info)| info.vote_pubkey == *vote_pubkey)
+		else {
+			return Err(ClientError::ValidatorNotFound {
+				vote_pubkey: *vote_pubkey,
+			});
+		};
+
+		Ok(ValidatorHealth {
+			is_delinquent,
+			last_vote: info.last_vote,
+			slots_behind: slot.saturating_sub(info.last_vote),
+			activated_stake: info.activated_stake,
+			commission: info.commission,
+		})
+	}
+
+	pub async fn get_epoch_schedule(&self) -> ClientResult<EpochSchedule> {
+		let response: ClientResponse<GetEpochScheduleResponse> =
+			self.send(GetEpochScheduleRequest).await?;
+
+		Ok(response.result.into())
+	}
+
+	pub async fn get_signatures_for_address(
+		&self,
+		address: &Pubkey,
+	) -> ClientResult<Vec<RpcConfirmedTransactionStatusWithSignature>> {
+		self.get_signatures_for_address_with_config(
+			address,
+			GetConfirmedSignaturesForAddress2Config::default(),
+		)
+		.await
+	}
+
+	/// Stream the signatures for the `address`, newest first, paging backward
+	/// through its history.
+	///
+	/// Each page is requested with the last signature of the previous page as
+	/// `before`, and up to `config.limit` signatures per request. The stream
+	/// ends once a page has fewer signatures than the limit, which is either
+	/// the end of the history or the `until` signature, and after the first
+	/// error.
+	pub fn signatures_for_address_stream(
+		&self,
+		address: &Pubkey,
+		config: GetConfirmedSignaturesForAddress2Config,
+	) -> impl Stream<Item = ClientResult<RpcConfirmedTransactionStatusWithSignature>> + '_ {
+		let address = *address;
+		let GetConfirmedSignaturesForAddress2Config {
+			before,
+			until,
+			limit,
+			commitment,
+		} = config;
+		let limit = limit
+			.unwrap_or(MAX_SIGNATURES_FOR_ADDRESS_LIMIT)
+			.clamp(1, MAX_SIGNATURES_FOR_ADDRESS_LIMIT);
+
+		stream::unfold(Some(before), move |before| {
+			async move {
+				let before = before?;
+				let config = GetConfirmedSignaturesForAddress2Config {
+					before,
+					until,
+					limit: Some(limit),
+					commitment,
+				};
+				let signatures = match self
+					.get_signatures_for_address_with_config(&address, config)
+					.await
+				{
+					Ok(signatures) => signatures,
+					Err(error) => return Some((Err(error), None)),
+				};
+				let last = signatures.last().map(|status| status.signature);
+				// a page which doesn't move backward would be requested forever
+				let next = (signatures.len() >= limit && last != before).then_some(last);
+
+				Some((Ok(signatures), next))
+			}
+		})
+		.map_ok(|signatures| stream::iter(signatures).map(Ok))
+		.try_flatten()
+	}
+
+	pub async fn get_signatures_for_address_with_config(
+		&self,
+		address: &Pubkey,
+		config: GetConfirmedSignaturesForAddress2Config,
+	) -> ClientResult<Vec<RpcConfirmedTransactionStatusWithSignature>> {
+		let config = RpcSignaturesForAddressConfig {
+			before: config.before,
+			until: config.until,
+			limit: config.limit,
+			commitment: config.commitment,
+			min_context_slot: None,
+		};
+
+		let request = GetSignaturesForAddressRequest::new_with_config(*address, config);
+		let response: ClientResponse<GetSignaturesForAddressResponse> = self.send(request).await?;
+
+		Ok(response.result.into())
+	}
+
+	pub async fn minimum_ledger_slot(&self) -> ClientResult<Slot> {
+		let response: ClientResponse<MinimumLedgerSlotResponse> =
+			self.send(MinimumLedgerSlotRequest).await?;
+
+		Ok(response.result.into())
+	}
+
+	pub async fn get_blocks_with_commitment(
+		&self,
+		start_slot: Slot,
+		end_slot: Option<Slot>,
+		commitment_config: CommitmentConfig,
+	) -> ClientResult<Vec<Slot>> {
+		let request = GetBlocksRequest::new_with_config(start_slot, end_slot, commitment_config);
+		let response: ClientResponse<GetBlocksResponse> = self.send(request).await?;
+
+		Ok(response.result.into())
+	}
+
+	pub async fn get_blocks(
 		&self,
 		start_slot: Slot,
 		end_slot: Option<Slot>,
@@ -1038,22 +2663,52 @@ impl SolanaRpcClient {
 		Ok(response.result.into())
 	}
 
-	pub async fn get_inflation_reward_with_config(
+	/// Get the inflation rewards of the `addresses` in the `epoch`, or in the
+	/// last completed epoch when it's `None`.
+	///
+	/// The rewards are in the same order as the `addresses`, with `None` for
+	/// an address which wasn't rewarded in the epoch. The `addresses` are
+	/// requested in chunks of [`MAX_INFLATION_REWARD_ADDRESSES`] with up to
+	/// [`DEFAULT_MULTIPLE_ACCOUNTS_CONCURRENCY`] requests in flight.
+	pub async fn get_inflation_reward_with_commitment(
 		&self,
 		addresses: &[Pubkey],
 		epoch: Option<Epoch>,
+		commitment_config: CommitmentConfig,
 	) -> ClientResult<Vec<Option<RpcInflationReward>>> {
-		let request = GetInflationRewardRequest::new_with_config(
-			addresses.to_vec(),
-			RpcEpochConfig {
-				commitment: Some(self.commitment_config()),
-				epoch,
-				..Default::default()
-			},
-		);
-		let response: ClientResponse<GetInflationRewardResponse> = self.send(request).await?;
+		let requests = addresses
+			.chunks(MAX_INFLATION_REWARD_ADDRESSES)
+			.map(|chunk| {
+				async move {
+					let request = GetInflationRewardRequest::new_with_config(
+						chunk.to_vec(),
+						RpcEpochConfig {
+							commitment: Some(commitment_config),
+							epoch,
+							..Default::default()
+						},
+					);
+					let response: ClientResponse<GetInflationRewardResponse> =
+						self.send(request).await?;
+
+					ClientResult::Ok(Vec::<Option<RpcInflationReward>>::from(response.result))
+				}
+			});
+		let chunks = stream::iter(requests)
+			.buffered(DEFAULT_MULTIPLE_ACCOUNTS_CONCURRENCY)
+			.try_collect::<Vec<_>>()
+			.await?;
 
-		Ok(response.result.into())
+		Ok(chunks.into_iter().flatten().collect())
+	}
+
+	pub async fn get_inflation_reward_with_config(
+		&self,
+		addresses: &[Pubkey],
+		epoch: Option<Epoch>,
+	) -> ClientResult<Vec<Option<RpcInflationReward>>> {
+		self.get_inflation_reward_with_commitment(addresses, epoch, self.commitment_config())
+			.await
 	}
 
 	pub async fn get_inflation_reward(
@@ -1063,6 +2718,64 @@ impl SolanaRpcClient {
 		self.get_inflation_reward_with_config(addresses, None).await
 	}
 
+	/// Get the inflation rewards of the `stake_accounts` for `num_epochs`
+	/// epochs starting from the `first_epoch`.
+	///
+	/// A `getInflationReward` request is made for every epoch, with at most
+	/// [`MAX_INFLATION_REWARD_ADDRESSES`] addresses each and
+	/// [`DEFAULT_MULTIPLE_ACCOUNTS_CONCURRENCY`] requests in flight. The range
+	/// should only include completed epochs, since the rpc rejects epochs
+	/// which haven't been rewarded yet.
+	///
+	/// A history is returned for every stake account, in the same order.
+	/// Epochs where an account earned nothing, such as before it was
+	/// delegated, are skipped rather than included as a zero reward.
+	pub async fn get_stake_rewards(
+		&self,
+		stake_accounts: &[Pubkey],
+		first_epoch: Epoch,
+		num_epochs: u32,
+	) -> ClientResult<Vec<StakeRewardHistory>> {
+		let epochs = first_epoch..first_epoch.saturating_add(num_epochs.into());
+		let requests = epochs.flat_map(|epoch| {
+			stake_accounts
+				.chunks(MAX_INFLATION_REWARD_ADDRESSES)
+				.enumerate()
+				.map(move |(index, chunk)| {
+					async move {
+						let rewards = self
+							.get_inflation_reward_with_config(chunk, Some(epoch))
+							.await?;
+
+						ClientResult::Ok((index * MAX_INFLATION_REWARD_ADDRESSES, rewards))
+					}
+				})
+		});
+		let responses = stream::iter(requests)
+			.buffered(DEFAULT_MULTIPLE_ACCOUNTS_CONCURRENCY)
+			.try_collect::<Vec<_>>()
+			.await?;
+		let mut histories = stake_accounts
+			.iter()
+			.map(|stake_account| {
+				StakeRewardHistory {
+					stake_account: *stake_account,
+					rewards: vec![],
+				}
+			})
+			.collect::<Vec<_>>();
+
+		for (offset, rewards) in responses {
+			for (history, reward) in histories[offset..].iter_mut().zip(rewards) {
+				if let Some(reward) = reward.filter(|reward| reward.amount > 0) {
+					history.rewards.push(reward);
+				}
+			}
+		}
+
+		Ok(histories)
+	}
+
 	pub async fn get_token_account_with_commitment(
 		&self,
 		pubkey: &Pubkey,
@@ -1143,6 +2856,154 @@ impl SolanaRpcClient {
 		.await
 	}
 
+	/// Get the token accounts of the `owner` across both the classic token
+	/// program and the token 2022 program.
+	///
+	/// Both programs are queried in parallel. Each account is returned with
+	/// the id of the token program which holds it.
+	pub async fn get_all_token_accounts_by_owner(
+		&self,
+		owner: &Pubkey,
+	) -> ClientResult<Vec<(Pubkey, RpcKeyedAccount)>> {
+		let token_program_ids = [spl_token_interface::ID, spl_token_2022_interface::ID];
+		let responses = future::try_join_all(token_program_ids.map(async |program_id| {
+			let accounts = self
+				.get_token_accounts_by_owner(owner, TokenAccountsFilter::ProgramId(program_id))
+				.await?;
+
+			ClientResult::Ok((program_id, accounts))
+		}))
+		.await?;
+
+		Ok(responses
+			.into_iter()
+			.flat_map(|(program_id, accounts)| {
+				accounts
+					.into_iter()
+					.map(move |keyed_account| (program_id, keyed_account))
+			})
+			.collect())
+	}
+
+	/// Get the token accounts of the `owner` and filter them by their balance.
+	///
+	/// The `balance_filter` is applied on the client after all accounts are
+	/// fetched, so it doesn't reduce the size of the RPC response.
+	pub async fn get_token_accounts_by_owner_with_balance_filter(
+		&self,
+		owner: &Pubkey,
+		token_account_filter: TokenAccountsFilter,
+		balance_filter: TokenAccountBalanceFilter,
+	) -> ClientResult<Vec<RpcKeyedAccount>> {
+		let accounts = self
+			.get_token_accounts_by_owner(owner, token_account_filter)
+			.await?;
+
+		Ok(accounts
+			.into_iter()
+			.filter(|account| balance_filter.matches(account))
+			.collect())
+	}
+
+	/// Get the token accounts of the `owner` with their data parsed on the
+	/// client, along with the pubkey of each token account.
+	///
+	/// When no `token_account_filter` is provided both the classic token
+	/// program and the token 2022 program are queried in parallel. The
+	/// accounts are parsed the same as
+	/// [`SolanaRpcClient::get_multiple_parsed_accounts`], so the decimals of
+	/// their mints are fetched in a single additional request and the
+	/// `uiAmount` of every account is correct.
+	pub async fn get_token_accounts_by_owner_parsed(
+		&self,
+		owner: &Pubkey,
+		token_account_filter: Option<TokenAccountsFilter>,
+	) -> ClientResult<Vec<(Pubkey, ParsedAccount)>> {
+		self.get_token_accounts_parsed(token_account_filter, async |filter, config| {
+			let request = GetTokenAccountsByOwnerRequest::new_with_config(*owner, filter, config);
+			let response: ClientResponse<GetTokenAccountsByOwnerResponse> =
+				self.send(request).await?;
+
+			Ok(response.result.value)
+		})
+		.await
+	}
+
+	/// Fetch the token accounts matching the `token_account_filter` as base64
+	/// with `fetch_accounts` and parse them with the data of their mints.
+	async fn get_token_accounts_parsed(
+		&self,
+		token_account_filter: Option<TokenAccountsFilter>,
+		fetch_accounts: impl AsyncFn(
+			RpcTokenAccountsFilter,
+			RpcAccountInfoConfig,
+		) -> ClientResult<Vec<RpcKeyedAccount>>,
+	) -> ClientResult<Vec<(Pubkey, ParsedAccount)>> {
+		let filters = match token_account_filter {
+			Some(TokenAccountsFilter::Mint(mint)) => vec![RpcTokenAccountsFilter::Mint(mint)],
+			Some(TokenAccountsFilter::ProgramId(program_id)) => {
+				vec![RpcTokenAccountsFilter::ProgramId(program_id)]
+			}
+			None => {
+				vec![
+					RpcTokenAccountsFilter::ProgramId(spl_token_interface::ID),
+					RpcTokenAccountsFilter::ProgramId(spl_token_2022_interface::ID),
+				]
+			}
+		};
+		let config = RpcAccountInfoConfig {
+			encoding: Some(UiAccountEncoding::Base64),
+			commitment: Some(self.commitment_config()),
+			..Default::default()
+		};
+		let responses = future::try_join_all(
+			filters
+				.into_iter()
+				.map(|filter| fetch_accounts(filter, config.clone())),
+		)
+		.await?;
+		let accounts = responses
+			.into_iter()
+			.flatten()
+			.map(|keyed_account| {
+				let pubkey = keyed_account.pubkey;
+				let account: Account = keyed_account.account.decode().ok_or_else(|| {
+					ClientError::Other(format!("Account {pubkey} could not be decoded"))
+				})?;
+
+				Ok((pubkey, account))
+			})
+			.collect::<ClientResult<Vec<_>>>()?;
+		let mints = accounts
+			.iter()
+			.filter_map(|(_, account)| token_account_mint(account))
+			.collect::<HashSet<_>>()
+			.into_iter()
+			.collect::<Vec<_>>();
+		let mint_data = self.get_mint_additional_data(mints).await?;
+
+		accounts
+			.into_iter()
+			.map(|(pubkey, account)| {
+				let additional_data = AccountAdditionalDataV3 {
+					spl_token_additional_data: token_account_mint(&account)
+						.and_then(|mint| mint_data.get(&mint).copied()),
+				};
+				let parsed_account = parse_account_data_v3(
+					&pubkey,
+					&account.owner,
+					&account.data,
+					Some(additional_data),
+				)
+				.map_err(|error| {
+					ClientError::Other(format!("Account {pubkey} could not be parsed: {error}"))
+				})?;
+
+				Ok((pubkey, parsed_account))
+			})
+			.collect()
+	}
+
 	pub async fn get_token_account_balance_with_commitment(
 		&self,
 		pubkey: &Pubkey,
@@ -1175,6 +3036,13 @@ impl SolanaRpcClient {
 			.await
 	}
 
+	/// Simulate the transaction with the `config`.
+	///
+	/// With `sig_verify` disabled the transaction doesn't need to be signed,
+	/// and with `replace_recent_blockhash` its blockhash doesn't need to be
+	/// recent. The post simulation state of the `config.accounts` addresses
+	/// is returned in the requested encoding, which is parsed into json by
+	/// the node for [`UiAccountEncoding::JsonParsed`].
 	pub async fn simulate_transaction_with_config(
 		&self,
 		transaction: &VersionedTransaction,
@@ -1202,10 +3070,40 @@ impl SolanaRpcClient {
 		.await
 	}
 
-	pub async fn get_health(&self) -> ClientResult<GetHealthResponse> {
+	/// Check that the node is up to date with the cluster. An unhealthy node
+	/// fails with [`RpcError::NodeUnhealthy`], which includes the number of
+	/// slots it's behind when the node reports it.
+	pub async fn get_health(&self) -> ClientResult<()> {
 		let response: ClientResponse<GetHealthResponse> = self.send(GetHealthRequest).await?;
 
-		Ok(response.result)
+		if response.result.0 != "ok" {
+			return Err(RpcError::NodeUnhealthy {
+				num_slots_behind: None,
+			}
+			.into());
+		}
+
+		Ok(())
+	}
+
+	/// Get the health and version of the node at once, such as for showing a
+	/// connection indicator. An unhealthy node is reported in the
+	/// [`NodeStatus`] rather than as an error.
+	pub async fn node_status(&self) -> ClientResult<NodeStatus> {
+		let (health, version) = future::join(self.get_health(), self.get_version()).await;
+		let num_slots_behind = match health {
+			Ok(()) => None,
+			Err(ClientError::Rpc(RpcError::NodeUnhealthy { num_slots_behind })) => {
+				Some(num_slots_behind)
+			}
+			Err(error) => return Err(error),
+		};
+
+		Ok(NodeStatus {
+			healthy: num_slots_behind.is_none(),
+			num_slots_behind: num_slots_behind.flatten(),
+			version: version?,
+		})
 	}
 
 	/// Returns the identity pubkey for the current node.
@@ -1263,24 +3161,25 @@ impl SolanaRpcClient {
 		Ok(response.result)
 	}
 
-	/// Returns the slot leaders for a given slot range
+	/// Returns the leaders of the `limit` slots starting at `start_slot`, in
+	/// slot order.
 	pub async fn get_slot_leaders_with_config(
 		&self,
 		start_slot: u64,
 		limit: u64,
-	) -> ClientResult<GetSlotLeadersResponse> {
+	) -> ClientResult<Vec<Pubkey>> {
 		let request = GetSlotLeadersRequest::new_with_config(start_slot, limit);
 		let response: ClientResponse<GetSlotLeadersResponse> = self.send(request).await?;
 
-		Ok(response.result)
+		Ok(response.result.into())
 	}
 
 	/// Returns the slot leaders for a given slot range
-	pub async fn get_slot_leaders(&self) -> ClientResult<GetSlotLeadersResponse> {
+	pub async fn get_slot_leaders(&self) -> ClientResult<Vec<Pubkey>> {
 		let request = GetSlotLeadersRequest::new();
 		let response: ClientResponse<GetSlotLeadersResponse> = self.send(request).await?;
 
-		Ok(response.result)
+		Ok(response.result.into())
 	}
 
 	pub async fn get_stake_activation(
@@ -1304,6 +3203,61 @@ impl SolanaRpcClient {
 		Ok(response.result)
 	}
 
+	/// Estimate when the deactivated stake of the `stake_account` will be
+	/// fully inactive.
+	///
+	/// This combines the stake account, the stake history sysvar and the
+	/// epoch schedule. See [`crate::stake_cooldown`] for the assumptions
+	/// made by the estimate.
+	pub async fn estimate_stake_cooldown(
+		&self,
+		stake_account: &Pubkey,
+	) -> ClientResult<StakeCooldownEstimate> {
+		let (account, history, epoch_info, epoch_schedule) = future::try_join4(
+			self.get_full_account(stake_account),
+			self.get_full_account(&sysvar::stake_history::ID),
+			self.get_epoch_info(),
+			self.get_epoch_schedule(),
+		)
+		.await?;
+
+		let Ok(StakeStateV2::Stake(_, stake, _)) = bincode::deserialize(&account.data) else {
+			return Err(ClientError::Other(format!(
+				"Account {stake_account} is not a delegated stake account"
+			)));
+		};
+		let delegation = stake.delegation;
+
+		if delegation.deactivation_epoch == Epoch::MAX {
+			return Err(ClientError::Other(format!(
+				"Stake account {stake_account} has not been deactivated"
+			)));
+		}
+
+		let history: StakeHistory = bincode::deserialize(&history.data)
+			.map_err(|error| ClientError::Other(error.to_string()))?;
+		// the reduced warmup cooldown rate is active on every cluster
+		let status =
+			delegation.stake_activating_and_deactivating(epoch_info.epoch, &history, Some(0));
+		let cluster = history
+			.iter()
+			.next()
+			.map(|(_, entry)| entry.clone())
+			.unwrap_or_default();
+		let epochs = estimate_cooldown_epochs(status.effective, &cluster, NEW_WARMUP_COOLDOWN_RATE);
+		let inactive_epoch = epoch_info.epoch + epochs;
+		let inactive_slot = epoch_schedule.get_first_slot_in_epoch(inactive_epoch);
+		let slots_remaining = inactive_slot.saturating_sub(epoch_info.absolute_slot);
+
+		Ok(StakeCooldownEstimate {
+			deactivation_epoch: delegation.deactivation_epoch,
+			deactivating_stake: status.effective,
+			inactive_epoch,
+			inactive_slot,
+			time_remaining: Duration::from_millis(slots_remaining * DEFAULT_MS_PER_SLOT),
+		})
+	}
+
 	pub async fn get_token_accounts_by_delegate_with_config(
 		&self,
 		pubkey: Pubkey,
@@ -1337,14 +3291,36 @@ impl SolanaRpcClient {
 		Ok(response.result)
 	}
 
+	/// Get the token accounts which the `delegate` can transfer from, with
+	/// their data parsed on the client.
+	///
+	/// The result has the same shape as
+	/// [`SolanaRpcClient::get_token_accounts_by_owner_parsed`] and the
+	/// accounts are parsed the same way. When no `token_account_filter` is
+	/// provided both token programs are queried.
+	pub async fn get_token_accounts_by_delegate_parsed(
+		&self,
+		delegate: &Pubkey,
+		token_account_filter: Option<TokenAccountsFilter>,
+	) -> ClientResult<Vec<(Pubkey, ParsedAccount)>> {
+		self.get_token_accounts_parsed(token_account_filter, async |filter, config| {
+			let response = self
+				.get_token_accounts_by_delegate_with_config(*delegate, filter, config)
+				.await?;
+
+			Ok(response.value.unwrap_or_default())
+		})
+		.await
+	}
+
+	/// Get the 20 largest token accounts of the mint with their ui amounts,
+	/// with the client commitment.
 	pub async fn get_token_largest_accounts(
 		&self,
 		pubkey: Pubkey,
 	) -> ClientResult<GetTokenLargestAccountsResponse> {
-		let request = GetTokenLargestAccountsRequest::new(pubkey);
-		let response: ClientResponse<GetTokenLargestAccountsResponse> = self.send(request).await?;
-
-		Ok(response.result)
+		self.get_token_largest_accounts_with_config(pubkey, self.commitment_config())
+			.await
 	}
 
 	pub async fn get_token_largest_accounts_with_config(
@@ -1363,19 +3339,159 @@ impl SolanaRpcClient {
 		&self,
 		pubkey: &Pubkey,
 	) -> ClientResult<LookupTableAccountType> {
-		let account = self.get_account(pubkey).await?;
+		let account = self.get_full_account(pubkey).await?;
 		let table_type = parse_address_lookup_table(&account.data)
 			.map_err(|error| RpcError::new(error.to_string()))?;
 
 		Ok(table_type)
 	}
 
-	/// Wait for the new block which is `n` blocks in the future.
-	pub async fn wait_for_new_block(&self, n: u8) -> ClientResult<()> {
-		let (_, last_valid_block_height) = self
-			.get_latest_blockhash_with_commitment(self.commitment_config())
-			.await?;
-
+	/// Get the address lookup table as an [`AddressLookupTableAccount`] which
+	/// can be used to compile a `v0` message.
+	pub async fn get_address_lookup_table_account(
+		&self,
+		pubkey: &Pubkey,
+	) -> ClientResult<AddressLookupTableAccount> {
+		let LookupTableAccountType::LookupTable(table) =
+			self.get_address_lookup_table(pubkey).await?
+		else {
+			return Err(ClientError::LookupTable {
+				key: *pubkey,
+				reason: LookupTableError::Uninitialized,
+			});
+		};
+		let addresses = table
+			.addresses
+			.iter()
+			.map(|address| Pubkey::from_str(address))
+			.collect::<Result<Vec<_>, _>>()
+			.map_err(|error| RpcError::new(error.to_string()))?;
+
+		Ok(AddressLookupTableAccount {
+			key: *pubkey,
+			addresses,
+		})
+	}
+
+	/// Resolve the addresses which the `v0` `message` loads from its address
+	/// lookup tables, so the full list of account keys can be reconstructed.
+	///
+	/// Fails with [`ClientError::LookupTable`] when a referenced table has been
+	/// closed, is uninitialized or has finished the cooldown after its
+	/// deactivation, or when an index is outside of the table. Like the
+	/// runtime, a deactivated table is still used while its deactivation slot
+	/// is in the slot hashes sysvar.
+	pub async fn resolve_address_lookup_tables(
+		&self,
+		message: &v0::Message,
+	) -> ClientResult<LoadedAddresses> {
+		let lookups = &message.address_table_lookups;
+
+		if lookups.is_empty() {
+			return Ok(LoadedAddresses::default());
+		}
+
+		let table_keys = lookups
+			.iter()
+			.map(|lookup| lookup.account_key)
+			.collect::<Vec<_>>();
+		let config = RpcAccountInfoConfig {
+			encoding: Some(UiAccountEncoding::Base64),
+			commitment: Some(self.commitment_config()),
+			..Default::default()
+		};
+		let accounts = self
+			.get_multiple_full_accounts(&table_keys, config, DEFAULT_MULTIPLE_ACCOUNTS_CONCURRENCY)
+			.await?;
+		let accounts = lookups
+			.iter()
+			.zip(accounts)
+			.map(|(lookup, account)| {
+				account.ok_or(ClientError::LookupTable {
+					key: lookup.account_key,
+					reason: LookupTableError::NotFound,
+				})
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+		let tables = lookups
+			.iter()
+			.zip(&accounts)
+			.map(|(lookup, account)| deserialize_lookup_table(&lookup.account_key, &account.data))
+			.collect::<Result<Vec<_>, _>>()?;
+		// a deactivated table is still served until its deactivation slot is
+		// no longer in the slot hashes
+		let cooldown = if tables
+			.iter()
+			.any(|table| table.meta.deactivation_slot != Slot::MAX)
+		{
+			Some(self.get_slot_and_slot_hashes().await?)
+		} else {
+			None
+		};
+		let mut loaded_addresses = LoadedAddresses::default();
+
+		for (lookup, table) in lookups.iter().zip(tables) {
+			let key = lookup.account_key;
+
+			if cooldown
+				.as_ref()
+				.is_some_and(|(current_slot, slot_hashes)| {
+					!table.meta.is_active(*current_slot, slot_hashes)
+				}) {
+				return Err(ClientError::LookupTable {
+					key,
+					reason: LookupTableError::Deactivated(table.meta.deactivation_slot),
+				});
+			}
+
+			let resolve = |indexes: &[u8]| {
+				indexes
+					.iter()
+					.map(|index| {
+						table.addresses.get(usize::from(*index)).copied().ok_or(
+							ClientError::LookupTable {
+								key,
+								reason: LookupTableError::InvalidIndex {
+									index: *index,
+									len: table.addresses.len(),
+								},
+							},
+						)
+					})
+					.collect::<Result<Vec<_>, _>>()
+			};
+
+			loaded_addresses
+				.writable
+				.extend(resolve(&lookup.writable_indexes)?);
+			loaded_addresses
+				.readonly
+				.extend(resolve(&lookup.readonly_indexes)?);
+		}
+
+		Ok(loaded_addresses)
+	}
+
+	/// The current slot and the slot hashes sysvar, which together tell
+	/// whether a deactivated lookup table is still cooling down.
+	async fn get_slot_and_slot_hashes(&self) -> ClientResult<(Slot, SlotHashes)> {
+		let (current_slot, account) = future::try_join(
+			self.get_slot(),
+			self.get_full_account(&sysvar::slot_hashes::ID),
+		)
+		.await?;
+		let slot_hashes = bincode::deserialize(&account.data)
+			.map_err(|error| ClientError::Other(error.to_string()))?;
+
+		Ok((current_slot, slot_hashes))
+	}
+
+	/// Wait for the new block which is `n` blocks in the future.
+	pub async fn wait_for_new_block(&self, n: u8) -> ClientResult<()> {
+		let (_, last_valid_block_height) = self
+			.get_latest_blockhash_with_commitment(self.commitment_config())
+			.await?;
+
 		for _ in 0..MAX_RETRIES {
 			let (_, latest) = self
 				.get_latest_blockhash_with_commitment(self.commitment_config())
@@ -1459,6 +3575,10 @@ impl SolanaRpcClient {
 	/// Receives messages of type [`RpcLogsResponse`] when a transaction is
 	/// committed.
 	///
+	/// The commitment of the `request` defaults to the commitment of the
+	/// node. Logs received at the `processed` commitment may belong to a
+	/// transaction which never confirms, see [`LogsSubscribeRequest`].
+	///
 	/// # RPC Reference
 	///
 	/// This method corresponds directly to the [`logsSubscribe`] RPC method.
@@ -1474,10 +3594,91 @@ impl SolanaRpcClient {
 		Ok(subscription)
 	}
 
+	/// Subscribe to the transaction logs which mention any of the
+	/// `addresses`.
+	///
+	/// The [`RpcTransactionLogsFilter::Mentions`] filter only supports a single
+	/// address, so a subscription is created for each address and they are
+	/// merged into one stream. Each notification is tagged with the address
+	/// whose subscription received it.
+	pub async fn logs_subscribe_mentions(
+		&self,
+		addresses: &[Pubkey],
+		config: RpcTransactionLogsConfig,
+	) -> ClientResult<MergedSubscription<Pubkey, LogsNotificationResponse>> {
+		let mut subscriptions = Vec::with_capacity(addresses.len());
+
+		for address in addresses {
+			let request = LogsSubscribeRequest::builder()
+				.filter(RpcTransactionLogsFilter::Mentions(vec![
+					address.to_string(),
+				]))
+				.config(config.clone())
+				.build();
+
+			match self.logs_subscribe(request).await {
+				Ok(subscription) => subscriptions.push((*address, subscription)),
+				Err(error) => {
+					// don't leak the subscriptions which were already created
+					MergedSubscription::new(subscriptions)
+						.unsubscribe()
+						.await
+						.ok();
+					return Err(error);
+				}
+			}
+		}
+
+		Ok(MergedSubscription::new(subscriptions))
+	}
+
 	/// Subscribe to program account events.
 	///
-	/// Receives messages of type [`GetProgramAccountsResponse`] when an account
-	/// owned by the given program changes.
+	/// Receives messages of type [`ProgramNotificationResponse`] when an
+	/// account owned by the given program changes. Each notification contains
+	/// the pubkey of the account which changed.
+	///
+	/// The `request` can be a program id or a tuple of the program id and an
+	/// [`RpcProgramAccountsConfig`], which can filter the accounts and choose
+	/// their encoding. Accounts requested with
+	/// [`UiAccountEncoding::JsonParsed`] are parsed by the node in the same way
+	/// as `getProgramAccounts`.
+	///
+	/// ```rust
+	/// use futures::StreamExt;
+	/// use solana_pubkey::pubkey;
+	/// use wasm_client_solana::DEVNET;
+	/// use wasm_client_solana::SolanaRpcClient;
+	/// use wasm_client_solana::rpc_config::RpcAccountInfoConfig;
+	/// use wasm_client_solana::rpc_config::RpcProgramAccountsConfig;
+	/// use wasm_client_solana::rpc_filter::RpcFilterType;
+	/// use wasm_client_solana::solana_account_decoder::UiAccountEncoding;
+	///
+	/// # async fn run() -> anyhow::Result<()> {
+	/// let program_id = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+	/// let config = RpcProgramAccountsConfig::builder()
+	/// 	.filters(vec![RpcFilterType::DataSize(165)])
+	/// 	.account_config(
+	/// 		RpcAccountInfoConfig::builder()
+	/// 			.encoding(UiAccountEncoding::JsonParsed)
+	/// 			.build(),
+	/// 	)
+	/// 	.build();
+	/// let client = SolanaRpcClient::new(DEVNET);
+	/// let mut subscription = client.program_subscribe((program_id, config)).await?;
+	///
+	/// while let Some(notification) = subscription.next().await {
+	/// 	let keyed_account = notification.params.result.value;
+	/// 	println!(
+	/// 		"{} changed: {:?}",
+	/// 		keyed_account.pubkey, keyed_account.account
+	/// 	);
+	/// }
+	///
+	/// subscription.unsubscribe().await?;
+	/// # Ok(())
+	/// # }
+	/// ```
 	///
 	/// # RPC Reference
 	///
@@ -1486,11 +3687,2732 @@ impl SolanaRpcClient {
 	/// [`programSubscribe`]: https://docs.solana.com/api/websocket#programsubscribe
 	pub async fn program_subscribe(
 		&self,
-		request: ProgramSubscribeRequest,
-	) -> ClientResult<Subscription<GetProgramAccountsResponse>> {
+		request: impl Into<ProgramSubscribeRequest>,
+	) -> ClientResult<Subscription<ProgramNotificationResponse>> {
+		let request: ProgramSubscribeRequest = request.into();
+		let (id, subscription_id) = self.ws.create_subscription(request).await?;
+		let subscription = Subscription::new(&self.ws, id, subscription_id);
+
+		Ok(subscription)
+	}
+
+	/// Subscribe to the confirmation of a transaction `signature`.
+	///
+	/// The node sends a single [`SignatureNotificationResponse`] once the
+	/// transaction reaches the `commitment_config` and then removes the
+	/// subscription, so there is no need to unsubscribe after it is received.
+	/// Use [`SolanaRpcClient::wait_for_signature`] to wait for the
+	/// notification and its transaction result.
+	///
+	/// # RPC Reference
+	///
+	/// This method corresponds directly to the [`signatureSubscribe`] RPC
+	/// method.
+	///
+	/// [`signatureSubscribe`]: https://docs.solana.com/api/websocket#signaturesubscribe
+	pub async fn signature_subscribe(
+		&self,
+		signature: &Signature,
+		commitment_config: CommitmentConfig,
+	) -> ClientResult<Subscription<SignatureNotificationResponse>> {
+		let request = SignatureSubscribeRequest::builder()
+			.signature(*signature)
+			.config(
+				RpcSignatureSubscribeConfig::builder()
+					.commitment(commitment_config)
+					.build(),
+			)
+			.build();
 		let (id, subscription_id) = self.ws.create_subscription(request).await?;
 		let subscription = Subscription::new(&self.ws, id, subscription_id);
 
 		Ok(subscription)
 	}
+
+	/// Wait for the transaction `signature` to reach the `commitment_config`
+	/// with a [`SolanaRpcClient::signature_subscribe`] subscription, without
+	/// polling.
+	///
+	/// The outer result is an error when the subscription fails. The inner
+	/// result contains the error of a transaction which landed and failed.
+	///
+	/// ```rust
+	/// use solana_commitment_config::CommitmentConfig;
+	/// use solana_signature::Signature;
+	/// use wasm_client_solana::LOCALNET;
+	/// use wasm_client_solana::SolanaRpcClient;
+	///
+	/// # async fn run(signature: Signature) -> anyhow::Result<()> {
+	/// let rpc = SolanaRpcClient::new(LOCALNET);
+	///
+	/// match rpc
+	/// 	.wait_for_signature(&signature, CommitmentConfig::confirmed())
+	/// 	.await?
+	/// {
+	/// 	Ok(()) => println!("confirmed"),
+	/// 	Err(error) => println!("failed: {error}"),
+	/// }
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub async fn wait_for_signature(
+		&self,
+		signature: &Signature,
+		commitment_config: CommitmentConfig,
+	) -> ClientResult<TransactionResult<()>> {
+		let mut subscription = self
+			.signature_subscribe(signature, commitment_config)
+			.await?;
+
+		while let Some(notification) = subscription.next().await {
+			// only sent when `enableReceivedNotification` is set
+			let RpcSignatureResult::ProcessedSignature(result) = notification.params.result.value
+			else {
+				continue;
+			};
+
+			return Ok(result.err.map_or(Ok(()), Err));
+		}
+
+		Err(ClientError::Other(format!(
+			"The signature subscription for {signature} ended without a notification"
+		)))
+	}
+
+	/// Subscribe to receive a notification each time a slot is processed by
+	/// the validator.
+	///
+	/// # RPC Reference
+	///
+	/// This method corresponds directly to the [`slotSubscribe`] RPC method.
+	///
+	/// [`slotSubscribe`]: https://docs.solana.com/api/websocket#slotsubscribe
+	pub async fn slot_subscribe(&self) -> ClientResult<Subscription<SlotInfo>> {
+		let (id, subscription_id) = self.ws.create_subscription(SlotSubscribeRequest).await?;
+		let subscription = Subscription::new(&self.ws, id, subscription_id);
+
+		Ok(subscription)
+	}
+
+	/// Subscribe to receive a notification each time a new slot is rooted by
+	/// the validator.
+	///
+	/// # RPC Reference
+	///
+	/// This method corresponds directly to the [`rootSubscribe`] RPC method.
+	///
+	/// [`rootSubscribe`]: https://solana.com/docs/rpc/websocket/rootsubscribe
+	pub async fn root_subscribe(&self) -> ClientResult<Subscription<RootNotification>> {
+		let (id, subscription_id) = self.ws.create_subscription(RootSubscribeRequest).await?;
+		let subscription = Subscription::new(&self.ws, id, subscription_id);
+
+		Ok(subscription)
+	}
+
+	/// Wait until the slot has advanced by `count` slots from the slot at the
+	/// time of the call and return the new slot.
+	///
+	/// This is useful for protocols which require a fixed number of slots to
+	/// pass, since slot times vary. Slots are counted at the `processed`
+	/// commitment and received from a [`SolanaRpcClient::slot_subscribe`]
+	/// subscription. The slot is polled with `getSlot` once per slot duration
+	/// of [`DEFAULT_MS_PER_SLOT`] milliseconds instead when the subscription
+	/// can't be created or ends early.
+	pub async fn wait_slots(&self, count: u64) -> ClientResult<Slot> {
+		self.wait_slots_with_poll_interval(count, Duration::from_millis(DEFAULT_MS_PER_SLOT))
+			.await
+	}
+
+	/// Wait until the slot has advanced by `count` slots, the same as
+	/// [`SolanaRpcClient::wait_slots`], polling `getSlot` every
+	/// `poll_interval` when the subscription isn't available.
+	pub async fn wait_slots_with_poll_interval(
+		&self,
+		count: u64,
+		poll_interval: Duration,
+	) -> ClientResult<Slot> {
+		let commitment_config = CommitmentConfig::processed();
+		let start = self.get_slot_with_commitment(commitment_config).await?;
+		let target = start.saturating_add(count);
+
+		if count == 0 {
+			return Ok(start);
+		}
+
+		if let Ok(subscription) = self.slot_subscribe().await {
+			let unsubscription = subscription.get_unsubscription();
+			let mut slots = subscription.map(|notification| notification.params.result.slot);
+
+			while let Some(slot) = slots.next().await {
+				if slot >= target {
+					unsubscription.run().await.ok();
+					return Ok(slot);
+				}
+			}
+		}
+
+		loop {
+			let slot = self.get_slot_with_commitment(commitment_config).await?;
+
+			if slot >= target {
+				return Ok(slot);
+			}
+
+			Delay::new(poll_interval).await;
+		}
+	}
+
+	/// Subscribe with a watchdog which recreates the subscription on a new
+	/// websocket connection whenever it stays silent for too long. See
+	/// [`WatchedSubscribeRequest`] for the default watchdog of each method.
+	///
+	/// The first subscription is made on the shared connection of this client
+	/// and any error is returned. Failed reconnections are retried after
+	/// every timeout. Notifications which were sent while the connection was
+	/// dead are not replayed.
+	///
+	/// ```rust
+	/// use wasm_client_solana::LOCALNET;
+	/// use wasm_client_solana::SolanaRpcClient;
+	/// use wasm_client_solana::prelude::*;
+	/// use wasm_client_solana::rpc_config::SlotSubscribeRequest;
+	/// use wasm_client_solana::rpc_config::WatchedSubscribeRequest;
+	/// use wasm_client_solana::rpc_response::SlotInfo;
+	/// # use wasm_client_solana::ClientResult;
+	///
+	/// # async fn run() -> ClientResult<()> {
+	/// let rpc = SolanaRpcClient::new(LOCALNET);
+	/// let request = WatchedSubscribeRequest::builder()
+	/// 	.request(SlotSubscribeRequest)
+	/// 	.build();
+	/// let mut slots = rpc.watched_subscribe::<_, SlotInfo>(request).await?;
+	///
+	/// while let Some(notification) = slots.next().await {
+	/// 	log::info!("slot {}", notification.params.result.slot);
+	/// }
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub async fn watched_subscribe<R, T>(
+		&self,
+		request: WatchedSubscribeRequest<R>,
+	) -> ClientResult<impl Stream<Item = SubscriptionResponse<T>> + use<R, T>>
+	where
+		R: WebSocketMethod + Clone,
+		T: DeserializeOwned + WebSocketNotification,
+	{
+		let WatchedSubscribeRequest { request, watchdog } = request;
+		let (id, subscription_id) = self.ws.create_subscription(request.clone()).await?;
+		let subscription = Subscription::<T>::new(&self.ws, id, subscription_id);
+		let resubscriber = self.resubscriber(request);
+
+		Ok(subscription.watchdog(watchdog, move || resubscriber.subscribe()))
+	}
+
+	/// Subscribe with the `request` and resubscribe on a new websocket
+	/// connection whenever the subscription ends, such as when the connection
+	/// drops.
+	///
+	/// A [`SubscriptionEvent::Reconnected`] is yielded after each
+	/// resubscription, since notifications which were sent while the
+	/// connection was closed are not replayed. The first subscription is made
+	/// on the shared connection of this client and any error is returned. The
+	/// stream ends once [`ReconnectConfig::max_retries`] consecutive attempts
+	/// fail.
+	///
+	/// ```rust
+	/// use wasm_client_solana::LOCALNET;
+	/// use wasm_client_solana::SolanaRpcClient;
+	/// use wasm_client_solana::prelude::*;
+	/// use wasm_client_solana::rpc_config::LogsSubscribeRequest;
+	/// use wasm_client_solana::rpc_response::LogsNotificationResponse;
+	/// use wasm_client_solana::streams::ReconnectConfig;
+	/// use wasm_client_solana::streams::SubscriptionEvent;
+	/// # use wasm_client_solana::ClientResult;
+	///
+	/// # async fn run(request: LogsSubscribeRequest) -> ClientResult<()> {
+	/// let rpc = SolanaRpcClient::new(LOCALNET);
+	/// let mut logs = rpc
+	/// 	.reconnecting_subscribe::<_, LogsNotificationResponse>(request, ReconnectConfig::default())
+	/// 	.await?;
+	///
+	/// while let Some(event) = logs.next().await {
+	/// 	match event {
+	/// 		SubscriptionEvent::Notification(notification) => {
+	/// 			log::info!("logs {:?}", notification.params.result.value.logs);
+	/// 		}
+	/// 		SubscriptionEvent::Reconnected => log::warn!("logs may have been missed"),
+	/// 	}
+	/// }
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub async fn reconnecting_subscribe<R, T>(
+		&self,
+		request: R,
+		config: ReconnectConfig,
+	) -> ClientResult<impl Stream<Item = SubscriptionEvent<SubscriptionResponse<T>>> + use<R, T>>
+	where
+		R: WebSocketMethod + Clone,
+		T: DeserializeOwned + WebSocketNotification,
+	{
+		let (id, subscription_id) = self.ws.create_subscription(request.clone()).await?;
+		let subscription = Subscription::<T>::new(&self.ws, id, subscription_id);
+		let resubscriber = self.resubscriber(request);
+
+		Ok(subscription.reconnecting(config, move || resubscriber.subscribe()))
+	}
+
+	/// Make the subscription for the `request` again whenever it's replaced by
+	/// [`Self::watched_subscribe`] or [`Self::reconnecting_subscribe`].
+	fn resubscriber<R>(&self, request: R) -> Resubscriber<R> {
+		Resubscriber {
+			url: self.ws.url().to_string(),
+			method_name: self.method_name.clone(),
+			request,
+		}
+	}
+}
+
+/// Subscribes with the `request` on a new websocket connection, since the
+/// original connection may be dead.
+struct Resubscriber<R> {
+	url: String,
+	method_name: MethodNameTransform,
+	request: R,
+}
+
+impl<R: WebSocketMethod + Clone> Resubscriber<R> {
+	fn subscribe<T: DeserializeOwned + WebSocketNotification>(
+		&self,
+	) -> impl Future<Output = ClientResult<Subscription<T>>> + use<R, T> {
+		let ws = WebSocketProvider::new(self.url.clone())
+			.with_method_name_transform(self.method_name.clone());
+		let request = self.request.clone();
+
+		async move {
+			let (id, subscription_id) = ws.create_subscription(request).await?;
+
+			Ok(Subscription::new(&ws, id, subscription_id))
+		}
+	}
+}
+
+/// The [`UNPARSABLE_ACCOUNT_PROGRAM`] json of an `account` which can't be
+/// parsed, with its owner and base64 encoded data.
+fn unparsable_account(account: &Account) -> ParsedAccount {
+	ParsedAccount {
+		program: UNPARSABLE_ACCOUNT_PROGRAM.to_string(),
+		parsed: serde_json::json!({
+			"owner": account.owner.to_string(),
+			"data": [BASE64_STANDARD.encode(&account.data), "base64"],
+		}),
+		space: account.data.len() as u64,
+	}
+}
+
+/// The mint of the `account` when it's owned by one of the token programs.
+fn token_account_mint(account: &Account) -> Option<Pubkey> {
+	is_known_spl_token_id(&account.owner)
+		.then(|| get_token_account_mint(&account.data))
+		.flatten()
+}
+
+/// Check that the `endpoint` is an absolute url with one of the `schemes`
+/// and a host.
+fn validate_endpoint(endpoint: &str, schemes: &[&str]) -> ClientResult<()> {
+	let is_valid = endpoint.split_once("://").is_some_and(|(scheme, rest)| {
+		let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+		let host = match authority.rsplit_once(':') {
+			Some(_) if authority.ends_with(']') => authority,
+			Some((host, port)) if port.parse::<u16>().is_ok() => host,
+			Some(_) => "",
+			None => authority,
+		};
+
+		schemes.contains(&scheme) && !host.is_empty() && !endpoint.contains(char::is_whitespace)
+	});
+
+	if is_valid {
+		Ok(())
+	} else {
+		Err(ClientError::Other(format!(
+			"Invalid endpoint {endpoint}, expected a {} url",
+			schemes.join(" or ")
+		)))
+	}
+}
+
+pub(crate) fn request_params<T: HttpMethod>(request: T) -> ClientResult<Value> {
+	serde_json::to_value(request).map_err(|error| ClientError::Other(error.to_string()))
+}
+
+/// Deserialize the json rpc `result`, falling back to the error response.
+pub(crate) fn parse_response<R: DeserializeOwned>(result: Value) -> ClientResult<R> {
+	match serde_json::from_value::<R>(result.clone()) {
+		Ok(response) => Ok(response),
+		_ => {
+			match serde_json::from_value::<RpcErrorResponse>(result) {
+				Ok(response) => Err(response.error.into()),
+				Err(error) => Err(ClientError::Other(error.to_string())),
+			}
+		}
+	}
+}
+
+/// Deserialize the lookup table `key`, which fails when the table is
+/// uninitialized.
+fn deserialize_lookup_table<'a>(
+	key: &Pubkey,
+	data: &'a [u8],
+) -> ClientResult<AddressLookupTable<'a>> {
+	AddressLookupTable::deserialize(data).map_err(|error| {
+		let reason = if error == InstructionError::UninitializedAccount {
+			LookupTableError::Uninitialized
+		} else {
+			LookupTableError::InvalidData
+		};
+
+		ClientError::LookupTable { key: *key, reason }
+	})
+}
+
+/// Reject more signatures than the node accepts in a `getSignatureStatuses`
+/// request before sending it.
+fn check_signature_statuses_len(signatures: &[Signature]) -> ClientResult<()> {
+	if signatures.len() > MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS {
+		return Err(ClientError::Other(format!(
+			"{} signatures were passed to getSignatureStatuses which accepts at most \
+			 {MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS}",
+			signatures.len()
+		)));
+	}
+
+	Ok(())
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+	use std::sync::Arc;
+	use std::sync::atomic::AtomicUsize;
+	use std::sync::atomic::Ordering;
+
+	use assert2::check;
+	use base64::Engine;
+	use base64::prelude::BASE64_STANDARD;
+	use serde_json::Value;
+	use serde_json::json;
+	use solana_address_lookup_table_interface::state::LookupTableMeta;
+	use solana_instruction::AccountMeta;
+	use solana_message::Message;
+	use solana_message::v0::MessageAddressTableLookup;
+	use solana_program_pack::Pack;
+	use solana_transaction::Transaction;
+	use solana_transaction_error::TransactionError;
+	use spl_token_2022_interface::state::Account as TokenAccount;
+	use spl_token_2022_interface::state::AccountState;
+	use spl_token_2022_interface::state::Mint;
+
+	use super::*;
+	use crate::JSON_RPC_SERVER_ERROR_SLOT_SKIPPED;
+	use crate::MockRpcProvider;
+	use crate::priority_fees::FeeUrgency;
+	use crate::rpc_config::RpcSimulateTransactionAccountsConfig;
+	use crate::rpc_filter::Memcmp;
+	use crate::rpc_filter::MemcmpEncodedBytes;
+
+	fn account_info(value: &Value) -> Value {
+		json!({ "context": { "slot": 1 }, "value": value })
+	}
+
+	fn system_account(lamports: u64) -> Value {
+		json!({
+			"data": ["", "base64"],
+			"executable": false,
+			"lamports": lamports,
+			"owner": "11111111111111111111111111111111",
+			"rentEpoch": 0,
+			"space": 0
+		})
+	}
+
+	#[tokio::test]
+	async fn with_commitment_changes_the_default_commitment() -> anyhow::Result<()> {
+		let provider = MockRpcProvider::new().on(GetSlotRequest::NAME, |params| {
+			match params[0]["commitment"].as_str() {
+				Some("processed") => json!(3),
+				_ => json!(1),
+			}
+		});
+		let rpc = provider.to_rpc_client();
+		let processed_rpc = rpc.with_commitment(CommitmentConfig::processed());
+
+		check!(processed_rpc.commitment() == CommitmentLevel::Processed);
+		check!(rpc.commitment() == CommitmentLevel::Confirmed);
+		check!(processed_rpc.get_slot().await? == 3);
+		check!(rpc.get_slot().await? == 1);
+		check!(
+			processed_rpc
+				.get_slot_with_commitment(CommitmentConfig::finalized())
+				.await? == 1
+		);
+		check!(provider.count(GetSlotRequest::NAME) == 3);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn builder_infers_the_ws_endpoint() -> anyhow::Result<()> {
+		let rpc = SolanaRpcClient::builder("https://rpc.example.com/?api-key=1")
+			.commitment(CommitmentConfig::finalized())
+			.header("x-api-key", "secret")
+			.build()?;
+
+		check!(rpc.url() == "https://rpc.example.com/?api-key=1");
+		check!(rpc.ws.url() == "wss://rpc.example.com/?api-key=1");
+		check!(rpc.commitment_config() == CommitmentConfig::finalized());
+
+		let rpc = SolanaRpcClient::builder("http://127.0.0.1:8899").build()?;
+		check!(rpc.ws.url() == "ws://127.0.0.1:8900");
+		check!(rpc.commitment_config() == CommitmentConfig::confirmed());
+
+		let rpc = SolanaRpcClient::builder("http://[::1]:8899")
+			.ws_endpoint("ws://[::1]:9000")
+			.build()?;
+		check!(rpc.ws.url() == "ws://[::1]:9000");
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn builder_rejects_invalid_endpoints_and_headers() {
+		for endpoint in [
+			"rpc.example.com",
+			"ftp://rpc.example.com",
+			"http://",
+			"http://:8899",
+			"http://rpc.example.com:port",
+			"http://rpc example.com",
+		] {
+			check!(
+				SolanaRpcClient::builder(endpoint).build().is_err(),
+				"{endpoint}"
+			);
+		}
+
+		check!(
+			SolanaRpcClient::builder("https://rpc.example.com")
+				.ws_endpoint("https://ws.example.com")
+				.build()
+				.is_err()
+		);
+		check!(
+			SolanaRpcClient::builder("https://rpc.example.com")
+				.header("x api key", "secret")
+				.build()
+				.is_err()
+		);
+		check!(
+			SolanaRpcClient::builder("https://rpc.example.com")
+				.header("x-api-key", "secret\n")
+				.build()
+				.is_err()
+		);
+	}
+
+	#[tokio::test]
+	async fn get_account_await_returns_once_found() -> anyhow::Result<()> {
+		let calls = Arc::new(AtomicUsize::new(0));
+		let counter = calls.clone();
+		let provider = MockRpcProvider::new().on(GetAccountInfoRequest::NAME, move |_| {
+			// the account only becomes visible on the third request
+			if counter.fetch_add(1, Ordering::SeqCst) < 2 {
+				account_info(&Value::Null)
+			} else {
+				account_info(&system_account(1_000))
+			}
+		});
+		let rpc = provider.to_rpc_client();
+		let account = rpc
+			.get_account_await(
+				&Pubkey::new_unique(),
+				CommitmentConfig::confirmed(),
+				Duration::from_secs(10),
+			)
+			.await?;
+
+		check!(account.lamports == 1_000);
+		check!(provider.count(GetAccountInfoRequest::NAME) == 3);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn get_account_await_times_out() {
+		let provider =
+			MockRpcProvider::new().on(GetAccountInfoRequest::NAME, |_| account_info(&Value::Null));
+		let rpc = provider.to_rpc_client();
+		let result = rpc
+			.get_account_await(
+				&Pubkey::new_unique(),
+				CommitmentConfig::confirmed(),
+				Duration::from_millis(SLEEP_MS * 2),
+			)
+			.await;
+
+		check!(let Err(ClientError::Timeout(_)) = result);
+	}
+
+	fn keyed_token_account(pubkey: &Pubkey, amount: u64) -> Value {
+		json!({
+			"pubkey": pubkey.to_string(),
+			"account": {
+				"data": {
+					"program": "spl-token",
+					"parsed": {
+						"type": "account",
+						"info": {
+							"isNative": false,
+							"mint": "So11111111111111111111111111111111111111112",
+							"owner": "11111111111111111111111111111111",
+							"state": "initialized",
+							"tokenAmount": {
+								"amount": amount.to_string(),
+								"decimals": 9,
+								"uiAmount": amount as f64 / 1e9,
+								"uiAmountString": (amount as f64 / 1e9).to_string()
+							}
+						}
+					},
+					"space": 165
+				},
+				"executable": false,
+				"lamports": 2_039_280,
+				"owner": "TokenkegQfeZyiNwAJbNbGqPFXCWuBvf9Ss623VQ5DA",
+				"rentEpoch": 0,
+				"space": 165
+			}
+		})
+	}
+
+	#[tokio::test]
+	async fn get_token_accounts_by_owner_with_balance_filter() -> anyhow::Result<()> {
+		let empty = Pubkey::new_unique();
+		let funded = Pubkey::new_unique();
+		let accounts = json!([
+			keyed_token_account(&empty, 0),
+			keyed_token_account(&funded, 1_000),
+		]);
+		let provider = MockRpcProvider::new().on(GetTokenAccountsByOwnerRequest::NAME, move |_| {
+			account_info(&accounts)
+		});
+		let rpc = provider.to_rpc_client();
+		let filter = TokenAccountsFilter::ProgramId(Pubkey::new_unique());
+		let owner = Pubkey::new_unique();
+		let pubkeys = async |balance_filter| -> anyhow::Result<Vec<Pubkey>> {
+			let accounts = rpc
+				.get_token_accounts_by_owner_with_balance_filter(&owner, filter, balance_filter)
+				.await?;
+
+			Ok(accounts.into_iter().map(|account| account.pubkey).collect())
+		};
+
+		check!(pubkeys(TokenAccountBalanceFilter::All).await? == vec![empty, funded]);
+		check!(pubkeys(TokenAccountBalanceFilter::NonZero).await? == vec![funded]);
+		check!(pubkeys(TokenAccountBalanceFilter::ZeroBalanceOnly).await? == vec![empty]);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn get_all_token_accounts_by_owner_queries_both_programs() -> anyhow::Result<()> {
+		let classic = Pubkey::new_unique();
+		let extended = Pubkey::new_unique();
+		let provider =
+			MockRpcProvider::new().on(GetTokenAccountsByOwnerRequest::NAME, move |params| {
+				let program_id = params[1]["programId"].as_str().unwrap_or_default();
+				let pubkey = if program_id == spl_token_2022_interface::ID.to_string() {
+					extended
+				} else {
+					classic
+				};
+
+				account_info(&json!([keyed_token_account(&pubkey, 1_000)]))
+			});
+		let rpc = provider.to_rpc_client();
+		let accounts = rpc
+			.get_all_token_accounts_by_owner(&Pubkey::new_unique())
+			.await?;
+		let tagged = accounts
+			.into_iter()
+			.map(|(token_program, keyed_account)| (keyed_account.pubkey, token_program))
+			.collect::<Vec<_>>();
+
+		check!(
+			tagged
+				== vec![
+					(classic, spl_token_interface::ID),
+					(extended, spl_token_2022_interface::ID),
+				]
+		);
+		check!(provider.count(GetTokenAccountsByOwnerRequest::NAME) == 2);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn get_account_progressive_upgrades_until_converged() -> anyhow::Result<()> {
+		let provider = MockRpcProvider::new().on(GetAccountInfoRequest::NAME, |params| {
+			match params[1]["commitment"].as_str() {
+				Some("processed") => account_info(&system_account(3)),
+				_ => account_info(&system_account(2)),
+			}
+		});
+		let rpc = provider.to_rpc_client();
+		let views = rpc
+			.get_account_progressive(&Pubkey::new_unique())
+			.map(|view| view.map(|(config, account)| (config, account.map(|a| a.lamports))))
+			.try_collect::<Vec<_>>()
+			.await?;
+
+		check!(
+			views
+				== vec![
+					(CommitmentConfig::processed(), Some(3)),
+					(CommitmentConfig::confirmed(), Some(2)),
+				]
+		);
+		check!(provider.count(GetAccountInfoRequest::NAME) == 3);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn get_multiple_accounts_chunks_requests() -> anyhow::Result<()> {
+		let pubkeys = (0..250).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+		let lamports = pubkeys
+			.iter()
+			.enumerate()
+			.map(|(index, pubkey)| (pubkey.to_string(), index as u64))
+			.collect::<HashMap<_, _>>();
+		let provider = MockRpcProvider::new().on(GetMultipleAccountsRequest::NAME, move |params| {
+			let accounts = params[0]
+				.as_array()
+				.unwrap()
+				.iter()
+				.map(|pubkey| {
+					// every third account is missing
+					match lamports[pubkey.as_str().unwrap()] {
+						index if index % 3 == 0 => Value::Null,
+						index => system_account(index),
+					}
+				})
+				.collect::<Vec<_>>();
+
+			account_info(&Value::Array(accounts))
+		});
+		let rpc = provider.to_rpc_client();
+		let accounts = rpc
+			.get_multiple_accounts_with_concurrency(
+				&pubkeys,
+				RpcAccountInfoConfig {
+					encoding: Some(UiAccountEncoding::Base64),
+					..Default::default()
+				},
+				2,
+			)
+			.await?;
+
+		check!(accounts.len() == 250);
+		check!(provider.count(GetMultipleAccountsRequest::NAME) == 3);
+
+		for (index, account) in accounts.iter().enumerate() {
+			let expected = (index % 3 != 0).then_some(index as u64);
+			check!(account.as_ref().map(|account| account.lamports) == expected);
+		}
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn transaction_resource_usage_profiles_the_transaction() -> anyhow::Result<()> {
+		let payer = Pubkey::new_unique();
+		let program_id = Pubkey::new_unique();
+		let instructions = [1_u8, 2].map(|tag| {
+			Instruction::new_with_bytes(program_id, &[tag; 10], vec![AccountMeta::new(payer, true)])
+		});
+		let message = Message::new(&instructions, Some(&payer));
+		let transaction = VersionedTransaction {
+			signatures: vec![Signature::default()],
+			message: VersionedMessage::Legacy(message),
+		};
+		let encoded = BASE64_STANDARD.encode(bincode::serialize(&transaction)?);
+		let size_bytes = bincode::serialized_size(&transaction)?;
+		let recent = Signature::from([1; 64]);
+		let provider = MockRpcProvider::new().on(GetTransactionRequest::NAME, move |params| {
+			let mut meta = json!({
+				"err": null,
+				"status": { "Ok": null },
+				"fee": 5_000,
+				"preBalances": [10_000, 1],
+				"postBalances": [5_000, 1],
+				"loadedAddresses": {
+					"writable": [Pubkey::new_unique().to_string()],
+					"readonly": []
+				}
+			});
+
+			// older transactions didn't record their compute units
+			if params[0] == recent.to_string() {
+				meta["computeUnitsConsumed"] = json!(1_234);
+			}
+
+			json!({
+				"slot": 100,
+				"blockTime": null,
+				"transaction": [encoded, "base64"],
+				"meta": meta,
+				"version": "legacy"
+			})
+		});
+		let rpc = provider.to_rpc_client();
+		let usage = rpc.transaction_resource_usage(&recent).await?;
+
+		check!(
+			usage
+				== ResourceUsage {
+					fee: 5_000,
+					compute_units_consumed: Some(1_234),
+					num_instructions: 2,
+					num_accounts: 3,
+					size_bytes: usize::try_from(size_bytes)?,
+				}
+		);
+
+		let usage = rpc
+			.transaction_resource_usage(&Signature::from([2; 64]))
+			.await?;
+
+		check!(usage.compute_units_consumed == None);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn get_transaction_of_an_unknown_signature_is_none() -> anyhow::Result<()> {
+		let payer = Pubkey::new_unique();
+		let transaction = VersionedTransaction {
+			signatures: vec![Signature::default()],
+			message: VersionedMessage::Legacy(Message::new(&[], Some(&payer))),
+		};
+		let encoded = BASE64_STANDARD.encode(bincode::serialize(&transaction)?);
+		let known = Signature::from([1; 64]);
+		let provider = MockRpcProvider::new().on(GetTransactionRequest::NAME, move |params| {
+			check!(params[1]["encoding"] == "json");
+			check!(params[1]["commitment"] == "confirmed");
+			check!(params[1]["maxSupportedTransactionVersion"] == 0);
+
+			if params[0] != known.to_string() {
+				return Value::Null;
+			}
+
+			json!({
+				"slot": 100,
+				"blockTime": null,
+				"transaction": [encoded, "base64"],
+				"meta": {
+					"err": null,
+					"status": { "Ok": null },
+					"fee": 5_000,
+					"preBalances": [10_000],
+					"postBalances": [5_000],
+					"logMessages": ["Program log: hello"]
+				},
+				"version": "legacy"
+			})
+		});
+		let rpc = provider.to_rpc_client();
+		let confirmed = rpc
+			.get_transaction(&known)
+			.await?
+			.expect("the signature is known");
+		let meta = confirmed
+			.transaction
+			.meta
+			.expect("the transaction has a meta");
+
+		check!(confirmed.slot == 100);
+		check!(meta.fee == 5_000);
+		check!(meta.post_balances == [5_000]);
+		check!(
+			rpc.get_transaction(&Signature::from([2; 64]))
+				.await?
+				.is_none()
+		);
+
+		Ok(())
+	}
+
+	fn signed_transaction() -> VersionedTransaction {
+		let payer = Pubkey::new_unique();
+		let mut message = Message::new(&[], Some(&payer));
+		message.recent_blockhash = Hash::new_unique();
+
+		VersionedTransaction {
+			signatures: vec![Signature::from([1; 64])],
+			message: VersionedMessage::Legacy(message),
+		}
+	}
+
+	#[tokio::test]
+	async fn rebroadcast_resends_until_confirmed() -> anyhow::Result<()> {
+		let transaction = signed_transaction();
+		let signature = transaction.signatures[0];
+		let polls = Arc::new(AtomicUsize::new(0));
+		let provider = MockRpcProvider::new()
+			.on(SendTransactionRequest::NAME, move |_| json!(signature.to_string()))
+			.on(IsBlockhashValidRequest::NAME, |_| {
+				json!({ "context": { "slot": 1 }, "value": true })
+			})
+			.on(GetSignatureStatusesRequest::NAME, move |_| {
+				let status = match polls.fetch_add(1, Ordering::SeqCst) {
+					0 | 1 => Value::Null,
+					2 => json!({ "slot": 1, "confirmations": 0, "err": null, "status": { "Ok": null }, "confirmationStatus": "processed" }),
+					_ => json!({ "slot": 1, "confirmations": 2, "err": null, "status": { "Ok": null }, "confirmationStatus": "confirmed" }),
+				};
+
+				json!({ "context": { "slot": 1 }, "value": [status] })
+			});
+		let rpc = provider.to_rpc_client();
+		let policy = RebroadcastPolicy::builder()
+			.interval(Duration::from_millis(1))
+			.build();
+		let result = rpc
+			.send_and_confirm_transaction_with_rebroadcast(
+				&transaction,
+				CommitmentConfig::confirmed(),
+				RpcSendTransactionConfig::default(),
+				policy,
+			)
+			.await?;
+
+		check!(result == signature);
+		// sent once and rebroadcast after each poll which didn't find it
+		check!(provider.count(SendTransactionRequest::NAME) == 3);
+		check!(provider.count(GetSignatureStatusesRequest::NAME) == 4);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn rebroadcast_stops_when_the_blockhash_expires() -> anyhow::Result<()> {
+		let transaction = signed_transaction();
+		let signature = transaction.signatures[0];
+		let checks = Arc::new(AtomicUsize::new(0));
+		let provider = MockRpcProvider::new()
+			.on(SendTransactionRequest::NAME, move |params| {
+				check!(params[1]["skipPreflight"] == (params[1]["maxRetries"] == 0));
+				json!(signature.to_string())
+			})
+			.on(IsBlockhashValidRequest::NAME, move |_| {
+				let is_valid = checks.fetch_add(1, Ordering::SeqCst) < 3;
+				json!({ "context": { "slot": 1 }, "value": is_valid })
+			})
+			.on(
+				GetSignatureStatusesRequest::NAME,
+				|_| json!({ "context": { "slot": 1 }, "value": [null] }),
+			);
+		let rpc = provider.to_rpc_client();
+		let policy = RebroadcastPolicy::builder()
+			.interval(Duration::from_millis(1))
+			.max_rebroadcasts(1)
+			.build();
+		let result = rpc
+			.send_and_confirm_transaction_with_rebroadcast(
+				&transaction,
+				CommitmentConfig::confirmed(),
+				RpcSendTransactionConfig::default(),
+				policy,
+			)
+			.await;
+
+		let Err(ClientError::ConfirmationTimeout {
+			signature: timed_out,
+		}) = result
+		else {
+			panic!("the blockhash should expire");
+		};
+		check!(timed_out == signature);
+		check!(provider.count(SendTransactionRequest::NAME) == 2);
+		check!(provider.count(IsBlockhashValidRequest::NAME) == 4);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn rebroadcast_returns_the_error_of_a_failed_transaction() -> anyhow::Result<()> {
+		let transaction = signed_transaction();
+		let signature = transaction.signatures[0];
+		let provider = MockRpcProvider::new()
+			.on(SendTransactionRequest::NAME, move |_| {
+				json!(signature.to_string())
+			})
+			.on(
+				IsBlockhashValidRequest::NAME,
+				|_| json!({ "context": { "slot": 1 }, "value": true }),
+			)
+			.on(GetSignatureStatusesRequest::NAME, |_| {
+				json!({
+					"context": { "slot": 1 },
+					"value": [{
+						"slot": 1,
+						"confirmations": 2,
+						"err": "AccountNotFound",
+						"status": { "Err": "AccountNotFound" },
+						"confirmationStatus": "confirmed"
+					}]
+				})
+			});
+		let rpc = provider.to_rpc_client();
+		let policy = RebroadcastPolicy::builder()
+			.interval(Duration::from_millis(1))
+			.build();
+		let result = rpc
+			.send_and_confirm_transaction_with_rebroadcast(
+				&transaction,
+				CommitmentConfig::confirmed(),
+				RpcSendTransactionConfig::default(),
+				policy,
+			)
+			.await;
+
+		let Err(ClientError::TransactionFailed {
+			signature: failed,
+			error,
+		}) = result
+		else {
+			panic!("the transaction should fail");
+		};
+		check!(failed == signature);
+		check!(error == TransactionError::AccountNotFound);
+		check!(provider.count(SendTransactionRequest::NAME) == 1);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn token_supply_and_largest_accounts_use_the_client_commitment() -> anyhow::Result<()> {
+		let mint = Pubkey::new_unique();
+		let holder = Pubkey::new_unique();
+		let ui_amount = |amount: &str, ui_amount: f64, ui_amount_string: &str| {
+			json!({
+				"amount": amount,
+				"decimals": 2,
+				"uiAmount": ui_amount,
+				"uiAmountString": ui_amount_string
+			})
+		};
+		let provider = MockRpcProvider::new()
+			.on(GetTokenSupplyRequest::NAME, move |params| {
+				check!(params[0] == mint.to_string());
+				check!(params[1]["commitment"] == "confirmed");
+				account_info(&ui_amount("100050", 1000.5, "1000.5"))
+			})
+			.on(GetTokenLargestAccountsRequest::NAME, move |params| {
+				check!(params[0] == mint.to_string());
+				check!(params[1]["commitment"] == "confirmed");
+				let mut value = ui_amount("250", 2.5, "2.5");
+				value["address"] = json!(holder.to_string());
+				account_info(&json!([value]))
+			});
+		let rpc = provider.to_rpc_client();
+		let supply = rpc.get_token_supply(&mint).await?;
+		let largest = rpc.get_token_largest_accounts(mint).await?;
+
+		check!(supply.amount == "100050");
+		check!(supply.ui_amount_string == "1000.5");
+		check!(
+			largest.value
+				== [TokenLargestAccountsValue {
+					address: holder,
+					amount: "250".into(),
+					decimals: 2,
+					ui_amount: Some(2.5),
+					ui_amount_string: "2.5".into(),
+				}]
+		);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn get_inflation_reward_chunks_in_order() -> anyhow::Result<()> {
+		let addresses = (0..MAX_INFLATION_REWARD_ADDRESSES + 5)
+			.map(|_| Pubkey::new_unique())
+			.collect::<Vec<_>>();
+		let rewarded = addresses[MAX_INFLATION_REWARD_ADDRESSES + 1].to_string();
+		let provider = MockRpcProvider::new().on(GetInflationRewardRequest::NAME, move |params| {
+			assert_eq!(params[1]["commitment"], "finalized");
+			assert_eq!(params[1]["epoch"], 7);
+
+			let rewards = params[0]
+				.as_array()
+				.unwrap()
+				.iter()
+				.map(|address| {
+					if address.as_str() == Some(rewarded.as_str()) {
+						json!({
+							"epoch": 7,
+							"effectiveSlot": 3_024_000,
+							"amount": 2_500,
+							"postBalance": 1_000_002_500,
+							"commission": null
+						})
+					} else {
+						Value::Null
+					}
+				})
+				.collect::<Vec<_>>();
+
+			Value::Array(rewards)
+		});
+		let rpc = provider.to_rpc_client();
+		let rewards = rpc
+			.get_inflation_reward_with_commitment(
+				&addresses,
+				Some(7),
+				CommitmentConfig::finalized(),
+			)
+			.await?;
+
+		check!(provider.count(GetInflationRewardRequest::NAME) == 2);
+		check!(rewards.len() == addresses.len());
+		check!(
+			rewards.iter().position(Option::is_some) == Some(MAX_INFLATION_REWARD_ADDRESSES + 1)
+		);
+		let reward = rewards[MAX_INFLATION_REWARD_ADDRESSES + 1]
+			.as_ref()
+			.unwrap();
+		check!(reward.amount == 2_500);
+		check!(reward.commission == None);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn get_stake_rewards_builds_a_timeline_per_account() -> anyhow::Result<()> {
+		let stake_accounts = [Pubkey::new_unique(), Pubkey::new_unique()];
+		let provider = MockRpcProvider::new().on(GetInflationRewardRequest::NAME, |params| {
+			let epoch = params[1]["epoch"].as_u64().unwrap();
+			let reward = |amount: u64| {
+				json!({
+					"epoch": epoch,
+					"effectiveSlot": epoch * 432_000,
+					"amount": amount,
+					"postBalance": 1_000_000_000 + amount,
+					"commission": 5
+				})
+			};
+
+			// the second account was delegated after the first epoch
+			if epoch == 10 {
+				json!([reward(epoch * 100), null])
+			} else {
+				json!([reward(epoch * 100), reward(epoch)])
+			}
+		});
+		let rpc = provider.to_rpc_client();
+		let histories = rpc.get_stake_rewards(&stake_accounts, 10, 3).await?;
+
+		check!(provider.count(GetInflationRewardRequest::NAME) == 3);
+		check!(histories.len() == 2);
+		check!(histories[0].stake_account == stake_accounts[0]);
+		check!(
+			histories[0]
+				.rewards
+				.iter()
+				.map(|reward| reward.epoch)
+				.collect::<Vec<_>>()
+				== vec![10, 11, 12]
+		);
+		check!(histories[0].total_amount() == 3_300);
+		check!(histories[1].stake_account == stake_accounts[1]);
+		check!(
+			histories[1]
+				.rewards
+				.iter()
+				.map(|reward| (reward.epoch, reward.amount))
+				.collect::<Vec<_>>()
+				== vec![(11, 11), (12, 12)]
+		);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn get_multiple_accounts_parsed_chunks_in_order() -> anyhow::Result<()> {
+		let pubkeys = (0..150).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+		let lamports = pubkeys
+			.iter()
+			.enumerate()
+			.map(|(index, pubkey)| (pubkey.to_string(), index as u64))
+			.collect::<HashMap<_, _>>();
+		let provider = MockRpcProvider::new().on(GetMultipleAccountsRequest::NAME, move |params| {
+			assert_eq!(params[1]["encoding"], "base64");
+
+			let accounts = params[0]
+				.as_array()
+				.unwrap()
+				.iter()
+				.map(|pubkey| {
+					match lamports[pubkey.as_str().unwrap()] {
+						index if index % 4 == 0 => Value::Null,
+						index => system_account(index),
+					}
+				})
+				.collect::<Vec<_>>();
+
+			account_info(&Value::Array(accounts))
+		});
+		let rpc = provider.to_rpc_client();
+		let accounts = rpc.get_multiple_accounts_parsed(&pubkeys, None).await?;
+
+		check!(accounts.len() == 150);
+		check!(provider.count(GetMultipleAccountsRequest::NAME) == 2);
+
+		for (index, account) in accounts.iter().enumerate() {
+			let expected = (index % 4 != 0).then_some(index as u64);
+			check!(account.as_ref().map(|account| account.lamports) == expected);
+
+			// an empty system account isn't a nonce account so it can't be parsed
+			if let Some(account) = account {
+				let UiAccountData::Json(parsed) = &account.data else {
+					panic!("the account data should be json: {account:?}");
+				};
+				check!(parsed.program == UNPARSABLE_ACCOUNT_PROGRAM);
+			}
+		}
+
+		let data_slice = UiDataSliceConfig {
+			offset: 0,
+			length: 8,
+		};
+		let sliced = rpc
+			.get_multiple_accounts_parsed(&pubkeys, Some(data_slice))
+			.await?;
+
+		check!(sliced == accounts);
+		check!(provider.count(GetMultipleAccountsRequest::NAME) == 4);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn get_multiple_accounts_parsed_decodes_each_account() -> anyhow::Result<()> {
+		let token_account = Pubkey::new_unique();
+		let mint = Pubkey::new_unique();
+		let missing = Pubkey::new_unique();
+		let data = token_account_data(TokenAccount {
+			mint,
+			owner: Pubkey::new_unique(),
+			amount: 2_500,
+			state: AccountState::Initialized,
+			..Default::default()
+		});
+		let mut accounts = token_fixture(&mint, 3);
+		accounts.insert(missing.to_string(), Value::Null);
+		let handler = multiple_accounts_handler(accounts);
+		let discriminator = BASE64_STANDARD.encode(&data[..8]);
+		let provider = MockRpcProvider::new().on(GetMultipleAccountsRequest::NAME, move |params| {
+			check!(params[1]["encoding"] == "base64");
+
+			if params[0][0] != token_account.to_string() {
+				return handler(params);
+			}
+
+			let mut account = match params[1]["dataSlice"]["length"].as_u64() {
+				Some(length) => base64_account(&spl_token_interface::ID, &data[..length as usize]),
+				None => base64_account(&spl_token_interface::ID, &data),
+			};
+			account["space"] = json!(TokenAccount::LEN);
+
+			account_info(&json!([account, Value::Null]))
+		});
+		let rpc = provider.to_rpc_client();
+		let pubkeys = [token_account, missing];
+
+		let accounts = rpc.get_multiple_accounts_parsed(&pubkeys, None).await?;
+		let [Some(account), None] = accounts.as_slice() else {
+			panic!("unexpected accounts: {accounts:?}");
+		};
+		let UiAccountData::Json(parsed) = &account.data else {
+			panic!("the token account should be parsed: {account:?}");
+		};
+		check!(parsed.program == "spl-token");
+		check!(parsed.parsed["info"]["tokenAmount"]["uiAmountString"] == "2.5");
+		check!(account.space == Some(TokenAccount::LEN as u64));
+
+		// the discriminator alone can't be parsed, so it's marked as unparsable
+		let data_slice = UiDataSliceConfig {
+			offset: 0,
+			length: 8,
+		};
+		let accounts = rpc
+			.get_multiple_accounts_parsed(&pubkeys, Some(data_slice))
+			.await?;
+		let [Some(account), None] = accounts.as_slice() else {
+			panic!("unexpected accounts: {accounts:?}");
+		};
+		let UiAccountData::Json(parsed) = &account.data else {
+			panic!("the token account should be marked as unparsable: {account:?}");
+		};
+		check!(parsed.program == UNPARSABLE_ACCOUNT_PROGRAM);
+		check!(parsed.parsed["data"][0] == discriminator);
+		check!(account.space == Some(TokenAccount::LEN as u64));
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn method_name_transform_is_applied() -> anyhow::Result<()> {
+		let provider = MockRpcProvider::new()
+			.on("svm_getSlot", |_| json!(42))
+			.on("svm_getBlockHeight", |_| json!(40));
+		let rpc = provider
+			.to_rpc_client()
+			.with_method_name_transform(|method| format!("svm_{method}"));
+
+		check!(rpc.get_slot().await? == 42);
+		check!(
+			rpc.get_slot_and_block_height(CommitmentConfig::confirmed())
+				.await? == (42, 40)
+		);
+		check!(provider.count(GetSlotRequest::NAME) == 0);
+		check!(provider.count("svm_getSlot") == 2);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn get_pda_accounts_derives_and_fetches_in_order() -> anyhow::Result<()> {
+		let program_id = Pubkey::new_unique();
+		let users = (0..3).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+		let seed_sets = users
+			.iter()
+			.map(|user| vec![b"user".to_vec(), user.to_bytes().to_vec()])
+			.collect::<Vec<_>>();
+		let missing = Pubkey::find_program_address(&[b"user", users[1].as_ref()], &program_id).0;
+		let provider = MockRpcProvider::new().on(GetMultipleAccountsRequest::NAME, move |params| {
+			let accounts = params[0]
+				.as_array()
+				.unwrap()
+				.iter()
+				.map(|pubkey| {
+					if pubkey.as_str() == Some(missing.to_string().as_str()) {
+						Value::Null
+					} else {
+						system_account(1_000)
+					}
+				})
+				.collect::<Vec<_>>();
+
+			account_info(&Value::Array(accounts))
+		});
+		let rpc = provider.to_rpc_client();
+		let accounts = rpc.get_pda_accounts(&program_id, &seed_sets).await?;
+
+		check!(accounts.len() == 3);
+		check!(provider.count(GetMultipleAccountsRequest::NAME) == 1);
+
+		for ((pubkey, bump, account), user) in accounts.iter().zip(&users) {
+			let expected = Pubkey::find_program_address(&[b"user", user.as_ref()], &program_id);
+
+			check!((*pubkey, *bump) == expected);
+			check!(account.is_some() == (*pubkey != missing));
+		}
+
+		Ok(())
+	}
+
+	fn base64_account(owner: &Pubkey, data: &[u8]) -> Value {
+		json!({
+			"data": [BASE64_STANDARD.encode(data), "base64"],
+			"executable": false,
+			"lamports": 1_000_000,
+			"owner": owner.to_string(),
+			"rentEpoch": 0,
+			"space": data.len()
+		})
+	}
+
+	fn lookup_table_data(deactivation_slot: Slot, addresses: &[Pubkey]) -> Vec<u8> {
+		AddressLookupTable::serialize_for_tests(AddressLookupTable {
+			meta: LookupTableMeta {
+				deactivation_slot,
+				..LookupTableMeta::default()
+			},
+			addresses: addresses.into(),
+		})
+		.unwrap()
+	}
+
+	/// The packed data of the `token_account`.
+	fn token_account_data(token_account: TokenAccount) -> Vec<u8> {
+		let mut data = vec![0; TokenAccount::LEN];
+		TokenAccount::pack(token_account, &mut data).unwrap();
+		data
+	}
+
+	/// The `mint` with the `decimals` and the clock sysvar, keyed by their
+	/// address, which are fetched to parse the token accounts of the `mint`.
+	fn token_fixture(mint: &Pubkey, decimals: u8) -> HashMap<String, Value> {
+		let mut mint_data = vec![0; Mint::LEN];
+		Mint::pack(
+			Mint {
+				decimals,
+				is_initialized: true,
+				..Default::default()
+			},
+			&mut mint_data,
+		)
+		.unwrap();
+		let clock_data = bincode::serialize(&Clock::default()).unwrap();
+
+		HashMap::from([
+			(
+				mint.to_string(),
+				base64_account(&spl_token_interface::ID, &mint_data),
+			),
+			(
+				sysvar::clock::ID.to_string(),
+				base64_account(&sysvar::ID, &clock_data),
+			),
+		])
+	}
+
+	/// Respond to `getMultipleAccounts` with the `accounts` keyed by their
+	/// address.
+	fn multiple_accounts_handler(
+		accounts: HashMap<String, Value>,
+	) -> impl Fn(&Value) -> Value + Send + Sync + 'static {
+		move |params| {
+			let values = params[0]
+				.as_array()
+				.unwrap()
+				.iter()
+				.map(|pubkey| accounts[pubkey.as_str().unwrap()].clone())
+				.collect::<Vec<_>>();
+
+			account_info(&Value::Array(values))
+		}
+	}
+
+	#[tokio::test]
+	async fn resolve_address_lookup_tables_loads_the_addresses() -> anyhow::Result<()> {
+		let first = Pubkey::new_unique();
+		let second = Pubkey::new_unique();
+		let deactivated = Pubkey::new_unique();
+		let cooling_down = Pubkey::new_unique();
+		let closed = Pubkey::new_unique();
+		let addresses = (0..4).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+		let tables = HashMap::from([
+			(
+				first.to_string(),
+				lookup_table_data(Slot::MAX, &addresses[..3]),
+			),
+			(
+				second.to_string(),
+				lookup_table_data(Slot::MAX, &addresses[3..]),
+			),
+			(deactivated.to_string(), lookup_table_data(100, &addresses)),
+			(cooling_down.to_string(), lookup_table_data(900, &addresses)),
+		]);
+		// the slot hashes of the 512 slots before the current slot 1000
+		let slot_hashes = (488..1000)
+			.map(|slot| (slot, Hash::new_unique()))
+			.collect::<Vec<_>>();
+		let slot_hashes_data = bincode::serialize(&SlotHashes::new(&slot_hashes))?;
+		let rpc = MockRpcProvider::new()
+			.on(GetMultipleAccountsRequest::NAME, move |params| {
+				let accounts = params[0]
+					.as_array()
+					.unwrap()
+					.iter()
+					.map(|pubkey| {
+						tables
+							.get(pubkey.as_str().unwrap())
+							.map_or(Value::Null, |data| {
+								base64_account(&solana_sdk_ids::address_lookup_table::id(), data)
+							})
+					})
+					.collect::<Vec<_>>();
+
+				account_info(&Value::Array(accounts))
+			})
+			.on(GetSlotRequest::NAME, |_| json!(1000))
+			.on(GetAccountInfoRequest::NAME, move |params| {
+				check!(params[0] == sysvar::slot_hashes::ID.to_string());
+				account_info(&base64_account(&sysvar::ID, &slot_hashes_data))
+			})
+			.to_rpc_client();
+		let lookup = |account_key: Pubkey, writable_indexes: Vec<u8>, readonly_indexes: Vec<u8>| {
+			MessageAddressTableLookup {
+				account_key,
+				writable_indexes,
+				readonly_indexes,
+			}
+		};
+		let message = |address_table_lookups| {
+			v0::Message {
+				address_table_lookups,
+				..v0::Message::default()
+			}
+		};
+
+		let loaded = rpc
+			.resolve_address_lookup_tables(&message(vec![
+				lookup(first, vec![2], vec![0, 1]),
+				lookup(second, vec![0], vec![]),
+			]))
+			.await?;
+
+		check!(loaded.writable == [addresses[2], addresses[3]]);
+		check!(loaded.readonly == [addresses[0], addresses[1]]);
+
+		let loaded = rpc
+			.resolve_address_lookup_tables(&message(vec![lookup(cooling_down, vec![1], vec![])]))
+			.await?;
+
+		check!(loaded.writable == [addresses[1]]);
+
+		for (lookups, expected_key, expected_reason) in [
+			(
+				vec![lookup(first, vec![3], vec![])],
+				first,
+				LookupTableError::InvalidIndex { index: 3, len: 3 },
+			),
+			(
+				vec![lookup(deactivated, vec![0], vec![])],
+				deactivated,
+				LookupTableError::Deactivated(100),
+			),
+			(
+				vec![lookup(closed, vec![0], vec![])],
+				closed,
+				LookupTableError::NotFound,
+			),
+		] {
+			let Err(ClientError::LookupTable { key, reason }) =
+				rpc.resolve_address_lookup_tables(&message(lookups)).await
+			else {
+				panic!("expected a lookup table error");
+			};
+
+			check!(key == expected_key);
+			check!(reason == expected_reason);
+		}
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn get_account_parsed_fetches_the_mint_decimals() -> anyhow::Result<()> {
+		let token_account = Pubkey::new_unique();
+		let mint = Pubkey::new_unique();
+		let token_account_data = token_account_data(TokenAccount {
+			mint,
+			owner: Pubkey::new_unique(),
+			amount: 1_500_000,
+			state: AccountState::Initialized,
+			..Default::default()
+		});
+		let mut accounts = token_fixture(&mint, 6);
+		accounts.extend([(
+			token_account.to_string(),
+			base64_account(&spl_token_interface::ID, &token_account_data),
+		)]);
+		let provider = MockRpcProvider::new().on(
+			GetMultipleAccountsRequest::NAME,
+			multiple_accounts_handler(accounts),
+		);
+		let rpc = provider.to_rpc_client();
+		let parsed = rpc.get_account_parsed(&token_account).await?.unwrap();
+
+		check!(parsed.program == "spl-token");
+		check!(parsed.parsed["info"]["tokenAmount"]["decimals"] == 6);
+		check!(parsed.parsed["info"]["tokenAmount"]["uiAmountString"] == "1.5");
+		check!(provider.count(GetMultipleAccountsRequest::NAME) == 2);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn get_token_accounts_by_owner_parsed_fetches_the_mint_decimals() -> anyhow::Result<()> {
+		let owner = Pubkey::new_unique();
+		let token_account = Pubkey::new_unique();
+		let mint = Pubkey::new_unique();
+		let token_account_data = token_account_data(TokenAccount {
+			mint,
+			owner,
+			amount: 1_500_000,
+			state: AccountState::Initialized,
+			..Default::default()
+		});
+		let keyed_account = json!({
+			"pubkey": token_account.to_string(),
+			"account": base64_account(&spl_token_interface::ID, &token_account_data),
+		});
+		let accounts = token_fixture(&mint, 6);
+		let provider = MockRpcProvider::new()
+			.on(GetTokenAccountsByOwnerRequest::NAME, move |params| {
+				assert_eq!(params[2]["encoding"], "base64");
+
+				if params[1]["programId"] == spl_token_interface::ID.to_string() {
+					account_info(&json!([keyed_account]))
+				} else {
+					account_info(&json!([]))
+				}
+			})
+			.on(
+				GetMultipleAccountsRequest::NAME,
+				multiple_accounts_handler(accounts),
+			);
+		let rpc = provider.to_rpc_client();
+		let parsed = rpc.get_token_accounts_by_owner_parsed(&owner, None).await?;
+
+		check!(parsed.len() == 1);
+		let (pubkey, parsed_account) = &parsed[0];
+		check!(*pubkey == token_account);
+		check!(parsed_account.program == "spl-token");
+		check!(parsed_account.parsed["info"]["tokenAmount"]["decimals"] == 6);
+		check!(parsed_account.parsed["info"]["tokenAmount"]["uiAmountString"] == "1.5");
+		check!(provider.count(GetTokenAccountsByOwnerRequest::NAME) == 2);
+		check!(provider.count(GetMultipleAccountsRequest::NAME) == 1);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn get_token_accounts_by_delegate_parsed_filters_by_mint() -> anyhow::Result<()> {
+		let delegate = Pubkey::new_unique();
+		let token_account = Pubkey::new_unique();
+		let mint = Pubkey::new_unique();
+		let token_account_data = token_account_data(TokenAccount {
+			mint,
+			owner: Pubkey::new_unique(),
+			amount: 2_000_000,
+			delegate: Some(delegate).into(),
+			delegated_amount: 500_000,
+			state: AccountState::Initialized,
+			..Default::default()
+		});
+		let keyed_account = json!({
+			"pubkey": token_account.to_string(),
+			"account": base64_account(&spl_token_interface::ID, &token_account_data),
+		});
+		let accounts = token_fixture(&mint, 6);
+		let provider = MockRpcProvider::new()
+			.on(GetTokenAccountsByDelegateRequest::NAME, move |params| {
+				assert_eq!(params[0], delegate.to_string());
+				assert_eq!(params[1]["mint"], mint.to_string());
+
+				account_info(&json!([keyed_account]))
+			})
+			.on(
+				GetMultipleAccountsRequest::NAME,
+				multiple_accounts_handler(accounts),
+			);
+		let rpc = provider.to_rpc_client();
+		let parsed = rpc
+			.get_token_accounts_by_delegate_parsed(&delegate, Some(TokenAccountsFilter::Mint(mint)))
+			.await?;
+
+		check!(parsed.len() == 1);
+		let (pubkey, parsed_account) = &parsed[0];
+		check!(*pubkey == token_account);
+		check!(parsed_account.parsed["info"]["delegate"] == delegate.to_string());
+		check!(parsed_account.parsed["info"]["delegatedAmount"]["uiAmountString"] == "0.5");
+		check!(provider.count(GetTokenAccountsByDelegateRequest::NAME) == 1);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn reparse_account_notifications_caches_the_mint() -> anyhow::Result<()> {
+		let token_account = Pubkey::new_unique();
+		let mint = Pubkey::new_unique();
+		let token_account_data = token_account_data(TokenAccount {
+			mint,
+			owner: Pubkey::new_unique(),
+			amount: 2_500_000,
+			state: AccountState::Initialized,
+			..Default::default()
+		});
+		let accounts = token_fixture(&mint, 6);
+		let provider = MockRpcProvider::new().on(
+			GetMultipleAccountsRequest::NAME,
+			multiple_accounts_handler(accounts),
+		);
+		let rpc = provider.to_rpc_client();
+		let notification: SubscriptionResponse<GetAccountInfoResponse> =
+			serde_json::from_value(json!({
+				"jsonrpc": "2.0",
+				"method": "accountNotification",
+				"params": {
+					"result": account_info(&base64_account(&spl_token_interface::ID, &token_account_data)),
+					"subscription": 1
+				}
+			}))?;
+		let notifications = rpc
+			.reparse_account_notifications(
+				token_account,
+				stream::iter([notification.clone(), notification]),
+			)
+			.collect::<Vec<_>>()
+			.await;
+
+		for notification in notifications {
+			let Some(UiAccountData::Json(parsed)) =
+				notification.params.result.value.map(|account| account.data)
+			else {
+				panic!("expected the notification to be parsed");
+			};
+
+			check!(parsed.program == "spl-token");
+			check!(parsed.parsed["info"]["tokenAmount"]["uiAmountString"] == "2.5");
+		}
+
+		check!(provider.count(GetMultipleAccountsRequest::NAME) == 1);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn get_program_accounts_stream_as_decodes_each_account() -> anyhow::Result<()> {
+		let owner = Pubkey::new_unique();
+		let pubkeys = [Pubkey::new_unique(), Pubkey::new_unique()];
+		let keyed_accounts = pubkeys
+			.iter()
+			.zip([7_u64, 42])
+			.map(|(pubkey, value)| {
+				json!({
+					"pubkey": pubkey.to_string(),
+					"account": base64_account(&owner, &bincode::serialize(&value).unwrap())
+				})
+			})
+			.collect::<Vec<_>>();
+		let provider = MockRpcProvider::new().on(GetProgramAccountsRequest::NAME, move |_| {
+			Value::Array(keyed_accounts.clone())
+		});
+		let rpc = provider.to_rpc_client();
+		let accounts = rpc
+			.get_program_accounts_stream_as::<u64>(&owner, RpcProgramAccountsConfig::default())
+			.try_collect::<Vec<_>>()
+			.await?;
+
+		check!(accounts == vec![(pubkeys[0], 7), (pubkeys[1], 42)]);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn default_data_slice_applies_to_base64_fetches() -> anyhow::Result<()> {
+		let owner = Pubkey::new_unique();
+		let pubkey = Pubkey::new_unique();
+		let data_slice = json!({ "offset": 0, "length": 8 });
+		let expected_slice = data_slice.clone();
+		let received_slices = Arc::new(Mutex::new(vec![]));
+		let slices = received_slices.clone();
+		let provider = MockRpcProvider::new()
+			.on(GetAccountInfoRequest::NAME, {
+				let expected_slice = expected_slice.clone();
+				move |params| {
+					check!(params[1]["dataSlice"] == expected_slice);
+					json!({
+						"context": { "slot": 1 },
+						"value": base64_account(&owner, &[1; 8])
+					})
+				}
+			})
+			.on(GetMultipleAccountsRequest::NAME, move |params| {
+				slices
+					.lock()
+					.unwrap()
+					.push(params[1].get("dataSlice").cloned());
+
+				json!({
+					"context": { "slot": 1 },
+					"value": [base64_account(&owner, &[1; 8])]
+				})
+			});
+		let rpc = provider.to_rpc_client().with_data_slice(UiDataSliceConfig {
+			offset: 0,
+			length: 8,
+		});
+
+		check!(rpc.data_slice().is_some());
+		check!(rpc.get_account_data(&pubkey).await? == vec![1; 8]);
+		rpc.get_multiple_accounts(&[pubkey]).await?;
+		rpc.get_multiple_accounts_parsed(&[pubkey], None).await?;
+		check!(provider.count(GetAccountInfoRequest::NAME) == 1);
+		check!(provider.count(GetMultipleAccountsRequest::NAME) == 2);
+		// the default slice isn't applied to the accounts which are parsed
+		check!(*received_slices.lock().unwrap() == vec![Some(expected_slice), None]);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn wait_slots_polls_until_the_slot_advances() -> anyhow::Result<()> {
+		let slot = Arc::new(AtomicUsize::new(100));
+		let provider = MockRpcProvider::new().on(GetSlotRequest::NAME, move |params| {
+			check!(params[0]["commitment"] == "processed");
+			json!(slot.fetch_add(1, Ordering::SeqCst))
+		});
+		let rpc = provider.to_rpc_client();
+		let poll_interval = Duration::from_millis(1);
+		let waited = rpc.wait_slots_with_poll_interval(3, poll_interval).await?;
+
+		check!(waited >= 103);
+		check!(provider.count(GetSlotRequest::NAME) >= 4);
+		check!(rpc.wait_slots_with_poll_interval(0, poll_interval).await? == 104);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn get_program_accounts_with_filters_sends_filters() -> anyhow::Result<()> {
+		let owner = Pubkey::new_unique();
+		let pubkeys = [Pubkey::new_unique(), Pubkey::new_unique()];
+		let keyed_accounts = pubkeys
+			.iter()
+			.zip([vec![1, 2, 3, 4], vec![1, 2, 9]])
+			.map(|(pubkey, data)| {
+				json!({
+					"pubkey": pubkey.to_string(),
+					"account": base64_account(&owner, &data)
+				})
+			})
+			.collect::<Vec<_>>();
+		let provider = MockRpcProvider::new().on(GetProgramAccountsRequest::NAME, move |params| {
+			check!(params[1]["encoding"] == "base64");
+
+			match params[1].get("filters") {
+				None => Value::Array(keyed_accounts.clone()),
+				Some(filters) => {
+					check!(
+						filters
+							== &json!([
+								{ "dataSize": 4 },
+								{ "memcmp": { "offset": 1, "bytes": "gCB", "encoding": "base58" } },
+								{ "memcmp": { "offset": 0, "bytes": "AQI=", "encoding": "base64" } },
+							])
+					);
+					Value::Array(keyed_accounts[..1].to_vec())
+				}
+			}
+		});
+		let rpc = provider.to_rpc_client();
+		let filters = vec![
+			RpcFilterType::DataSize(4),
+			RpcFilterType::Memcmp(Memcmp::new_base58_encoded(1, &[2, 3, 4])),
+			RpcFilterType::Memcmp(Memcmp::new(
+				0,
+				MemcmpEncodedBytes::Base64(BASE64_STANDARD.encode([1, 2])),
+			)),
+		];
+		let filtered = rpc
+			.get_program_accounts_with_filters(&owner, filters)
+			.await?;
+		let unfiltered = rpc
+			.get_program_accounts_with_filters(&owner, vec![])
+			.await?;
+
+		check!(filtered.len() == 1);
+		check!(filtered[0].0 == pubkeys[0]);
+		check!(filtered[0].1.data == vec![1, 2, 3, 4]);
+		check!(unfiltered == rpc.get_program_accounts(&owner).await?);
+		check!(unfiltered.len() == 2);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn estimate_prioritization_fee_uses_the_percentile() -> anyhow::Result<()> {
+		let provider =
+			MockRpcProvider::new().on(GetRecentPrioritizationFeesRequest::NAME, |params| {
+				let fee = if params.as_array().is_some_and(|params| !params.is_empty()) {
+					10
+				} else {
+					0
+				};
+
+				json!([
+					{ "slot": 1, "prioritizationFee": fee },
+					{ "slot": 2, "prioritizationFee": fee + 100 },
+					{ "slot": 3, "prioritizationFee": fee + 500 },
+					{ "slot": 4, "prioritizationFee": fee + 1000 }
+				])
+			});
+		let rpc = provider.to_rpc_client();
+
+		check!(rpc.estimate_prioritization_fee(&[], 50).await? == 100);
+		check!(rpc.estimate_prioritization_fee(&[], 100).await? == 1000);
+		check!(
+			rpc.estimate_prioritization_fee(&[Pubkey::new_unique()], 75)
+				.await? == 510
+		);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn with_priority_fee_instructions_prepends_compute_budget() -> anyhow::Result<()> {
+		let payer = Pubkey::new_unique();
+		let writable = Pubkey::new_unique();
+		let instruction = Instruction::new_with_bytes(
+			Pubkey::new_unique(),
+			&[1, 2, 3],
+			vec![
+				AccountMeta::new(payer, true),
+				AccountMeta::new(writable, false),
+			],
+		);
+		let provider = MockRpcProvider::new()
+			.on(GetRecentPrioritizationFeesRequest::NAME, |params| {
+				check!(params[0].as_array().map(Vec::len) == Some(2));
+				json!([
+					{ "slot": 1, "prioritizationFee": 0 },
+					{ "slot": 2, "prioritizationFee": 100 },
+					{ "slot": 3, "prioritizationFee": 500 },
+					{ "slot": 4, "prioritizationFee": 1000 }
+				])
+			})
+			.on(SimulateTransactionRequest::NAME, |_| {
+				json!({
+					"context": { "slot": 1 },
+					"value": { "err": null, "logs": [], "accounts": null, "unitsConsumed": 10_000 }
+				})
+			});
+		let rpc = provider.to_rpc_client();
+		let config = PriorityFeeConfig::builder()
+			.urgency(FeeUrgency::High)
+			.build();
+		let instructions = rpc
+			.with_priority_fee_instructions(&payer, std::slice::from_ref(&instruction), config)
+			.await?;
+
+		check!(
+			instructions
+				== vec![
+					ComputeBudgetInstruction::set_compute_unit_price(1000),
+					ComputeBudgetInstruction::set_compute_unit_limit(11_000),
+					instruction,
+				]
+		);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn get_slot_and_block_height_in_one_batch() -> anyhow::Result<()> {
+		let provider = MockRpcProvider::new()
+			.on(GetSlotRequest::NAME, |params| {
+				check!(params[0]["commitment"] == "finalized");
+				json!(350)
+			})
+			.on(GetBlockHeightRequest::NAME, |params| {
+				check!(params[0]["commitment"] == "finalized");
+				json!(300)
+			});
+		let rpc = provider.to_rpc_client();
+		let result = rpc
+			.get_slot_and_block_height(CommitmentConfig::finalized())
+			.await?;
+
+		check!(result == (350, 300));
+		check!(provider.batch_count() == 1);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn get_block_uses_the_config() -> anyhow::Result<()> {
+		let blockhash = Hash::new_unique();
+		let previous_blockhash = Hash::new_unique();
+		let provider = MockRpcProvider::new().on(GetBlockRequest::NAME, move |params| {
+			check!(params[0] == 5);
+			check!(params[1]["transactionDetails"] == "signatures");
+			check!(params[1]["rewards"] == false);
+			check!(params[1]["maxSupportedTransactionVersion"] == 0);
+			json!({
+				"blockhash": blockhash.to_string(),
+				"previousBlockhash": previous_blockhash.to_string(),
+				"parentSlot": 4,
+				"signatures": [],
+				"blockTime": 1_700_000_000,
+				"blockHeight": 3,
+			})
+		});
+		let rpc = provider.to_rpc_client();
+		let config = RpcBlockConfig {
+			transaction_details: Some(TransactionDetails::Signatures),
+			rewards: Some(false),
+			max_supported_transaction_version: Some(0),
+			..Default::default()
+		};
+		let block = rpc
+			.get_block_with_config(5, config)
+			.await?
+			.expect("the block exists");
+
+		check!(block.blockhash == blockhash);
+		check!(block.previous_blockhash == previous_blockhash);
+		check!(block.parent_slot == 4);
+		check!(block.signatures == Some(vec![]));
+		check!(block.transactions.is_none());
+		check!(block.rewards.is_none());
+		check!(block.block_height == Some(3));
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn get_block_of_a_skipped_slot_is_none() -> anyhow::Result<()> {
+		let rpc = MockRpcProvider::new()
+			.on_error(
+				GetBlockRequest::NAME,
+				json!({
+					"code": JSON_RPC_SERVER_ERROR_SLOT_SKIPPED,
+					"message": "Slot 5 was skipped, or missing due to ledger jump to recent snapshot",
+				}),
+			)
+			.to_rpc_client();
+
+		check!(rpc.get_block(5).await?.is_none());
+
+		let rpc = MockRpcProvider::new()
+			.on_error(
+				GetBlockRequest::NAME,
+				json!({ "code": -32_602, "message": "Invalid param" }),
+			)
+			.to_rpc_client();
+
+		check!(rpc.get_block(5).await.is_err());
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn validator_health_reports_delinquency() -> anyhow::Result<()> {
+		let voting = Pubkey::new_unique();
+		let delinquent = Pubkey::new_unique();
+		let vote_account = |vote_pubkey: &Pubkey, last_vote: u64| {
+			json!({
+				"votePubkey": vote_pubkey.to_string(),
+				"nodePubkey": Pubkey::new_unique().to_string(),
+				"activatedStake": 5_000,
+				"commission": 10,
+				"epochVoteAccount": true,
+				"epochCredits": [],
+				"lastVote": last_vote,
+				"rootSlot": 0
+			})
+		};
+		let current = json!([vote_account(&voting, 995)]);
+		let delinquents = json!([vote_account(&delinquent, 800)]);
+		let provider = MockRpcProvider::new()
+			.on(
+				GetVoteAccountsRequest::NAME,
+				move |_| json!({ "current": current, "delinquent": delinquents }),
+			)
+			.on(GetSlotRequest::NAME, |_| json!(1_000));
+		let rpc = provider.to_rpc_client();
+
+		check!(
+			rpc.validator_health(&voting).await?
+				== ValidatorHealth {
+					is_delinquent: false,
+					last_vote: 995,
+					slots_behind: 5,
+					activated_stake: 5_000,
+					commission: 10,
+				}
+		);
+
+		let health = rpc.validator_health(&delinquent).await?;
+		check!(health.is_delinquent);
+		check!(health.slots_behind == 200);
+
+		let missing = Pubkey::new_unique();
+		let Err(ClientError::ValidatorNotFound { vote_pubkey }) =
+			rpc.validator_health(&missing).await
+		else {
+			panic!("expected a validator not found error");
+		};
+		check!(vote_pubkey == missing);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn get_rent_matches_minimum_balance() -> anyhow::Result<()> {
+		let rent = Rent {
+			lamports_per_byte_year: 3_480,
+			exemption_threshold: 2.0,
+			burn_percent: 50,
+		};
+		let rent_data = bincode::serialize(&rent)?;
+		let provider = MockRpcProvider::new()
+			.on(GetAccountInfoRequest::NAME, move |_| {
+				account_info(&base64_account(&sysvar::ID, &rent_data))
+			})
+			.on(GetMinimumBalanceForRentExemptionRequest::NAME, |_| {
+				json!(1_586_880)
+			});
+		let rpc = provider.to_rpc_client();
+		let fetched = rpc.get_rent().await?;
+
+		check!(fetched == rent);
+		check!(
+			fetched.minimum_balance(100) == rpc.get_minimum_balance_for_rent_exemption(100).await?
+		);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn get_program_info_follows_program_data() -> anyhow::Result<()> {
+		let program_id = Pubkey::new_unique();
+		let program_data_address = Pubkey::new_unique();
+		let authority = Pubkey::new_unique();
+		let program = bincode::serialize(&UpgradeableLoaderState::Program {
+			programdata_address: program_data_address,
+		})?;
+		let mut program_data = bincode::serialize(&UpgradeableLoaderState::ProgramData {
+			slot: 42,
+			upgrade_authority_address: Some(authority),
+		})?;
+		program_data.extend([0; 1000]);
+		let provider = MockRpcProvider::new().on(GetAccountInfoRequest::NAME, move |params| {
+			let data = if params[0] == program_id.to_string() {
+				&program
+			} else {
+				&program_data
+			};
+
+			account_info(&base64_account(&bpf_loader_upgradeable::ID, data))
+		});
+		let rpc = provider.to_rpc_client();
+		let info = rpc.get_program_info(&program_id).await?;
+
+		check!(
+			info == ProgramInfo {
+				authority: Some(authority),
+				last_deployed_slot: 42,
+				data_len: 1000,
+				is_immutable: false,
+			}
+		);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn get_multiple_parsed_accounts_isolates_parse_errors() -> anyhow::Result<()> {
+		let token_account = Pubkey::new_unique();
+		let mint = Pubkey::new_unique();
+		let corrupt_token_account = Pubkey::new_unique();
+		let missing = Pubkey::new_unique();
+		let token_account_data = token_account_data(TokenAccount {
+			mint,
+			owner: Pubkey::new_unique(),
+			amount: 2_500,
+			state: AccountState::Initialized,
+			..Default::default()
+		});
+		let mut accounts = token_fixture(&mint, 3);
+		accounts.extend([
+			(
+				token_account.to_string(),
+				base64_account(&spl_token_interface::ID, &token_account_data),
+			),
+			(
+				corrupt_token_account.to_string(),
+				base64_account(&spl_token_interface::ID, &[1, 2, 3]),
+			),
+			(missing.to_string(), Value::Null),
+		]);
+		let provider = MockRpcProvider::new().on(
+			GetMultipleAccountsRequest::NAME,
+			multiple_accounts_handler(accounts),
+		);
+		let rpc = provider.to_rpc_client();
+		let pubkeys = [token_account, corrupt_token_account, missing];
+		let parsed = rpc.get_multiple_parsed_accounts(&pubkeys).await?;
+
+		let [Ok(Some(token)), Err(error), Ok(None)] = parsed.as_slice() else {
+			panic!("unexpected accounts: {parsed:?}");
+		};
+		check!(token.program == "spl-token");
+		check!(token.parsed["info"]["tokenAmount"]["uiAmountString"] == "2.5");
+		check!(matches!(error, ParseAccountError::AccountNotParsable(_)));
+		check!(provider.count(GetMultipleAccountsRequest::NAME) == 2);
+		check!(
+			rpc.get_account_parsed(&corrupt_token_account)
+				.await
+				.is_err()
+		);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn get_account_auto_parsed_falls_back_to_base64() -> anyhow::Result<()> {
+		let nonce = Pubkey::new_unique();
+		let unknown = Pubkey::new_unique();
+		let owner = Pubkey::new_unique();
+		let provider = MockRpcProvider::new().on(GetMultipleAccountsRequest::NAME, move |params| {
+			let account = if params[0][0] == nonce.to_string() {
+				system_account(1_000_000)
+			} else {
+				base64_account(&owner, &[1, 2, 3])
+			};
+
+			account_info(&json!([account]))
+		});
+		let rpc = provider.to_rpc_client();
+
+		let parsed = rpc.get_account_auto_parsed(&unknown).await?.unwrap();
+		check!(parsed.program == UNPARSABLE_ACCOUNT_PROGRAM);
+		check!(parsed.parsed == json!({ "owner": owner.to_string(), "data": ["AQID", "base64"] }));
+		check!(parsed.space == 3);
+
+		// an empty system account isn't a nonce account so it can't be parsed
+		let parsed = rpc.get_account_auto_parsed(&nonce).await?.unwrap();
+		check!(parsed.program == UNPARSABLE_ACCOUNT_PROGRAM);
+		check!(parsed.parsed["owner"] == "11111111111111111111111111111111");
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn estimate_stake_cooldown_uses_stake_history() -> anyhow::Result<()> {
+		use solana_stake_interface::stake_flags::StakeFlags;
+		use solana_stake_interface::stake_history::StakeHistoryEntry;
+		use solana_stake_interface::state::Delegation;
+		use solana_stake_interface::state::Meta;
+		use solana_stake_interface::state::Stake;
+
+		let stake_account = Pubkey::new_unique();
+		let mut delegation = Delegation::new(&Pubkey::new_unique(), 1_000_000, Epoch::MAX);
+		delegation.deactivation_epoch = 10;
+		let stake = bincode::serialize(&StakeStateV2::Stake(
+			Meta::default(),
+			Stake {
+				delegation,
+				credits_observed: 0,
+			},
+			StakeFlags::empty(),
+		))?;
+		let mut history = StakeHistory::default();
+		history.add(
+			9,
+			StakeHistoryEntry {
+				effective: 10_000_000,
+				activating: 0,
+				deactivating: 1_000_000,
+			},
+		);
+		let history = bincode::serialize(&history)?;
+		let provider = MockRpcProvider::new()
+			.on(GetAccountInfoRequest::NAME, move |params| {
+				if params[0] == stake_account.to_string() {
+					account_info(&base64_account(&solana_sdk_ids::stake::ID, &stake))
+				} else {
+					account_info(&base64_account(&sysvar::ID, &history))
+				}
+			})
+			.on(GetEpochInfoRequest::NAME, |_| {
+				json!({
+					"absoluteSlot": 4_321_000,
+					"blockHeight": 4_320_000,
+					"epoch": 10,
+					"slotIndex": 1_000,
+					"slotsInEpoch": 432_000,
+					"transactionCount": 1
+				})
+			})
+			.on(GetEpochScheduleRequest::NAME, |_| {
+				json!({
+					"firstNormalEpoch": 0,
+					"firstNormalSlot": 0,
+					"leaderScheduleSlotOffset": 432_000,
+					"slotsPerEpoch": 432_000,
+					"warmup": false
+				})
+			});
+		let rpc = provider.to_rpc_client();
+		let estimate = rpc.estimate_stake_cooldown(&stake_account).await?;
+
+		// 9% of the cluster stake can cool down each epoch
+		check!(
+			estimate
+				== StakeCooldownEstimate {
+					deactivation_epoch: 10,
+					deactivating_stake: 1_000_000,
+					inactive_epoch: 12,
+					inactive_slot: 5_184_000,
+					time_remaining: Duration::from_millis(863_000 * DEFAULT_MS_PER_SLOT),
+				}
+		);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn signatures_for_address_stream_pages_backward() -> anyhow::Result<()> {
+		let address = Pubkey::new_unique();
+		let history = (1..=5_u8)
+			.rev()
+			.map(|index| Signature::from([index; 64]))
+			.collect::<Vec<_>>();
+		let status = |signature: &Signature| {
+			json!({
+				"signature": signature.to_string(),
+				"slot": 100,
+				"err": null,
+				"memo": null,
+				"blockTime": null,
+				"confirmationStatus": "finalized"
+			})
+		};
+		let pages = history.clone();
+		let provider =
+			MockRpcProvider::new().on(GetSignaturesForAddressRequest::NAME, move |params| {
+				let limit = params[1]["limit"].as_u64().unwrap() as usize;
+				let start = params[1]["before"].as_str().map_or(0, |before| {
+					pages
+						.iter()
+						.position(|signature| signature.to_string() == before)
+						.unwrap() + 1
+				});
+
+				Value::Array(pages.iter().skip(start).take(limit).map(status).collect())
+			});
+		let rpc = provider.to_rpc_client();
+		let config = GetConfirmedSignaturesForAddress2Config {
+			limit: Some(2),
+			..Default::default()
+		};
+		let signatures = rpc
+			.signatures_for_address_stream(&address, config)
+			.map_ok(|status| status.signature)
+			.try_collect::<Vec<_>>()
+			.await?;
+
+		check!(signatures == history);
+		check!(provider.count(GetSignaturesForAddressRequest::NAME) == 3);
+
+		// a server which ignores `before` doesn't page forever
+		let page = history[..2].to_vec();
+		let provider = MockRpcProvider::new().on(GetSignaturesForAddressRequest::NAME, move |_| {
+			Value::Array(page.iter().map(status).collect())
+		});
+		let rpc = provider.to_rpc_client();
+		let config = GetConfirmedSignaturesForAddress2Config {
+			until: Some(Signature::from([9; 64])),
+			limit: Some(2),
+			..Default::default()
+		};
+		let signatures = rpc
+			.signatures_for_address_stream(&address, config)
+			.try_collect::<Vec<_>>()
+			.await?;
+
+		check!(signatures.len() == 4);
+		check!(provider.count(GetSignaturesForAddressRequest::NAME) == 2);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn simulate_unsigned_transaction_with_parsed_accounts() -> anyhow::Result<()> {
+		let payer = Pubkey::new_unique();
+		let recipient = Pubkey::new_unique();
+		let instruction = solana_system_interface::instruction::transfer(&payer, &recipient, 1_000);
+		let message = Message::new(&[instruction], Some(&payer));
+		// unsigned, the signature is only a placeholder
+		let transaction = VersionedTransaction::from(Transaction::new_unsigned(message));
+		let addresses = vec![recipient.to_string()];
+		let provider = MockRpcProvider::new().on(SimulateTransactionRequest::NAME, move |params| {
+			assert_eq!(params[1]["sigVerify"], false);
+			assert_eq!(params[1]["replaceRecentBlockhash"], true);
+			assert_eq!(params[1]["accounts"]["encoding"], "jsonParsed");
+			assert_eq!(
+				params[1]["accounts"]["addresses"],
+				json!([recipient.to_string()])
+			);
+
+			json!({
+				"context": { "slot": 100 },
+				"value": {
+					"err": null,
+					"logs": ["Program 11111111111111111111111111111111 success"],
+					"accounts": [{
+						"lamports": 1_000,
+						"data": {
+							"program": "nonce",
+							"parsed": { "type": "uninitialized" },
+							"space": 80
+						},
+						"owner": "11111111111111111111111111111111",
+						"executable": false,
+						"rentEpoch": 0,
+						"space": 80
+					}],
+					"unitsConsumed": 150,
+					"returnData": null
+				}
+			})
+		});
+		let rpc = provider.to_rpc_client();
+		let config = RpcSimulateTransactionConfig {
+			sig_verify: false,
+			replace_recent_blockhash: Some(true),
+			encoding: Some(UiTransactionEncoding::Base64),
+			accounts: Some(RpcSimulateTransactionAccountsConfig {
+				encoding: Some(UiAccountEncoding::JsonParsed),
+				addresses,
+			}),
+			..Default::default()
+		};
+		let simulation = rpc
+			.simulate_transaction_with_config(&transaction, config)
+			.await?
+			.value;
+
+		check!(simulation.err.is_none());
+		check!(simulation.units_consumed == Some(150));
+		check!(simulation.logs.unwrap().len() == 1);
+		let accounts = simulation.accounts.unwrap();
+		let Some(UiAccount {
+			data: UiAccountData::Json(parsed),
+			..
+		}) = &accounts[0]
+		else {
+			panic!("expected a parsed account: {accounts:?}");
+		};
+		check!(parsed.program == "nonce");
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn latest_blockhash_with_expiry() -> anyhow::Result<()> {
+		let blockhash = Hash::new_unique();
+		let provider = MockRpcProvider::new()
+			.on(GetLatestBlockhashRequest::NAME, move |params| {
+				check!(params[0]["commitment"] == "confirmed");
+				json!({
+					"context": { "slot": 1 },
+					"value": {
+						"blockhash": blockhash.to_string(),
+						"lastValidBlockHeight": 3090,
+					},
+				})
+			})
+			.on(IsBlockhashValidRequest::NAME, move |params| {
+				check!(params[0] == blockhash.to_string());
+				check!(params[1]["commitment"] == "processed");
+				json!({ "context": { "slot": 1 }, "value": false })
+			});
+		let rpc = provider.to_rpc_client();
+		let latest = rpc.get_latest_blockhash_with_expiry().await?;
+
+		check!(latest.blockhash == blockhash);
+		check!(latest.last_valid_block_height == 3090);
+		check!(
+			!rpc.is_blockhash_valid(&latest.blockhash, CommitmentConfig::processed())
+				.await?
+		);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn confirm_transaction_searches_the_history_after_the_timeout() -> anyhow::Result<()> {
+		let provider = MockRpcProvider::new().on(GetSignatureStatusesRequest::NAME, |params| {
+			if params[1]["searchTransactionHistory"] == true {
+				json!({
+					"context": { "slot": 1 },
+					"value": [{
+						"slot": 1,
+						"confirmations": null,
+						"err": null,
+						"status": { "Ok": null },
+						"confirmationStatus": "finalized",
+					}],
+				})
+			} else {
+				json!({ "context": { "slot": 1 }, "value": [null] })
+			}
+		});
+		let rpc = provider.to_rpc_client();
+		let config = ConfirmTransactionConfig::builder()
+			.commitment(CommitmentConfig::finalized())
+			.timeout(Duration::from_millis(10))
+			.build();
+		let is_success = rpc
+			.confirm_transaction_with_config(&Signature::from([1; 64]), config)
+			.await?;
+
+		check!(is_success);
+		check!(provider.count(GetSignatureStatusesRequest::NAME) >= 2);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn confirm_transaction_times_out_when_the_commitment_is_not_reached() -> anyhow::Result<()>
+	{
+		let signature = Signature::from([1; 64]);
+		let provider = MockRpcProvider::new().on(GetSignatureStatusesRequest::NAME, |_| {
+			json!({
+				"context": { "slot": 1 },
+				"value": [{
+					"slot": 1,
+					"confirmations": 1,
+					"err": null,
+					"status": { "Ok": null },
+					"confirmationStatus": "confirmed",
+				}],
+			})
+		});
+		let rpc = provider.to_rpc_client();
+		let config = ConfirmTransactionConfig::builder()
+			.commitment(CommitmentConfig::finalized())
+			.timeout(Duration::from_millis(10))
+			.build();
+		let result = rpc
+			.confirm_transaction_with_config(&signature, config)
+			.await;
+
+		let Err(ClientError::ConfirmationTimeout {
+			signature: timed_out,
+		}) = result
+		else {
+			panic!("the confirmation should time out");
+		};
+		check!(timed_out == signature);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn signature_statuses_are_aligned_with_the_signatures() -> anyhow::Result<()> {
+		let provider = MockRpcProvider::new().on(GetSignatureStatusesRequest::NAME, |params| {
+			check!(params[0].as_array().map(Vec::len) == Some(2));
+			check!(params[1]["searchTransactionHistory"] == true);
+			json!({
+				"context": { "slot": 1 },
+				"value": [null, {
+					"slot": 72,
+					"confirmations": 10,
+					"err": null,
+					"status": { "Ok": null },
+					"confirmationStatus": "confirmed",
+				}],
+			})
+		});
+		let rpc = provider.to_rpc_client();
+		let statuses = rpc
+			.get_signature_statuses_with_history(&[
+				Signature::from([1; 64]),
+				Signature::from([2; 64]),
+			])
+			.await?;
+
+		check!(statuses[0].is_none());
+		let status = statuses[1].as_ref().expect("the second signature is known");
+		check!(status.slot == 72);
+		check!(status.confirmations == Some(10));
+		check!(status.confirmation_status == Some(TransactionConfirmationStatus::Confirmed));
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn too_many_signature_statuses_are_rejected() -> anyhow::Result<()> {
+		let provider = MockRpcProvider::new();
+		let rpc = provider.to_rpc_client();
+		let signatures = vec![Signature::default(); MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS + 1];
+
+		check!(let Err(ClientError::Other(_)) = rpc.get_signature_statuses(&signatures).await);
+		check!(
+			let Err(ClientError::Other(_)) = rpc.get_signature_statuses_with_history(&signatures).await
+		);
+		check!(provider.count(GetSignatureStatusesRequest::NAME) == 0);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn cluster_nodes_without_advertised_addresses() -> anyhow::Result<()> {
+		let node = Pubkey::new_unique();
+		let provider = MockRpcProvider::new().on(GetClusterNodesRequest::NAME, move |_| {
+			json!([{
+				"pubkey": node.to_string(),
+				"gossip": "10.239.6.48:8001",
+				"tpu": null,
+				"rpc": null,
+				"version": "2.1.0",
+				"featureSet": 3_294_202_862_u32,
+				"shredVersion": 50093,
+			}])
+		});
+		let nodes = provider.to_rpc_client().get_cluster_nodes().await?;
+
+		check!(
+			nodes
+				== [RpcContactInfoWasm {
+					pubkey: node,
+					gossip: Some("10.239.6.48:8001".into()),
+					tpu: None,
+					rpc: None,
+					version: Some("2.1.0".into()),
+					feature_set: Some(3_294_202_862),
+					shred_version: Some(50093),
+				}]
+		);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn node_status_reports_an_unhealthy_node() -> anyhow::Result<()> {
+		let provider = MockRpcProvider::new()
+			.on_error(
+				GetHealthRequest::NAME,
+				json!({
+					"code": -32005,
+					"message": "Node is behind by 42 slots",
+					"data": { "numSlotsBehind": 42 },
+				}),
+			)
+			.on(
+				GetVersionRequest::NAME,
+				|_| json!({ "solana-core": "2.1.0", "feature-set": 3_294_202_862_u32 }),
+			);
+		let rpc = provider.to_rpc_client();
+
+		check!(
+			let Err(ClientError::Rpc(RpcError::NodeUnhealthy {
+				num_slots_behind: Some(42)
+			})) = rpc.get_health().await
+		);
+
+		let status = rpc.node_status().await?;
+		check!(!status.healthy);
+		check!(status.num_slots_behind == Some(42));
+		check!(status.version.solana_core == "2.1.0");
+		check!(status.version.feature_set == Some(3_294_202_862));
+
+		let provider = MockRpcProvider::new()
+			.on(GetHealthRequest::NAME, |_| json!("ok"))
+			.on(
+				GetVersionRequest::NAME,
+				|_| json!({ "solana-core": "2.1.0" }),
+			);
+		let status = provider.to_rpc_client().node_status().await?;
+		check!(status.healthy);
+		check!(status.num_slots_behind.is_none());
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn rent_exemption_is_calculated_from_the_cached_rent() -> anyhow::Result<()> {
+		let rent = Rent {
+			lamports_per_byte_year: 3_480,
+			exemption_threshold: 2.0,
+			burn_percent: 50,
+		};
+		let rent_data = bincode::serialize(&rent)?;
+		let provider = MockRpcProvider::new().on(GetAccountInfoRequest::NAME, move |_| {
+			account_info(&base64_account(&sysvar::ID, &rent_data))
+		});
+		let rpc = provider.to_rpc_client();
+
+		check!(
+			rpc.minimum_balance_for_rent_exemption_cached(100).await? == rent.minimum_balance(100)
+		);
+		check!(rpc.minimum_balance_for_rent_exemption_cached(0).await? == rent.minimum_balance(0));
+		check!(
+			rpc.clone()
+				.minimum_balance_for_rent_exemption_cached(165)
+				.await? == rent.minimum_balance(165)
+		);
+		check!(provider.count(GetAccountInfoRequest::NAME) == 1);
+
+		rpc.invalidate_rent_cache();
+		rpc.minimum_balance_for_rent_exemption_cached(100).await?;
+		check!(provider.count(GetAccountInfoRequest::NAME) == 2);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn chain_tip_queries_use_the_commitment() -> anyhow::Result<()> {
+		let leader = Pubkey::new_unique();
+		let provider = MockRpcProvider::new()
+			.on(GetSlotRequest::NAME, |params| {
+				check!(params[0]["commitment"] == "finalized");
+				json!(300)
+			})
+			.on(GetBlockHeightRequest::NAME, |params| {
+				check!(params[0]["commitment"] == "processed");
+				json!(280)
+			})
+			.on(GetSlotLeadersRequest::NAME, move |params| {
+				check!(*params == json!([300, 2]));
+				json!([leader.to_string(), leader.to_string()])
+			});
+		let rpc = provider.to_rpc_client();
+		let slot = rpc
+			.get_slot_with_commitment(CommitmentConfig::finalized())
+			.await?;
+
+		check!(slot == 300);
+		check!(
+			rpc.get_block_height_with_commitment(CommitmentConfig::processed())
+				.await? == 280
+		);
+		check!(rpc.get_slot_leaders_with_config(slot, 2).await? == [leader, leader]);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn block_time_is_none_when_unavailable() -> anyhow::Result<()> {
+		let provider = MockRpcProvider::new().on(GetBlockTimeRequest::NAME, |params| {
+			if params[0] == 1 {
+				json!(1_574_721_591)
+			} else {
+				Value::Null
+			}
+		});
+		let rpc = provider.to_rpc_client();
+
+		check!(rpc.get_block_time(1).await? == Some(1_574_721_591));
+		check!(rpc.get_block_time(2).await?.is_none());
+
+		let provider = MockRpcProvider::new().on_error(
+			GetBlockTimeRequest::NAME,
+			json!({ "code": -32009, "message": "Slot 3 was skipped" }),
+		);
+		check!(provider.to_rpc_client().get_block_time(3).await?.is_none());
+
+		Ok(())
+	}
 }
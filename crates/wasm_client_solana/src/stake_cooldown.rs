@@ -0,0 +1,120 @@
+//! Estimate when deactivated stake will be fully inactive.
+//!
+//! Once stake is deactivated it cools down over several epochs. Each epoch the
+//! cluster can only deactivate a fraction of its effective stake, which is set
+//! by the warmup cooldown rate. That fraction is shared between all the stake
+//! accounts which are deactivating at the same time, in proportion to their
+//! stake. A single large deactivation can therefore slow down every other
+//! deactivation.
+//!
+//! The estimates assume the cluster stake stays the same as in the latest
+//! stake history entry, apart from the stake which cools down. New
+//! deactivations or activations on the cluster will make the real cooldown
+//! take longer or shorter than the estimate.
+
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde::Serialize;
+use solana_clock::Epoch;
+use solana_clock::Slot;
+use solana_stake_interface::stake_history::StakeHistoryEntry;
+
+/// The upper bound on the number of epochs simulated by
+/// [`estimate_cooldown_epochs`]. Stake which takes longer than this is
+/// reported as taking this many epochs.
+pub const MAX_COOLDOWN_EPOCHS: u64 = 1_000;
+
+/// The estimated end of the cooldown of a stake account.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StakeCooldownEstimate {
+	/// The epoch the stake was deactivated.
+	pub deactivation_epoch: Epoch,
+	/// The stake, in lamports, which is still effective and cooling down.
+	pub deactivating_stake: u64,
+	/// The first epoch at which the stake is estimated to be fully inactive.
+	pub inactive_epoch: Epoch,
+	/// The first slot of the `inactive_epoch`.
+	pub inactive_slot: Slot,
+	/// The approximate wall clock time until the `inactive_slot`, assuming
+	/// the target slot duration.
+	pub time_remaining: Duration,
+}
+
+/// Estimate the number of epoch boundaries which must pass before the
+/// `deactivating_stake` of a single account is fully inactive.
+///
+/// The `cluster` entry is the latest stake history entry and the
+/// `warmup_cooldown_rate` is the fraction of effective cluster stake which can
+/// cool down each epoch. This mirrors the calculation made by the stake
+/// program at every epoch boundary.
+pub fn estimate_cooldown_epochs(
+	deactivating_stake: u64,
+	cluster: &StakeHistoryEntry,
+	warmup_cooldown_rate: f64,
+) -> u64 {
+	let mut remaining = deactivating_stake;
+	let mut cluster_effective = cluster.effective.max(remaining);
+	let mut cluster_deactivating = cluster.deactivating.max(remaining);
+	let mut epochs = 0;
+
+	while remaining > 0 && epochs < MAX_COOLDOWN_EPOCHS {
+		let weight = remaining as f64 / cluster_deactivating as f64;
+		let cluster_cooldown = cluster_effective as f64 * warmup_cooldown_rate;
+		// the stake program always deactivates at least one lamport per epoch
+		let cooldown = ((weight * cluster_cooldown) as u64).max(1);
+
+		remaining = remaining.saturating_sub(cooldown);
+		cluster_effective = cluster_effective
+			.saturating_sub(cluster_cooldown as u64)
+			.max(remaining);
+		cluster_deactivating = cluster_deactivating
+			.saturating_sub(cluster_cooldown as u64)
+			.max(remaining);
+		epochs += 1;
+	}
+
+	epochs
+}
+
+#[cfg(test)]
+mod tests {
+	use assert2::check;
+	use solana_stake_interface::state::NEW_WARMUP_COOLDOWN_RATE;
+
+	use super::*;
+
+	#[test]
+	fn small_deactivation_finishes_in_one_epoch() {
+		let cluster = StakeHistoryEntry {
+			effective: 1_000_000,
+			activating: 0,
+			deactivating: 1_000,
+		};
+
+		check!(estimate_cooldown_epochs(1_000, &cluster, NEW_WARMUP_COOLDOWN_RATE) == 1);
+	}
+
+	#[test]
+	fn large_deactivation_is_rate_limited() {
+		let cluster = StakeHistoryEntry {
+			effective: 1_000_000,
+			activating: 0,
+			deactivating: 500_000,
+		};
+
+		// 9% of the effective stake cools down each epoch and this account
+		// holds half of the deactivating stake
+		let epochs = estimate_cooldown_epochs(250_000, &cluster, NEW_WARMUP_COOLDOWN_RATE);
+
+		check!(epochs > 1);
+		check!(epochs < MAX_COOLDOWN_EPOCHS);
+		check!(estimate_cooldown_epochs(250_000, &cluster, 0.25) < epochs);
+	}
+
+	#[test]
+	fn nothing_to_cool_down() {
+		check!(estimate_cooldown_epochs(0, &StakeHistoryEntry::default(), 0.09) == 0);
+	}
+}
@@ -0,0 +1,717 @@
+//! Stream combinators which are useful for websocket subscriptions.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Duration;
+
+use futures::Stream;
+use futures_timer::Delay;
+use pin_project::pin_project;
+use solana_clock::Slot;
+use typed_builder::TypedBuilder;
+
+use crate::GetAccountInfoResponse;
+use crate::SubscriptionResponse;
+use crate::rpc_response::BlockNotificationResponse;
+use crate::rpc_response::LogsNotificationResponse;
+use crate::rpc_response::ProgramNotificationResponse;
+use crate::rpc_response::RootNotification;
+use crate::rpc_response::SlotInfo;
+
+/// Extra combinators for streams, such as a [`crate::Subscription`].
+pub trait SolanaStreamExt: Stream + Sized {
+	/// Yield at most one item per `interval`.
+	///
+	/// The first item is yielded immediately. Any items received during the
+	/// following `interval` are coalesced and only the latest one is yielded
+	/// once the `interval` elapses. This is useful for high frequency
+	/// subscriptions which are used to update a UI.
+	fn throttle(self, interval: Duration) -> Throttle<Self> {
+		Throttle::new(self, interval)
+	}
+
+	/// Drop any item with a context slot lower than one which has already
+	/// been yielded.
+	///
+	/// This is useful when an account snapshot fetched over http is merged
+	/// with live notifications, for example after a reconnect. The snapshot
+	/// may be older than a notification which has already been received and
+	/// should not overwrite the newer state.
+	fn slot_ordered(self, ordering: SlotOrdering) -> SlotOrdered<Self>
+	where
+		Self::Item: ContextSlot,
+	{
+		SlotOrdered::new(self, ordering)
+	}
+
+	/// Replace the stream with a new one from `reconnect` whenever no item is
+	/// received within the [`WatchdogConfig::timeout`].
+	///
+	/// A websocket which silently stops delivering messages never ends the
+	/// stream, so prolonged silence on a subscription with regular updates is
+	/// treated as a dead connection. When `reconnect` fails it is retried
+	/// after another timeout. A `watchdog` of `None` leaves the stream
+	/// untouched.
+	fn watchdog<F, Fut, E>(
+		self,
+		watchdog: Option<WatchdogConfig>,
+		reconnect: F,
+	) -> Watchdog<Self, F, Fut>
+	where
+		F: FnMut() -> Fut,
+		Fut: Future<Output = Result<Self, E>>,
+		E: std::fmt::Display,
+	{
+		Watchdog::new(self, watchdog, reconnect)
+	}
+
+	/// Replace the stream with a new one from `reconnect` when it ends, such
+	/// as when the websocket connection is closed.
+	///
+	/// Items are yielded as [`SubscriptionEvent::Notification`] and each
+	/// successful reconnection yields a [`SubscriptionEvent::Reconnected`],
+	/// since items may have been missed in between. Reconnections are
+	/// attempted with an exponential backoff and the stream ends after
+	/// [`ReconnectConfig::max_retries`] consecutive failures.
+	fn reconnecting<F, Fut, E>(
+		self,
+		config: ReconnectConfig,
+		reconnect: F,
+	) -> Reconnecting<Self, F, Fut>
+	where
+		F: FnMut() -> Fut,
+		Fut: Future<Output = Result<Self, E>>,
+		E: std::fmt::Display,
+	{
+		Reconnecting::new(self, config, reconnect)
+	}
+}
+
+impl<S: Stream> SolanaStreamExt for S {}
+
+/// Items which were produced at a known slot.
+pub trait ContextSlot {
+	/// The slot at which the item was produced.
+	fn context_slot(&self) -> Slot;
+}
+
+macro_rules! impl_context_slot {
+	($($ident:ident),*) => {
+		$(
+			impl ContextSlot for $ident {
+				fn context_slot(&self) -> Slot {
+					self.context.slot
+				}
+			}
+		)*
+	};
+}
+
+impl_context_slot!(
+	GetAccountInfoResponse,
+	LogsNotificationResponse,
+	ProgramNotificationResponse,
+	BlockNotificationResponse
+);
+
+impl ContextSlot for SlotInfo {
+	fn context_slot(&self) -> Slot {
+		self.slot
+	}
+}
+
+impl ContextSlot for RootNotification {
+	fn context_slot(&self) -> Slot {
+		self.0
+	}
+}
+
+impl<T: ContextSlot> ContextSlot for SubscriptionResponse<T> {
+	fn context_slot(&self) -> Slot {
+		self.params.result.context_slot()
+	}
+}
+
+impl<K, T: ContextSlot> ContextSlot for (K, T) {
+	fn context_slot(&self) -> Slot {
+		self.1.context_slot()
+	}
+}
+
+/// How [`SolanaStreamExt::slot_ordered`] treats items which share a slot.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SlotOrdering {
+	/// Items with the same slot as the latest yielded item are kept.
+	#[default]
+	Monotonic,
+	/// Only items with a slot greater than the latest yielded item are kept.
+	Strict,
+}
+
+/// The stream returned by [`SolanaStreamExt::slot_ordered`].
+#[pin_project]
+#[must_use = "streams do nothing unless polled"]
+pub struct SlotOrdered<S> {
+	#[pin]
+	stream: S,
+	ordering: SlotOrdering,
+	latest: Option<Slot>,
+}
+
+impl<S> SlotOrdered<S> {
+	pub fn new(stream: S, ordering: SlotOrdering) -> Self {
+		Self {
+			stream,
+			ordering,
+			latest: None,
+		}
+	}
+
+	/// The slot of the latest item which was yielded.
+	pub fn latest_slot(&self) -> Option<Slot> {
+		self.latest
+	}
+}
+
+impl<S: Stream> Stream for SlotOrdered<S>
+where
+	S::Item: ContextSlot,
+{
+	type Item = S::Item;
+
+	fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		let mut this = self.project();
+
+		loop {
+			let Some(item) = std::task::ready!(this.stream.as_mut().poll_next(cx)) else {
+				return Poll::Ready(None);
+			};
+
+			let slot = item.context_slot();
+			let stale = match (*this.latest, *this.ordering) {
+				(None, _) => false,
+				(Some(latest), SlotOrdering::Monotonic) => slot < latest,
+				(Some(latest), SlotOrdering::Strict) => slot <= latest,
+			};
+
+			if !stale {
+				*this.latest = Some(slot);
+				return Poll::Ready(Some(item));
+			}
+		}
+	}
+}
+
+/// The default number of missed intervals before a watchdog reconnects.
+pub const DEFAULT_WATCHDOG_MULTIPLE: u32 = 10;
+
+/// How long a subscription may stay silent before it is considered dead. See
+/// [`SolanaStreamExt::watchdog`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, TypedBuilder)]
+pub struct WatchdogConfig {
+	/// The expected time between notifications.
+	pub expected_interval: Duration,
+	/// The number of `expected_interval`s without a notification which are
+	/// tolerated. Defaults to [`DEFAULT_WATCHDOG_MULTIPLE`].
+	#[builder(default = DEFAULT_WATCHDOG_MULTIPLE)]
+	pub multiple: u32,
+}
+
+impl WatchdogConfig {
+	pub fn new(expected_interval: Duration) -> Self {
+		Self::builder().expected_interval(expected_interval).build()
+	}
+
+	/// The silence after which the subscription is reconnected.
+	pub fn timeout(&self) -> Duration {
+		self.expected_interval.saturating_mul(self.multiple.max(1))
+	}
+}
+
+/// The state shared by [`Watchdog`] and [`Reconnecting`], which replaces the
+/// current stream with a new one from the `reconnect` closure.
+struct ReconnectState<S, F, Fut> {
+	stream: Option<Pin<Box<S>>>,
+	reconnect: F,
+	pending: Option<Pin<Box<Fut>>>,
+	reconnects: usize,
+}
+
+impl<S, F, Fut> ReconnectState<S, F, Fut> {
+	fn new(stream: S, reconnect: F) -> Self {
+		Self {
+			stream: Some(Box::pin(stream)),
+			reconnect,
+			pending: None,
+			reconnects: 0,
+		}
+	}
+
+	/// Drop the current stream and start creating its replacement.
+	fn start(&mut self)
+	where
+		F: FnMut() -> Fut,
+	{
+		self.stream = None;
+		self.pending = Some(Box::pin((self.reconnect)()));
+	}
+
+	/// Poll the replacement which is being created, which is `None` when there
+	/// is no pending reconnection. The stream is replaced once it's created.
+	fn poll_pending<E>(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<(), E>>>
+	where
+		Fut: Future<Output = Result<S, E>>,
+	{
+		let Some(pending) = self.pending.as_mut() else {
+			return Poll::Ready(None);
+		};
+		let result = std::task::ready!(pending.as_mut().poll(cx));
+		self.pending = None;
+
+		Poll::Ready(Some(result.map(|stream| {
+			self.stream = Some(Box::pin(stream));
+			self.reconnects += 1;
+		})))
+	}
+
+	/// Poll the current stream, which is pending while it's being replaced.
+	fn poll_stream(&mut self, cx: &mut Context<'_>) -> Poll<Option<S::Item>>
+	where
+		S: Stream,
+	{
+		match self.stream.as_mut() {
+			Some(stream) => stream.as_mut().poll_next(cx),
+			None => Poll::Pending,
+		}
+	}
+}
+
+/// The stream returned by [`SolanaStreamExt::watchdog`].
+#[pin_project]
+#[must_use = "streams do nothing unless polled"]
+pub struct Watchdog<S, F, Fut> {
+	inner: ReconnectState<S, F, Fut>,
+	timeout: Option<Duration>,
+	delay: Option<Delay>,
+}
+
+impl<S, F, Fut> Watchdog<S, F, Fut> {
+	pub fn new(stream: S, watchdog: Option<WatchdogConfig>, reconnect: F) -> Self {
+		let timeout = watchdog.map(|watchdog| watchdog.timeout());
+
+		Self {
+			inner: ReconnectState::new(stream, reconnect),
+			timeout,
+			delay: timeout.map(Delay::new),
+		}
+	}
+
+	/// The number of times the stream was successfully replaced.
+	pub fn reconnects(&self) -> usize {
+		self.inner.reconnects
+	}
+}
+
+impl<S, F, Fut, E> Stream for Watchdog<S, F, Fut>
+where
+	S: Stream,
+	F: FnMut() -> Fut,
+	Fut: Future<Output = Result<S, E>>,
+	E: std::fmt::Display,
+{
+	type Item = S::Item;
+
+	fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		let this = self.project();
+
+		loop {
+			match this.inner.poll_pending(cx) {
+				Poll::Ready(Some(Ok(()))) => reset_delay(this.delay, *this.timeout),
+				Poll::Ready(Some(Err(error))) => {
+					// try again once the next timeout elapses
+					log::warn!("the watchdog failed to reconnect: {error}");
+					reset_delay(this.delay, *this.timeout);
+				}
+				Poll::Ready(None) | Poll::Pending => {}
+			}
+
+			match this.inner.poll_stream(cx) {
+				Poll::Ready(Some(item)) => {
+					reset_delay(this.delay, *this.timeout);
+					return Poll::Ready(Some(item));
+				}
+				Poll::Ready(None) => return Poll::Ready(None),
+				Poll::Pending => {}
+			}
+
+			let Some(delay) = this.delay.as_mut() else {
+				return Poll::Pending;
+			};
+
+			if Pin::new(delay).poll(cx).is_pending() {
+				return Poll::Pending;
+			}
+
+			// the silence lasted too long, so replace the stream
+			this.inner.start();
+			reset_delay(this.delay, *this.timeout);
+		}
+	}
+}
+
+fn reset_delay(delay: &mut Option<Delay>, timeout: Option<Duration>) {
+	if let (Some(delay), Some(timeout)) = (delay.as_mut(), timeout) {
+		delay.reset(timeout);
+	}
+}
+
+/// The default delay before the first reconnection attempt.
+pub const DEFAULT_RECONNECT_INITIAL_BACKOFF: Duration = Duration::from_millis(250);
+/// The default longest delay between reconnection attempts.
+pub const DEFAULT_RECONNECT_MAX_BACKOFF: Duration = Duration::from_secs(30);
+/// The default number of consecutive failed reconnection attempts.
+pub const DEFAULT_RECONNECT_MAX_RETRIES: u32 = 10;
+
+/// How a stream which ended is reconnected. See
+/// [`SolanaStreamExt::reconnecting`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, TypedBuilder)]
+pub struct ReconnectConfig {
+	/// The delay before the first attempt, which doubles after each failed
+	/// attempt. Defaults to [`DEFAULT_RECONNECT_INITIAL_BACKOFF`].
+	#[builder(default = DEFAULT_RECONNECT_INITIAL_BACKOFF)]
+	pub initial_backoff: Duration,
+	/// The longest delay between attempts. Defaults to
+	/// [`DEFAULT_RECONNECT_MAX_BACKOFF`].
+	#[builder(default = DEFAULT_RECONNECT_MAX_BACKOFF)]
+	pub max_backoff: Duration,
+	/// The number of consecutive failed attempts after which the stream ends.
+	/// Defaults to [`DEFAULT_RECONNECT_MAX_RETRIES`].
+	#[builder(default = DEFAULT_RECONNECT_MAX_RETRIES)]
+	pub max_retries: u32,
+}
+
+impl Default for ReconnectConfig {
+	fn default() -> Self {
+		Self::builder().build()
+	}
+}
+
+impl ReconnectConfig {
+	/// The delay before the attempt which follows `failures` failed attempts.
+	pub fn backoff(&self, failures: u32) -> Duration {
+		self.initial_backoff
+			.saturating_mul(2_u32.saturating_pow(failures))
+			.min(self.max_backoff)
+	}
+}
+
+/// An item of the stream returned by [`SolanaStreamExt::reconnecting`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubscriptionEvent<T> {
+	/// An item of the current stream.
+	Notification(T),
+	/// The stream ended and was replaced. Items may have been missed.
+	Reconnected,
+}
+
+impl<T> SubscriptionEvent<T> {
+	/// The notification, or `None` for a reconnection.
+	pub fn notification(self) -> Option<T> {
+		match self {
+			Self::Notification(notification) => Some(notification),
+			Self::Reconnected => None,
+		}
+	}
+}
+
+/// The stream returned by [`SolanaStreamExt::reconnecting`].
+#[pin_project]
+#[must_use = "streams do nothing unless polled"]
+pub struct Reconnecting<S, F, Fut> {
+	inner: ReconnectState<S, F, Fut>,
+	delay: Option<Delay>,
+	config: ReconnectConfig,
+	failures: u32,
+}
+
+impl<S, F, Fut> Reconnecting<S, F, Fut> {
+	pub fn new(stream: S, config: ReconnectConfig, reconnect: F) -> Self {
+		Self {
+			inner: ReconnectState::new(stream, reconnect),
+			delay: None,
+			config,
+			failures: 0,
+		}
+	}
+
+	/// The number of times the stream was successfully replaced.
+	pub fn reconnects(&self) -> usize {
+		self.inner.reconnects
+	}
+}
+
+impl<S, F, Fut, E> Stream for Reconnecting<S, F, Fut>
+where
+	S: Stream,
+	F: FnMut() -> Fut,
+	Fut: Future<Output = Result<S, E>>,
+	E: std::fmt::Display,
+{
+	type Item = SubscriptionEvent<S::Item>;
+
+	fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		let this = self.project();
+
+		loop {
+			match this.inner.poll_pending(cx) {
+				Poll::Ready(Some(Ok(()))) => {
+					*this.failures = 0;
+					return Poll::Ready(Some(SubscriptionEvent::Reconnected));
+				}
+				Poll::Ready(Some(Err(error))) => {
+					log::warn!("failed to reconnect the stream: {error}");
+					*this.failures += 1;
+
+					if *this.failures >= this.config.max_retries {
+						return Poll::Ready(None);
+					}
+
+					*this.delay = Some(Delay::new(this.config.backoff(*this.failures)));
+				}
+				Poll::Pending => return Poll::Pending,
+				Poll::Ready(None) => {}
+			}
+
+			if let Some(delay) = this.delay.as_mut() {
+				if Pin::new(delay).poll(cx).is_pending() {
+					return Poll::Pending;
+				}
+
+				*this.delay = None;
+				this.inner.start();
+				continue;
+			}
+
+			// the stream ended and won't be replaced
+			if this.inner.stream.is_none() {
+				return Poll::Ready(None);
+			}
+
+			match this.inner.poll_stream(cx) {
+				Poll::Ready(Some(item)) => {
+					return Poll::Ready(Some(SubscriptionEvent::Notification(item)));
+				}
+				Poll::Ready(None) => {
+					this.inner.stream = None;
+
+					if this.config.max_retries == 0 {
+						return Poll::Ready(None);
+					}
+
+					*this.delay = Some(Delay::new(this.config.backoff(0)));
+				}
+				Poll::Pending => return Poll::Pending,
+			}
+		}
+	}
+}
+
+/// The stream returned by [`SolanaStreamExt::throttle`].
+#[pin_project]
+#[must_use = "streams do nothing unless polled"]
+pub struct Throttle<S: Stream> {
+	#[pin]
+	stream: S,
+	interval: Duration,
+	delay: Option<Delay>,
+	latest: Option<S::Item>,
+	done: bool,
+}
+
+impl<S: Stream> Throttle<S> {
+	pub fn new(stream: S, interval: Duration) -> Self {
+		Self {
+			stream,
+			interval,
+			delay: None,
+			latest: None,
+			done: false,
+		}
+	}
+}
+
+impl<S: Stream> Stream for Throttle<S> {
+	type Item = S::Item;
+
+	fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		let mut this = self.project();
+
+		loop {
+			if let Some(delay) = this.delay.as_mut() {
+				if Pin::new(delay).poll(cx).is_ready() {
+					*this.delay = None;
+
+					if let Some(item) = this.latest.take() {
+						*this.delay = Some(Delay::new(*this.interval));
+						return Poll::Ready(Some(item));
+					}
+				}
+			}
+
+			if *this.done {
+				// flush the latest item without waiting once the stream has ended
+				return Poll::Ready(this.latest.take());
+			}
+
+			match this.stream.as_mut().poll_next(cx) {
+				Poll::Ready(Some(item)) => {
+					if this.delay.is_none() {
+						*this.delay = Some(Delay::new(*this.interval));
+						return Poll::Ready(Some(item));
+					}
+
+					*this.latest = Some(item);
+				}
+				Poll::Ready(None) => *this.done = true,
+				Poll::Pending => return Poll::Pending,
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use assert2::check;
+	use futures::StreamExt;
+	use futures::channel::mpsc;
+	use futures::future;
+	use futures::stream;
+
+	use super::*;
+	use crate::Context as RpcContext;
+
+	fn account_notification(slot: Slot) -> GetAccountInfoResponse {
+		GetAccountInfoResponse {
+			context: RpcContext { slot },
+			value: None,
+		}
+	}
+
+	#[tokio::test]
+	async fn throttle_coalesces_to_latest() {
+		let interval = Duration::from_millis(50);
+		let (sender, receiver) = mpsc::unbounded();
+		let mut stream = receiver.throttle(interval);
+
+		for value in 1..=3 {
+			sender.unbounded_send(value).unwrap();
+		}
+
+		check!(stream.next().await == Some(1));
+		check!(stream.next().await == Some(3));
+
+		Delay::new(interval * 2).await;
+		sender.unbounded_send(4).unwrap();
+		sender.unbounded_send(5).unwrap();
+		drop(sender);
+
+		check!(stream.collect::<Vec<_>>().await == vec![4, 5]);
+	}
+
+	#[tokio::test]
+	async fn slot_ordered_drops_stale_snapshot() {
+		let before = stream::iter([10, 12]).map(account_notification);
+		// the snapshot fetched after a reconnect is older than the live updates
+		let snapshot = stream::iter([11]).map(account_notification);
+		let after = stream::iter([12, 15]).map(account_notification);
+		let merged = before.chain(snapshot).chain(after);
+
+		let slots = merged
+			.slot_ordered(SlotOrdering::default())
+			.map(|notification| notification.context_slot())
+			.collect::<Vec<_>>()
+			.await;
+
+		check!(slots == vec![10, 12, 12, 15]);
+	}
+
+	#[tokio::test]
+	async fn slot_ordered_strict_drops_equal_slots() {
+		let slots = stream::iter([3, 3, 2, 4, 4, 5])
+			.map(account_notification)
+			.slot_ordered(SlotOrdering::Strict)
+			.map(|notification| notification.context_slot())
+			.collect::<Vec<_>>()
+			.await;
+
+		check!(slots == vec![3, 4, 5]);
+	}
+
+	#[tokio::test]
+	async fn watchdog_reconnects_after_silence() {
+		let watchdog = WatchdogConfig::builder()
+			.expected_interval(Duration::from_millis(10))
+			.multiple(2)
+			.build();
+		// the original connection stays open but never delivers anything
+		let (silent_sender, silent) = mpsc::unbounded::<u64>();
+		let (live_sender, live) = mpsc::unbounded();
+		live_sender.unbounded_send(1).unwrap();
+		live_sender.unbounded_send(2).unwrap();
+		drop(live_sender);
+
+		// the first reconnect fails and is retried after another timeout
+		let mut attempts = vec![Ok(live), Err("connection refused")];
+		let stream = silent.watchdog(Some(watchdog), move || {
+			future::ready(attempts.pop().unwrap_or(Err("no more connections")))
+		});
+		let mut stream = std::pin::pin!(stream);
+
+		check!(stream.next().await == Some(1));
+		check!(stream.next().await == Some(2));
+		check!(stream.next().await == None);
+		check!(stream.reconnects() == 1);
+		drop(silent_sender);
+	}
+
+	#[tokio::test]
+	async fn reconnecting_resubscribes_when_the_stream_ends() {
+		let config = ReconnectConfig::builder()
+			.initial_backoff(Duration::from_millis(5))
+			.max_backoff(Duration::from_millis(10))
+			.max_retries(2)
+			.build();
+		// the first connection closes after two items, the reconnection
+		// succeeds on the second attempt and is closed again after one item
+		let mut attempts = vec![
+			Err("connection refused"),
+			Err("connection refused"),
+			Ok(stream::iter(vec![3])),
+			Err("connection refused"),
+		];
+		let stream = stream::iter(vec![1, 2]).reconnecting(config, move || {
+			future::ready(attempts.pop().unwrap_or(Err("no more connections")))
+		});
+		let mut stream = std::pin::pin!(stream);
+		let mut events = vec![];
+
+		while let Some(event) = stream.next().await {
+			events.push(event);
+		}
+
+		check!(
+			events
+				== vec![
+					SubscriptionEvent::Notification(1),
+					SubscriptionEvent::Notification(2),
+					SubscriptionEvent::Reconnected,
+					SubscriptionEvent::Notification(3),
+				]
+		);
+		check!(stream.reconnects() == 1);
+		check!(config.backoff(0) == Duration::from_millis(5));
+		check!(config.backoff(4) == Duration::from_millis(10));
+	}
+}
@@ -1,5 +1,10 @@
 use std::future::Future;
 
+use solana_commitment_config::CommitmentConfig;
+use solana_commitment_config::CommitmentLevel;
+use solana_commitment_config::ParseCommitmentLevelError;
+use solana_pubkey::Pubkey;
+
 pub fn spawn_local<F>(fut: F)
 where
 	F: Future<Output = ()> + 'static,
@@ -42,3 +47,89 @@ pub(crate) fn get_ws_url(url: impl Into<String>) -> String {
 
 	url
 }
+
+/// Check whether the `pubkey` is a point on the ed25519 curve.
+///
+/// Wallet addresses are always on the curve, while program derived addresses
+/// are always off the curve, so this can be used to check whether an address
+/// could be a valid PDA.
+pub fn is_on_curve(pubkey: &Pubkey) -> bool {
+	pubkey.is_on_curve()
+}
+
+/// Parse a commitment level, accepting the deprecated aliases which are still
+/// used by older tooling.
+///
+/// | alias          | level       |
+/// | -------------- | ----------- |
+/// | `recent`       | `processed` |
+/// | `single`       | `confirmed` |
+/// | `singleGossip` | `confirmed` |
+/// | `root`         | `finalized` |
+/// | `max`          | `finalized` |
+///
+/// A deprecation warning is logged whenever an alias is used.
+pub fn parse_commitment_level(value: &str) -> Result<CommitmentLevel, ParseCommitmentLevelError> {
+	let level = match value {
+		"recent" => CommitmentLevel::Processed,
+		"single" | "singleGossip" => CommitmentLevel::Confirmed,
+		"root" | "max" => CommitmentLevel::Finalized,
+		_ => return value.parse(),
+	};
+
+	log::warn!("the commitment `{value}` is deprecated, use `{level}` instead");
+
+	Ok(level)
+}
+
+/// Parse a [`CommitmentConfig`] with [`parse_commitment_level`].
+pub fn parse_commitment_config(value: &str) -> Result<CommitmentConfig, ParseCommitmentLevelError> {
+	parse_commitment_level(value).map(|commitment| CommitmentConfig { commitment })
+}
+
+#[cfg(test)]
+mod tests {
+	use assert2::check;
+	use solana_keypair::Keypair;
+	use solana_signer::Signer;
+
+	use super::*;
+
+	#[test]
+	fn wallet_is_on_curve() {
+		check!(is_on_curve(&Keypair::new().pubkey()));
+	}
+
+	#[test]
+	fn pda_is_off_curve() {
+		let (pda, _) =
+			Pubkey::find_program_address(&[b"seed"], &solana_sdk_ids::system_program::ID);
+		check!(!is_on_curve(&pda));
+	}
+
+	#[test]
+	fn legacy_commitment_aliases() {
+		let cases = [
+			("recent", CommitmentLevel::Processed),
+			("single", CommitmentLevel::Confirmed),
+			("singleGossip", CommitmentLevel::Confirmed),
+			("root", CommitmentLevel::Finalized),
+			("max", CommitmentLevel::Finalized),
+			("processed", CommitmentLevel::Processed),
+			("confirmed", CommitmentLevel::Confirmed),
+			("finalized", CommitmentLevel::Finalized),
+		];
+
+		for (alias, level) in cases {
+			check!(
+				parse_commitment_level(alias).ok() == Some(level),
+				"alias: {alias}"
+			);
+			check!(
+				parse_commitment_config(alias).ok() == Some(CommitmentConfig { commitment: level })
+			);
+		}
+
+		check!(parse_commitment_level("unknown").is_err());
+	}
+}
@@ -0,0 +1,360 @@
+#![cfg(feature = "js")]
+
+use std::sync::Arc;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use anyhow::Result;
+use assert2::check;
+use async_trait::async_trait;
+use js_sys::Array;
+use js_sys::BigInt;
+use js_sys::Reflect;
+use solana_commitment_config::CommitmentConfig;
+use solana_keypair::Keypair;
+use solana_native_token::sol_str_to_lamports;
+use solana_program_pack::Pack;
+use solana_pubkey::Pubkey;
+use solana_signer::Signer;
+use spl_token_2022_interface::state::Account as TokenAccount;
+use spl_token_2022_interface::state::AccountState;
+use spl_token_2022_interface::state::Mint;
+use wasm_bindgen_test::*;
+use wasm_client_solana::ClientResult;
+use wasm_client_solana::HttpProvider;
+use wasm_client_solana::LOCALNET;
+use wasm_client_solana::RpcProvider;
+use wasm_client_solana::SolanaRpcClient;
+use wasm_client_solana::account_display::DisplayRow;
+use wasm_client_solana::js::ToJsValue;
+use wasm_client_solana::js::create_siws_message;
+use wasm_client_solana::js::format_parsed_account;
+use wasm_client_solana::js::from_js_value;
+use wasm_client_solana::js::is_on_curve;
+use wasm_client_solana::js::parse_account_data;
+use wasm_client_solana::js::to_js_value;
+use wasm_client_solana::js::verify_siws_message;
+use wasm_client_solana::siws::SiwsMessage;
+use wasm_client_solana::solana_account_decoder::UiAccount;
+use wasm_client_solana::solana_account_decoder::UiAccountData;
+use wasm_client_solana::solana_account_decoder::UiAccountEncoding;
+use wasm_client_solana::solana_account_decoder_client_types::ParsedAccount;
+use wasm_client_solana::solana_account_decoder_client_types::token::UiTokenAmount;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+pub fn large_lamports_round_trip() -> Result<()> {
+	// `2^53 + 1` is the first integer which can't be represented by a js number.
+	let lamports = 9_007_199_254_740_993;
+	let account = UiAccount::builder()
+		.lamports(lamports)
+		.data(UiAccountData::Binary(
+			String::new(),
+			UiAccountEncoding::Base64,
+		))
+		.owner(Pubkey::new_unique())
+		.rent_epoch(u64::MAX)
+		.space(0u64)
+		.build();
+
+	let value = to_js_value(&account).unwrap();
+	let js_lamports = Reflect::get(&value, &"lamports".into()).unwrap();
+
+	check!(js_lamports.is_bigint());
+	check!(String::from(BigInt::from(js_lamports).to_string(10).unwrap()) == lamports.to_string());
+
+	let round_trip: UiAccount = from_js_value(value).unwrap();
+	check!(round_trip == account);
+
+	Ok(())
+}
+
+#[wasm_bindgen_test]
+pub fn is_on_curve_for_wallets_and_pdas() {
+	let (pda, _) = Pubkey::find_program_address(&[b"seed"], &Pubkey::new_unique());
+
+	check!(is_on_curve(&Keypair::new().pubkey().to_string()).unwrap());
+	check!(!is_on_curve(&pda.to_string()).unwrap());
+	check!(is_on_curve("invalid").is_err());
+}
+
+#[wasm_bindgen_test]
+pub fn siws_message_sign_and_verify() -> Result<()> {
+	let keypair = Keypair::new();
+	let message = SiwsMessage::builder()
+		.domain("example.com")
+		.address(keypair.pubkey())
+		.nonce("32891756")
+		.issued_at("2024-01-01T00:00:00.000Z")
+		.build();
+	let input = to_js_value(&message).unwrap();
+	let text = create_siws_message(input.clone()).unwrap();
+	let signature = keypair.sign_message(text.as_bytes());
+
+	check!(text == message.text()?);
+	check!(verify_siws_message(input.clone(), signature.as_ref()).unwrap());
+	check!(!verify_siws_message(input, &[0; 64]).unwrap());
+
+	Ok(())
+}
+
+#[wasm_bindgen_test]
+pub fn format_parsed_account_rows() {
+	let account = ParsedAccount {
+		program: "nonce".into(),
+		parsed: serde_json::json!({
+			"type": "initialized",
+			"info": {
+				"authority": "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM",
+				"blockhash": "4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi",
+				"feeCalculator": { "lamportsPerSignature": "5000" }
+			}
+		}),
+		space: 80,
+	};
+	let rows = format_parsed_account(to_js_value(&account).unwrap()).unwrap();
+	let rows: Vec<DisplayRow> = from_js_value(rows).unwrap();
+
+	check!(
+		rows == vec![
+			DisplayRow::new("Type", "Nonce Account"),
+			DisplayRow::new("Authority", "9WzD…AWWM"),
+			DisplayRow::new("Blockhash", "4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi"),
+			DisplayRow::new("Fee Per Signature", "0.000005 SOL"),
+		]
+	);
+}
+
+#[wasm_bindgen_test]
+pub fn parse_token_account_data() {
+	let address = Pubkey::new_unique();
+	let mint = Pubkey::new_unique();
+	let owner = Pubkey::new_unique();
+	let mut mint_data = vec![0; Mint::LEN];
+	Mint {
+		decimals: 2,
+		is_initialized: true,
+		..Mint::default()
+	}
+	.pack_into_slice(&mut mint_data);
+	let mut data = vec![0; TokenAccount::LEN];
+	TokenAccount {
+		mint,
+		owner,
+		amount: 12_345,
+		state: AccountState::Initialized,
+		..TokenAccount::default()
+	}
+	.pack_into_slice(&mut data);
+	let token_program = spl_token_2022_interface::id().to_string();
+
+	let parsed = parse_account_data(
+		&address.to_string(),
+		&token_program,
+		&data,
+		Some(mint_data.clone().into()),
+		None,
+	)
+	.unwrap();
+	let parsed: ParsedAccount = from_js_value(parsed).unwrap();
+
+	check!(parsed.program == "spl-token-2022");
+	check!(parsed.parsed["info"]["owner"] == owner.to_string());
+	check!(parsed.parsed["info"]["tokenAmount"]["uiAmountString"] == "123.45");
+
+	// token accounts can't be parsed without the mint decimals
+	check!(parse_account_data(&address.to_string(), &token_program, &data, None, None).is_err());
+	check!(
+		parse_account_data(
+			&address.to_string(),
+			&token_program,
+			&data,
+			Some(vec![1; 3].into()),
+			None
+		)
+		.is_err()
+	);
+	check!(parse_account_data("invalid", &token_program, &data, None, None).is_err());
+	check!(parse_account_data(&address.to_string(), "invalid", &data, None, None).is_err());
+}
+
+fn get(value: &wasm_bindgen::JsValue, path: &[&str]) -> wasm_bindgen::JsValue {
+	path.iter().fold(value.clone(), |value, key| {
+		Reflect::get(&value, &(*key).into()).unwrap()
+	})
+}
+
+#[wasm_bindgen_test]
+pub fn ui_account_to_js_matches_the_rpc_json() {
+	let binary = UiAccount::builder()
+		.lamports(1_000)
+		.data(UiAccountData::Binary(
+			"AQID".into(),
+			UiAccountEncoding::Base64,
+		))
+		.owner(Pubkey::new_unique())
+		.rent_epoch(u64::MAX)
+		.space(3u64)
+		.build();
+	let value = binary.to_js().unwrap();
+	let data = Array::from(&get(&value, &["data"]));
+
+	check!(Array::is_array(&data));
+	check!(data.get(0).as_string().as_deref() == Some("AQID"));
+	check!(data.get(1).as_string().as_deref() == Some("base64"));
+	check!(get(&value, &["rentEpoch"]).is_bigint());
+
+	let parsed = UiAccount::builder()
+		.lamports(1_000)
+		.data(UiAccountData::Json(ParsedAccount {
+			program: "spl-token".into(),
+			parsed: serde_json::json!({
+				"type": "mint",
+				"info": { "decimals": 6, "supply": "1000", "large": 9_007_199_254_740_993_u64 }
+			}),
+			space: 82,
+		}))
+		.owner(Pubkey::new_unique())
+		.rent_epoch(0)
+		.space(82u64)
+		.build();
+	let value = parsed.to_js().unwrap();
+
+	check!(get(&value, &["data", "program"]).as_string().as_deref() == Some("spl-token"));
+	check!(get(&value, &["data", "space"]).is_bigint());
+	check!(get(&value, &["data", "parsed", "info", "decimals"]).as_f64() == Some(6.0));
+	check!(
+		get(&value, &["data", "parsed", "info", "supply"])
+			.as_string()
+			.as_deref()
+			== Some("1000")
+	);
+	check!(get(&value, &["data", "parsed", "info", "large"]).is_bigint());
+
+	let round_trip: UiAccount = from_js_value(value).unwrap();
+	check!(round_trip == parsed);
+}
+
+fn bigint_string(value: &wasm_bindgen::JsValue) -> Option<String> {
+	value
+		.is_bigint()
+		.then(|| String::from(BigInt::from(value.clone()).to_string(10).unwrap()))
+}
+
+#[wasm_bindgen_test]
+pub fn large_integers_are_bigints_for_every_field() {
+	// the first integer which can't be represented by a js number
+	let large = 9_007_199_254_740_993_u64;
+	let account = UiAccount::builder()
+		.lamports(large)
+		.data(UiAccountData::Binary(
+			String::new(),
+			UiAccountEncoding::Base64,
+		))
+		.owner(Pubkey::new_unique())
+		.rent_epoch(large)
+		.space(large)
+		.build();
+
+	for value in [to_js_value(&account).unwrap(), account.to_js().unwrap()] {
+		for field in ["lamports", "rentEpoch", "space"] {
+			check!(bigint_string(&get(&value, &[field])) == Some(large.to_string()));
+		}
+
+		let round_trip: UiAccount = from_js_value(value).unwrap();
+		check!(round_trip == account);
+	}
+
+	// token amounts are strings so they are never rounded
+	let token_amount = UiTokenAmount {
+		ui_amount: None,
+		decimals: 0,
+		amount: u64::MAX.to_string(),
+		ui_amount_string: u64::MAX.to_string(),
+	};
+	let value = to_js_value(&token_amount).unwrap();
+
+	check!(get(&value, &["amount"]).as_string() == Some(u64::MAX.to_string()));
+
+	let round_trip: UiTokenAmount = from_js_value(value).unwrap();
+	check!(round_trip == token_amount);
+}
+
+/// Counts the `getSignatureStatuses` requests, which are only sent when the
+/// confirmation falls back to polling.
+struct StatusCountingProvider {
+	inner: HttpProvider,
+	ws_endpoint: String,
+	status_requests: AtomicUsize,
+}
+
+impl StatusCountingProvider {
+	fn new(ws_endpoint: &str) -> Arc<Self> {
+		Arc::new(Self {
+			inner: HttpProvider::new(LOCALNET),
+			ws_endpoint: ws_endpoint.into(),
+			status_requests: AtomicUsize::new(0),
+		})
+	}
+
+	fn status_requests(&self) -> usize {
+		self.status_requests.load(Ordering::SeqCst)
+	}
+}
+
+#[async_trait]
+impl RpcProvider for StatusCountingProvider {
+	async fn send(
+		&self,
+		method: &str,
+		request: serde_json::Value,
+	) -> ClientResult<serde_json::Value> {
+		if method == "getSignatureStatuses" {
+			self.status_requests.fetch_add(1, Ordering::SeqCst);
+		}
+
+		self.inner.send(method, request).await
+	}
+
+	/// The client connects its websocket to this url.
+	fn url(&self) -> String {
+		self.ws_endpoint.clone()
+	}
+}
+
+async fn confirm_airdrop(provider: Arc<StatusCountingProvider>) -> Result<bool> {
+	let rpc = SolanaRpcClient::new_with_provider(provider, CommitmentConfig::confirmed());
+	let signature = rpc
+		.request_airdrop(
+			&Keypair::new().pubkey(),
+			sol_str_to_lamports("1.0").unwrap(),
+		)
+		.await?;
+	let is_success = rpc
+		.confirm_transaction_with_commitment(&signature, CommitmentConfig::confirmed())
+		.await?;
+
+	Ok(is_success)
+}
+
+#[wasm_bindgen_test]
+pub async fn confirm_transaction_with_signature_notification() -> Result<()> {
+	let provider = StatusCountingProvider::new(LOCALNET);
+
+	check!(confirm_airdrop(provider.clone()).await?);
+	check!(provider.status_requests() == 0);
+
+	Ok(())
+}
+
+#[wasm_bindgen_test]
+pub async fn confirm_transaction_polls_when_the_subscription_fails() -> Result<()> {
+	// nothing listens on the port so the websocket fails to connect
+	let provider = StatusCountingProvider::new("ws://127.0.0.1:1");
+
+	check!(confirm_airdrop(provider.clone()).await?);
+	check!(provider.status_requests() > 0);
+
+	Ok(())
+}
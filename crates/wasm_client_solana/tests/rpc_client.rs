@@ -2,14 +2,22 @@
 
 use anyhow::Result;
 use assert2::check;
+use solana_instruction::Instruction;
 use solana_keypair::Keypair;
+use solana_loader_v3_interface::get_program_data_address;
+use solana_loader_v3_interface::instruction as loader_instruction;
+use solana_loader_v3_interface::state::UpgradeableLoaderState;
 use solana_native_token::sol_str_to_lamports;
+use solana_pubkey::Pubkey;
+use solana_pubkey::pubkey;
+use solana_sdk_ids::bpf_loader_upgradeable;
 use test_utils_keypairs::get_wallet_keypair;
 use wasm_bindgen_test::*;
 use wasm_client_solana::LOCALNET;
 use wasm_client_solana::SolanaRpcClient;
 use wasm_client_solana::prelude::*;
 use wasm_client_solana::rpc_config::LogsSubscribeRequest;
+use wasm_client_solana::rpc_config::RpcTransactionLogsConfig;
 use wasm_client_solana::rpc_config::RpcTransactionLogsFilter;
 
 wasm_bindgen_test_configure!(run_in_browser);
@@ -52,6 +60,79 @@ pub async fn log_subscription() -> Result<()> {
 	Ok(())
 }
 
+#[wasm_bindgen_test]
+pub async fn log_subscription_mentions() -> Result<()> {
+	let rpc = SolanaRpcClient::new(LOCALNET);
+	let addresses = [Keypair::new().pubkey(), Keypair::new().pubkey()];
+	let subscription = rpc
+		.logs_subscribe_mentions(&addresses, RpcTransactionLogsConfig::default())
+		.await?;
+
+	let unsubscriptions = subscription.get_unsubscriptions();
+	check!(unsubscriptions.len() == 2);
+
+	let lamports = sol_str_to_lamports("1.0").unwrap();
+	let signature = rpc.request_airdrop(&addresses[1], lamports).await?;
+	rpc.confirm_transaction(&signature).await?;
+
+	let mut stream = subscription.take(1);
+
+	while let Some((address, log_notification)) = stream.next().await {
+		console_log!("log: {log_notification:#?}");
+		check!(address == addresses[1]);
+		check!(log_notification.params.result.value.signature == signature);
+	}
+
+	for unsubscription in unsubscriptions {
+		unsubscription.run().await?;
+	}
+
+	Ok(())
+}
+
+#[wasm_bindgen_test]
+pub async fn slot_subscription() -> Result<()> {
+	let rpc = SolanaRpcClient::new(LOCALNET);
+	let subscription = rpc.slot_subscribe().await?;
+	let unsubscription = subscription.get_unsubscription();
+	let mut stream = subscription.take(2);
+	let mut previous = None;
+
+	while let Some(notification) = stream.next().await {
+		let slot_info = notification.params.result;
+		console_log!("slot: {slot_info:#?}");
+		check!(notification.method == "slotNotification");
+		check!(slot_info.root <= slot_info.slot);
+		check!(previous < Some(slot_info.slot));
+		previous = Some(slot_info.slot);
+	}
+
+	unsubscription.run().await?;
+
+	Ok(())
+}
+
+#[wasm_bindgen_test]
+pub async fn root_subscription() -> Result<()> {
+	let rpc = SolanaRpcClient::new(LOCALNET);
+	let subscription = rpc.root_subscribe().await?;
+	let unsubscription = subscription.get_unsubscription();
+	let mut stream = subscription.take(2);
+	let mut previous = None;
+
+	while let Some(notification) = stream.next().await {
+		let root = notification.params.result.0;
+		console_log!("root: {root}");
+		check!(notification.method == "rootNotification");
+		check!(previous < Some(root));
+		previous = Some(root);
+	}
+
+	unsubscription.run().await?;
+
+	Ok(())
+}
+
 // TODO this test doesn't actually work. Spent too long trying to get it to
 // fail for the correct reason. It seems like there is a lock somewhere that is
 // only released on drop. So when the subscription is dropped all the stream
@@ -134,3 +215,124 @@ pub async fn account_subscription() -> Result<()> {
 
 	Ok(())
 }
+
+#[wasm_bindgen_test]
+pub async fn localnet_validator_health() -> Result<()> {
+	let rpc = SolanaRpcClient::new(LOCALNET);
+	let status = rpc.get_vote_accounts().await?;
+	let vote_pubkey = status.current[0].vote_pubkey;
+	let health = rpc.validator_health(&vote_pubkey).await?;
+
+	check!(!health.is_delinquent);
+	check!(health.last_vote > 0);
+
+	Ok(())
+}
+
+/// The memo program is loaded into every test validator, so its executable is
+/// reused as the program deployed by `localnet_program_info`.
+const MEMO_PROGRAM_ID: Pubkey = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+/// Bytes written to the buffer account in each transaction.
+const WRITE_CHUNK_SIZE: usize = 900;
+
+#[wasm_bindgen_test]
+pub async fn localnet_program_info() -> Result<()> {
+	let rpc = SolanaRpcClient::new(LOCALNET);
+	let payer = get_wallet_keypair();
+	let buffer = Keypair::new();
+	let program = Keypair::new();
+	let signature = rpc
+		.request_airdrop(&payer.pubkey(), sol_str_to_lamports("10.0").unwrap())
+		.await?;
+	rpc.confirm_transaction(&signature).await?;
+
+	let elf = program_executable(&rpc, &MEMO_PROGRAM_ID).await?;
+	let buffer_lamports = rpc
+		.get_minimum_balance_for_rent_exemption(UpgradeableLoaderState::size_of_buffer(elf.len()))
+		.await?;
+	let program_lamports = rpc
+		.get_minimum_balance_for_rent_exemption(UpgradeableLoaderState::size_of_program())
+		.await?;
+	let create_buffer = loader_instruction::create_buffer(
+		&payer.pubkey(),
+		&buffer.pubkey(),
+		&payer.pubkey(),
+		buffer_lamports,
+		elf.len(),
+	)?;
+	send_instructions(&rpc, &create_buffer, &[&payer, &buffer]).await?;
+
+	for (index, chunk) in elf.chunks(WRITE_CHUNK_SIZE).enumerate() {
+		let offset = u32::try_from(index * WRITE_CHUNK_SIZE)?;
+		let write =
+			loader_instruction::write(&buffer.pubkey(), &payer.pubkey(), offset, chunk.to_vec());
+		send_instructions(&rpc, &[write], &[&payer]).await?;
+	}
+
+	let slot_before_deploy = rpc.get_slot().await?;
+	let deploy = loader_instruction::deploy_with_max_program_len(
+		&payer.pubkey(),
+		&program.pubkey(),
+		&buffer.pubkey(),
+		&payer.pubkey(),
+		program_lamports,
+		elf.len(),
+	)?;
+	send_instructions(&rpc, &deploy, &[&payer, &program]).await?;
+
+	let info = rpc.get_program_info(&program.pubkey()).await?;
+
+	check!(info.authority == Some(payer.pubkey()));
+	check!(info.last_deployed_slot >= slot_before_deploy);
+	check!(info.last_deployed_slot <= rpc.get_slot().await?);
+	check!(info.data_len == elf.len());
+	check!(!info.is_immutable);
+
+	Ok(())
+}
+
+/// Read the executable of a program deployed on the validator, skipping the
+/// program data header when it was deployed with the upgradeable loader.
+async fn program_executable(rpc: &SolanaRpcClient, program_id: &Pubkey) -> Result<Vec<u8>> {
+	let program = rpc.get_account(program_id).await?;
+
+	if program.owner != bpf_loader_upgradeable::ID {
+		return Ok(program.data);
+	}
+
+	let program_data = rpc
+		.get_account(&get_program_data_address(program_id))
+		.await?;
+
+	Ok(program_data.data[UpgradeableLoaderState::size_of_programdata_metadata()..].to_vec())
+}
+
+async fn send_instructions(
+	rpc: &SolanaRpcClient,
+	instructions: &[Instruction],
+	signers: &[&Keypair],
+) -> Result<()> {
+	let recent_blockhash = rpc.get_latest_blockhash().await?;
+	let transaction = solana_transaction::Transaction::new_signed_with_payer(
+		instructions,
+		Some(&signers[0].pubkey()),
+		signers,
+		recent_blockhash,
+	);
+	rpc.send_and_confirm_transaction(&transaction.into())
+		.await?;
+
+	Ok(())
+}
+
+#[wasm_bindgen_test]
+pub async fn rent_sysvar_matches_minimum_balance() -> Result<()> {
+	let rpc = SolanaRpcClient::new(LOCALNET);
+	let rent = rpc.get_rent().await?;
+	let minimum_balance = rpc.get_minimum_balance_for_rent_exemption(165).await?;
+
+	check!(rent.minimum_balance(165) == minimum_balance);
+
+	Ok(())
+}
@@ -1,7 +1,9 @@
 //! Core RPC client types for solana-account-decoder
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
+use std::io;
 #[cfg(feature = "zstd")]
 use std::io::Read;
+use std::io::Write;
 
 use base64::Engine;
 use base64::prelude::BASE64_STANDARD;
@@ -17,6 +19,10 @@ use typed_builder::TypedBuilder;
 
 pub mod token;
 
+/// The maximum number of bytes which [`UiAccountData::decode_to_writer`]
+/// decompresses at a time.
+pub const DECODE_CHUNK_SIZE: usize = 64 * 1024;
+
 /// A duplicate representation of an Account for pretty JSON serialization
 #[serde_as]
 #[skip_serializing_none]
@@ -69,6 +75,49 @@ impl UiAccountData {
 			}
 		}
 	}
+
+	/// Decode the account data into the `writer` and return the number of
+	/// bytes written.
+	///
+	/// [`UiAccountEncoding::Base64Zstd`] data is streamed, decompressing at
+	/// most [`DECODE_CHUNK_SIZE`] bytes at a time, so the decompressed data is
+	/// never held in memory at once. This caps the peak memory of decoding
+	/// large accounts. Other encodings are decoded with
+	/// [`UiAccountData::decode`] before they are written.
+	pub fn decode_to_writer<W: Write>(&self, writer: &mut W) -> io::Result<u64> {
+		match self {
+			#[cfg(feature = "zstd")]
+			UiAccountData::Binary(blob, UiAccountEncoding::Base64Zstd) => {
+				let zstd_data = base64::read::DecoderReader::new(blob.as_bytes(), &BASE64_STANDARD);
+				let mut reader = zstd::stream::read::Decoder::new(zstd_data)?;
+				let mut chunk = vec![0; DECODE_CHUNK_SIZE];
+				let mut written = 0;
+
+				loop {
+					let len = match reader.read(&mut chunk) {
+						Ok(0) => return Ok(written),
+						Ok(len) => len,
+						Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+						Err(error) => return Err(error),
+					};
+
+					writer.write_all(&chunk[..len])?;
+					written += len as u64;
+				}
+			}
+			_ => {
+				let data = self.decode().ok_or_else(|| {
+					io::Error::new(
+						io::ErrorKind::InvalidData,
+						"the account data could not be decoded",
+					)
+				})?;
+				writer.write_all(&data)?;
+
+				Ok(data.len() as u64)
+			}
+		}
+	}
 }
 
 #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -86,6 +86,46 @@ pub struct UiTokenAccount {
 	pub extensions: Vec<UiExtension>,
 }
 
+impl UiTokenAccount {
+	/// Whether incoming transfers to this token account must be preceded by a
+	/// memo instruction, as set by the account's `MemoTransfer` extension.
+	pub fn requires_incoming_transfer_memos(&self) -> bool {
+		self.extensions.iter().any(|extension| {
+			matches!(
+				extension,
+				UiExtension::MemoTransfer(UiMemoTransfer {
+					require_incoming_transfer_memos: true
+				})
+			)
+		})
+	}
+
+	/// Whether the account's `CpiGuard` extension is locked, which prevents
+	/// programs from transferring, burning or approving the account's tokens
+	/// through a cross program invocation.
+	pub fn is_cpi_guard_locked(&self) -> bool {
+		self.extensions.iter().any(|extension| {
+			matches!(
+				extension,
+				UiExtension::CpiGuard(UiCpiGuard { lock_cpi: true })
+			)
+		})
+	}
+
+	/// The account's `ConfidentialTransferAccount` extension. The balances
+	/// are the base64 encoded ciphertexts, which only the owner can decrypt.
+	pub fn confidential_transfer(&self) -> Option<&UiConfidentialTransferAccount> {
+		self.extensions.iter().find_map(|extension| {
+			match extension {
+				UiExtension::ConfidentialTransferAccount(confidential_transfer) => {
+					Some(confidential_transfer)
+				}
+				_ => None,
+			}
+		})
+	}
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub enum UiAccountState {
@@ -152,6 +192,15 @@ pub struct UiMetadataPointer {
 	pub metadata_address: Option<String>,
 }
 
+impl UiMetadataPointer {
+	/// Whether the pointer refers to the `mint` account itself, in which case
+	/// the metadata is read from the mint's own `TokenMetadata` extension
+	/// rather than an external account.
+	pub fn is_self_referential(&self, mint: &str) -> bool {
+		self.metadata_address.as_deref() == Some(mint)
+	}
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct UiTransferHook {
@@ -261,6 +310,23 @@ pub struct UiTransferFeeConfig {
 	pub newer_transfer_fee: UiTransferFee,
 }
 
+impl UiTransferFeeConfig {
+	/// The transfer fee which applies in the `epoch`. The newer fee takes
+	/// effect from the start of its epoch.
+	pub fn get_epoch_fee(&self, epoch: u64) -> &UiTransferFee {
+		if epoch >= self.newer_transfer_fee.epoch {
+			&self.newer_transfer_fee
+		} else {
+			&self.older_transfer_fee
+		}
+	}
+
+	/// The fee withheld from a transfer of `amount` tokens in the `epoch`.
+	pub fn calculate_epoch_fee(&self, epoch: u64, amount: u64) -> Option<u64> {
+		self.get_epoch_fee(epoch).calculate_fee(amount)
+	}
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct UiTransferFee {
@@ -269,6 +335,24 @@ pub struct UiTransferFee {
 	pub transfer_fee_basis_points: u16,
 }
 
+impl UiTransferFee {
+	/// The fee withheld from a transfer of `amount` tokens. The fee is
+	/// rounded up and capped at the `maximum_fee`, the same as the token
+	/// program.
+	pub fn calculate_fee(&self, amount: u64) -> Option<u64> {
+		let basis_points = u128::from(self.transfer_fee_basis_points);
+
+		if basis_points == 0 || amount == 0 {
+			return Some(0);
+		}
+
+		let numerator = u128::from(amount).checked_mul(basis_points)?;
+		let fee = numerator.div_ceil(10_000);
+
+		Some(u64::try_from(fee).ok()?.min(self.maximum_fee))
+	}
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct UiGroupMemberPointer {
@@ -315,6 +399,64 @@ pub struct UiMint {
 	pub extensions: Vec<UiExtension>,
 }
 
+impl UiMint {
+	/// The mint's `MetadataPointer` extension, which records where the
+	/// metadata of the mint is stored.
+	pub fn metadata_pointer(&self) -> Option<&UiMetadataPointer> {
+		self.extensions.iter().find_map(|extension| {
+			match extension {
+				UiExtension::MetadataPointer(metadata_pointer) => Some(metadata_pointer),
+				_ => None,
+			}
+		})
+	}
+
+	/// The metadata stored inline in the mint's `TokenMetadata` extension.
+	pub fn token_metadata(&self) -> Option<&UiTokenMetadata> {
+		self.extensions.iter().find_map(|extension| {
+			match extension {
+				UiExtension::TokenMetadata(token_metadata) => Some(token_metadata),
+				_ => None,
+			}
+		})
+	}
+
+	/// The mint's `PermanentDelegate` extension. The delegate can transfer or
+	/// burn tokens from any account of the mint.
+	pub fn permanent_delegate(&self) -> Option<&UiPermanentDelegate> {
+		self.extensions.iter().find_map(|extension| {
+			match extension {
+				UiExtension::PermanentDelegate(permanent_delegate) => Some(permanent_delegate),
+				_ => None,
+			}
+		})
+	}
+
+	/// The mint's `TransferHook` extension, the program which is invoked on
+	/// every transfer of the mint.
+	pub fn transfer_hook(&self) -> Option<&UiTransferHook> {
+		self.extensions.iter().find_map(|extension| {
+			match extension {
+				UiExtension::TransferHook(transfer_hook) => Some(transfer_hook),
+				_ => None,
+			}
+		})
+	}
+
+	/// The mint's `ConfidentialTransferMint` extension, which configures
+	/// confidential transfers between the accounts of the mint.
+	pub fn confidential_transfer(&self) -> Option<&UiConfidentialTransferMint> {
+		self.extensions.iter().find_map(|extension| {
+			match extension {
+				UiExtension::ConfidentialTransferMint(confidential_transfer) => {
+					Some(confidential_transfer)
+				}
+				_ => None,
+			}
+		})
+	}
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct UiMultisig {
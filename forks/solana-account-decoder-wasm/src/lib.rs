@@ -7,37 +7,44 @@ pub mod parse_address_lookup_table;
 pub mod parse_bpf_loader;
 #[allow(deprecated)]
 pub mod parse_config;
+pub mod parse_feature;
 pub mod parse_nonce;
 pub mod parse_stake;
 pub mod parse_sysvar;
 pub mod parse_token;
 pub mod parse_token_extension;
+pub mod parse_token_metadata;
 pub mod parse_vote;
 pub mod validator_info;
 
 use base64::Engine;
 use base64::prelude::BASE64_STANDARD;
 use solana_account::ReadableAccount;
+pub use solana_account_decoder_client_types_wasm::DECODE_CHUNK_SIZE;
 pub use solana_account_decoder_client_types_wasm::UiAccount;
 pub use solana_account_decoder_client_types_wasm::UiAccountData;
 pub use solana_account_decoder_client_types_wasm::UiAccountEncoding;
 pub use solana_account_decoder_client_types_wasm::UiDataSliceConfig;
 use solana_fee_calculator::FeeCalculator;
 use solana_pubkey::Pubkey;
+use thiserror::Error;
 
 use crate::parse_account_data::AccountAdditionalDataV3;
 use crate::parse_account_data::parse_account_data_v3;
 
 pub type StringAmount = String;
 pub type StringDecimals = String;
+/// The default maximum number of bytes which are base58 encoded. Larger
+/// account data is replaced with an error string.
 pub const MAX_BASE58_BYTES: usize = 128;
 
 fn encode_bs58<T: ReadableAccount>(
 	account: &T,
 	data_slice_config: Option<UiDataSliceConfig>,
+	max_base58_bytes: usize,
 ) -> String {
 	let slice = slice_data(account.data(), data_slice_config);
-	if slice.len() <= MAX_BASE58_BYTES {
+	if slice.len() <= max_base58_bytes {
 		bs58::encode(slice).into_string()
 	} else {
 		"error: data too large for bs58 encoding".to_string()
@@ -50,15 +57,80 @@ pub fn encode_ui_account<T: ReadableAccount>(
 	encoding: UiAccountEncoding,
 	additional_data: Option<AccountAdditionalDataV3>,
 	data_slice_config: Option<UiDataSliceConfig>,
+) -> UiAccount {
+	encode_ui_account_with_options(
+		pubkey,
+		account,
+		encoding,
+		additional_data,
+		data_slice_config,
+		EncodeOptions::default(),
+	)
+}
+
+/// The compression settings for [`UiAccountEncoding::Base64Zstd`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ZstdConfig {
+	/// The zstd compression level. Higher levels make the data smaller at the
+	/// cost of more cpu time. `0` uses the zstd default, and levels outside
+	/// of the range supported by zstd are clamped to the nearest supported
+	/// level.
+	pub level: i32,
+}
+
+impl ZstdConfig {
+	/// The highest, and slowest, compression level.
+	pub const MAX_LEVEL: i32 = 22;
+
+	pub fn new(level: i32) -> Self {
+		Self { level }
+	}
+}
+
+/// The settings of [`encode_ui_account_with_options`]. The default is the
+/// same as [`encode_ui_account`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EncodeOptions {
+	/// The maximum number of bytes which are encoded with the `Binary` and
+	/// `Base58` encodings, [`MAX_BASE58_BYTES`] by default.
+	///
+	/// Base58 encoding is quadratic in the length of the data, since every
+	/// byte is carried through the whole encoded number. Encoding a few
+	/// kilobytes already takes noticeably longer than base64 and large
+	/// accounts can block the main thread in the browser for seconds, so
+	/// prefer [`UiAccountEncoding::Base64`] unless base58 is required.
+	pub max_base58_bytes: usize,
+	/// The compression of [`UiAccountEncoding::Base64Zstd`]. Other encodings
+	/// are unaffected.
+	pub zstd: ZstdConfig,
+}
+
+impl Default for EncodeOptions {
+	fn default() -> Self {
+		Self {
+			max_base58_bytes: MAX_BASE58_BYTES,
+			zstd: ZstdConfig::default(),
+		}
+	}
+}
+
+/// Encode the `account` with custom [`EncodeOptions`].
+pub fn encode_ui_account_with_options<T: ReadableAccount>(
+	pubkey: &Pubkey,
+	account: &T,
+	encoding: UiAccountEncoding,
+	additional_data: Option<AccountAdditionalDataV3>,
+	data_slice_config: Option<UiDataSliceConfig>,
+	options: EncodeOptions,
 ) -> UiAccount {
 	let space = account.data().len();
 	let data = match encoding {
 		UiAccountEncoding::Binary => {
-			let data = encode_bs58(account, data_slice_config);
+			let data = encode_bs58(account, data_slice_config, options.max_base58_bytes);
 			UiAccountData::LegacyBinary(data)
 		}
 		UiAccountEncoding::Base58 => {
-			let data = encode_bs58(account, data_slice_config);
+			let data = encode_bs58(account, data_slice_config, options.max_base58_bytes);
 			UiAccountData::Binary(data, encoding)
 		}
 		UiAccountEncoding::Base64 => {
@@ -67,17 +139,29 @@ pub fn encode_ui_account<T: ReadableAccount>(
 				encoding,
 			)
 		}
+		// without zstd the data is returned uncompressed, the same as when
+		// compression fails
 		#[cfg(not(feature = "zstd"))]
-		UiAccountEncoding::Base64Zstd => todo!("Currently zstd is not supported unless specified"),
+		UiAccountEncoding::Base64Zstd => {
+			UiAccountData::Binary(
+				BASE64_STANDARD.encode(slice_data(account.data(), data_slice_config)),
+				UiAccountEncoding::Base64,
+			)
+		}
 		#[cfg(feature = "zstd")]
 		UiAccountEncoding::Base64Zstd => {
 			use std::io::Write;
 
-			let mut encoder = zstd::stream::write::Encoder::new(Vec::new(), 0).unwrap();
-			match encoder
-				.write_all(slice_data(account.data(), data_slice_config))
-				.and_then(|()| encoder.finish())
-			{
+			let data = slice_data(account.data(), data_slice_config);
+			let range = zstd::compression_level_range();
+			let level = options.zstd.level.clamp(*range.start(), *range.end());
+			let compressed =
+				zstd::stream::write::Encoder::new(Vec::new(), level).and_then(|mut encoder| {
+					encoder.write_all(data)?;
+					encoder.finish()
+				});
+
+			match compressed {
 				Ok(zstd_data) => UiAccountData::Binary(BASE64_STANDARD.encode(zstd_data), encoding),
 				Err(_) => {
 					UiAccountData::Binary(
@@ -110,6 +194,55 @@ pub fn encode_ui_account<T: ReadableAccount>(
 	}
 }
 
+#[derive(Error, Debug)]
+pub enum DecodeError {
+	#[error("Parsed account data can't be losslessly decoded into bytes")]
+	ParsedData,
+
+	#[error("Invalid base58 account data")]
+	InvalidBase58(#[from] bs58::decode::Error),
+
+	#[error("Invalid base64 account data")]
+	InvalidBase64(#[from] base64::DecodeError),
+
+	#[error("Invalid zstd account data")]
+	InvalidZstd(#[from] std::io::Error),
+
+	#[error("Decoding base64+zstd account data requires the `zstd` feature")]
+	ZstdNotSupported,
+}
+
+/// Decode the raw account bytes from `data`, the inverse of
+/// [`encode_ui_account`].
+///
+/// Unlike [`UiAccountData::decode`], the reason the data couldn't be decoded
+/// is returned. Accounts encoded with [`UiAccountEncoding::JsonParsed`] can't
+/// be decoded since the parsed json doesn't contain every byte of the
+/// account.
+pub fn decode_ui_account_data(data: &UiAccountData) -> Result<Vec<u8>, DecodeError> {
+	match data {
+		UiAccountData::Json(_) => Err(DecodeError::ParsedData),
+		UiAccountData::LegacyBinary(blob) => Ok(bs58::decode(blob).into_vec()?),
+		UiAccountData::Binary(blob, encoding) => {
+			match encoding {
+				UiAccountEncoding::Binary | UiAccountEncoding::Base58 => {
+					Ok(bs58::decode(blob).into_vec()?)
+				}
+				UiAccountEncoding::Base64 => Ok(BASE64_STANDARD.decode(blob)?),
+				#[cfg(feature = "zstd")]
+				UiAccountEncoding::Base64Zstd => {
+					let zstd_data = BASE64_STANDARD.decode(blob)?;
+
+					Ok(zstd::stream::decode_all(zstd_data.as_slice())?)
+				}
+				#[cfg(not(feature = "zstd"))]
+				UiAccountEncoding::Base64Zstd => Err(DecodeError::ZstdNotSupported),
+				UiAccountEncoding::JsonParsed => Err(DecodeError::ParsedData),
+			}
+		}
+	}
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct UiFeeCalculator {
@@ -132,18 +265,43 @@ impl Default for UiFeeCalculator {
 	}
 }
 
-fn slice_data(data: &[u8], data_slice_config: Option<UiDataSliceConfig>) -> &[u8] {
-	if let Some(UiDataSliceConfig { offset, length }) = data_slice_config {
-		if offset >= data.len() {
-			&[]
-		} else if length > data.len() - offset {
-			&data[offset..]
-		} else {
-			&data[offset..offset + length]
-		}
-	} else {
-		data
-	}
+/// Slice the account `data` with the `data_slice_config`, the same way the
+/// rpc node does. This never panics: an `offset` past the end of the data
+/// returns an empty slice and a `length` past the end returns the available
+/// tail.
+pub fn slice_data(data: &[u8], data_slice_config: Option<UiDataSliceConfig>) -> &[u8] {
+	let Some(UiDataSliceConfig { offset, length }) = data_slice_config else {
+		return data;
+	};
+	let start = offset.min(data.len());
+	let end = start.saturating_add(length).min(data.len());
+
+	&data[start..end]
+}
+
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("Data slice of {length} bytes at offset {offset} is out of range for {data_len} bytes")]
+pub struct SliceError {
+	pub offset: usize,
+	pub length: usize,
+	pub data_len: usize,
+}
+
+/// The same as [`slice_data`], but fails when the slice doesn't fit within
+/// the `data` instead of truncating it.
+pub fn slice_data_checked(
+	data: &[u8],
+	data_slice_config: UiDataSliceConfig,
+) -> Result<&[u8], SliceError> {
+	let UiDataSliceConfig { offset, length } = data_slice_config;
+	let error = SliceError {
+		offset,
+		length,
+		data_len: data.len(),
+	};
+	let end = offset.checked_add(length).ok_or(error)?;
+
+	data.get(offset..end).ok_or(error)
 }
 
 #[cfg(test)]
@@ -151,6 +309,7 @@ mod test {
 	use assert_matches::assert_matches;
 	use solana_account::Account;
 	use solana_account::AccountSharedData;
+	use solana_account_decoder_client_types_wasm::ParsedAccount;
 
 	use super::*;
 
@@ -180,6 +339,53 @@ mod test {
 			length: 2,
 		});
 		assert_eq!(slice_data(&data, slice_config), &[] as &[u8]);
+
+		let slice_config = Some(UiDataSliceConfig {
+			offset: 3,
+			length: usize::MAX,
+		});
+		assert_eq!(slice_data(&data, slice_config), &data[3..]);
+
+		let slice_config = Some(UiDataSliceConfig {
+			offset: 5,
+			length: 0,
+		});
+		assert_eq!(slice_data(&data, slice_config), &[] as &[u8]);
+	}
+
+	#[test]
+	fn test_slice_data_checked() {
+		let data = vec![1, 2, 3, 4, 5];
+		let slice_config = UiDataSliceConfig {
+			offset: 1,
+			length: 4,
+		};
+		assert_eq!(slice_data_checked(&data, slice_config), Ok(&data[1..]));
+
+		let slice_config = UiDataSliceConfig {
+			offset: 5,
+			length: 0,
+		};
+		assert_eq!(slice_data_checked(&data, slice_config), Ok(&[] as &[u8]));
+
+		let slice_config = UiDataSliceConfig {
+			offset: 2,
+			length: 4,
+		};
+		assert_eq!(
+			slice_data_checked(&data, slice_config),
+			Err(SliceError {
+				offset: 2,
+				length: 4,
+				data_len: 5,
+			})
+		);
+
+		let slice_config = UiDataSliceConfig {
+			offset: 1,
+			length: usize::MAX,
+		};
+		assert!(slice_data_checked(&data, slice_config).is_err());
 	}
 
 	#[test]
@@ -192,7 +398,7 @@ mod test {
 
 		// Whole account
 		assert_eq!(
-			encode_bs58(&account, None),
+			encode_bs58(&account, None, MAX_BASE58_BYTES),
 			"error: data too large for bs58 encoding"
 		);
 
@@ -203,7 +409,8 @@ mod test {
 				Some(UiDataSliceConfig {
 					length: MAX_BASE58_BYTES + 1,
 					offset: 1
-				})
+				}),
+				MAX_BASE58_BYTES
 			),
 			"error: data too large for bs58 encoding"
 		);
@@ -215,7 +422,8 @@ mod test {
 				Some(UiDataSliceConfig {
 					length: MAX_BASE58_BYTES,
 					offset: 1
-				})
+				}),
+				MAX_BASE58_BYTES
 			),
 			"error: data too large for bs58 encoding"
 		);
@@ -228,12 +436,49 @@ mod test {
 				Some(UiDataSliceConfig {
 					length: MAX_BASE58_BYTES + 1,
 					offset: 2
-				})
+				}),
+				MAX_BASE58_BYTES
 			),
 			"error: data too large for bs58 encoding"
 		);
 	}
 
+	#[test]
+	fn test_encode_account_with_raised_base58_limit() {
+		let data = vec![42; MAX_BASE58_BYTES * 2];
+		let account = AccountSharedData::from(Account {
+			data: data.clone(),
+			..Account::default()
+		});
+		let pubkey = Pubkey::new_unique();
+
+		let encoded_account =
+			encode_ui_account(&pubkey, &account, UiAccountEncoding::Base58, None, None);
+		assert_eq!(
+			encoded_account.data,
+			UiAccountData::Binary(
+				"error: data too large for bs58 encoding".to_string(),
+				UiAccountEncoding::Base58
+			)
+		);
+
+		let encoded_account = encode_ui_account_with_options(
+			&pubkey,
+			&account,
+			UiAccountEncoding::Base58,
+			None,
+			None,
+			EncodeOptions {
+				max_base58_bytes: MAX_BASE58_BYTES * 2,
+				..EncodeOptions::default()
+			},
+		);
+		assert_eq!(
+			encoded_account.data,
+			UiAccountData::Binary(bs58::encode(data).into_string(), UiAccountEncoding::Base58)
+		);
+	}
+
 	#[test]
 	#[cfg(feature = "zstd")]
 	fn test_base64_zstd() {
@@ -257,4 +502,211 @@ mod test {
 		let decoded_account = encoded_account.decode::<AccountSharedData>().unwrap();
 		assert_eq!(decoded_account.data(), &vec![0; 1024]);
 	}
+
+	#[test]
+	#[cfg(feature = "zstd")]
+	fn test_base64_zstd_decode_to_writer() {
+		/// Checks the decoded data without keeping it.
+		#[derive(Default)]
+		struct ZeroCounter {
+			len: usize,
+			max_write: usize,
+		}
+
+		impl std::io::Write for ZeroCounter {
+			fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+				assert!(buf.iter().all(|&byte| byte == 0));
+				self.len += buf.len();
+				self.max_write = self.max_write.max(buf.len());
+				Ok(buf.len())
+			}
+
+			fn flush(&mut self) -> std::io::Result<()> {
+				Ok(())
+			}
+		}
+
+		let len = 8 * 1024 * 1024;
+		let encoded_account = encode_ui_account(
+			&Pubkey::default(),
+			&AccountSharedData::from(Account {
+				data: vec![0; len],
+				..Account::default()
+			}),
+			UiAccountEncoding::Base64Zstd,
+			None,
+			None,
+		);
+		let mut writer = ZeroCounter::default();
+
+		let written = encoded_account.data.decode_to_writer(&mut writer).unwrap();
+		assert_eq!(written, len as u64);
+		assert_eq!(writer.len, len);
+		assert!(writer.max_write <= DECODE_CHUNK_SIZE);
+
+		let data =
+			UiAccountData::Binary(BASE64_STANDARD.encode([1, 2, 3]), UiAccountEncoding::Base64);
+		let mut decoded = vec![];
+		assert_eq!(data.decode_to_writer(&mut decoded).unwrap(), 3);
+		assert_eq!(decoded, [1, 2, 3]);
+
+		let invalid = UiAccountData::Binary("not base64".into(), UiAccountEncoding::Base64Zstd);
+		assert!(invalid.decode_to_writer(&mut decoded).is_err());
+	}
+
+	#[test]
+	#[cfg(feature = "zstd")]
+	fn test_base64_zstd_with_compression_level() {
+		let data = (0..4096_u32)
+			.flat_map(|index| (index % 97).to_le_bytes())
+			.collect::<Vec<_>>();
+		let account = AccountSharedData::from(Account {
+			data: data.clone(),
+			..Account::default()
+		});
+		let encode = |level| {
+			encode_ui_account_with_options(
+				&Pubkey::default(),
+				&account,
+				UiAccountEncoding::Base64Zstd,
+				None,
+				None,
+				EncodeOptions {
+					zstd: ZstdConfig::new(level),
+					..EncodeOptions::default()
+				},
+			)
+		};
+		let encoded_len = |account: &UiAccount| {
+			match &account.data {
+				UiAccountData::Binary(blob, UiAccountEncoding::Base64Zstd) => blob.len(),
+				data => panic!("unexpected data: {data:?}"),
+			}
+		};
+
+		let fast = encode(1);
+		let small = encode(ZstdConfig::MAX_LEVEL);
+		assert!(encoded_len(&small) <= encoded_len(&fast));
+
+		// out of range levels are clamped rather than failing
+		for account in [fast, small, encode(i32::MAX), encode(i32::MIN)] {
+			assert_eq!(account.decode::<Account>().unwrap().data, data);
+		}
+	}
+
+	#[test]
+	#[cfg(feature = "zstd")]
+	fn test_encode_options_are_combined() {
+		let data = vec![42; MAX_BASE58_BYTES * 2];
+		let account = AccountSharedData::from(Account {
+			data: data.clone(),
+			..Account::default()
+		});
+		let options = EncodeOptions {
+			max_base58_bytes: data.len(),
+			zstd: ZstdConfig::new(ZstdConfig::MAX_LEVEL),
+		};
+		let encode = |encoding| {
+			encode_ui_account_with_options(
+				&Pubkey::default(),
+				&account,
+				encoding,
+				None,
+				None,
+				options,
+			)
+		};
+
+		assert_eq!(
+			encode(UiAccountEncoding::Base58).data,
+			UiAccountData::Binary(bs58::encode(&data).into_string(), UiAccountEncoding::Base58)
+		);
+		let compressed = encode(UiAccountEncoding::Base64Zstd);
+		assert_matches!(
+			compressed.data,
+			UiAccountData::Binary(_, UiAccountEncoding::Base64Zstd)
+		);
+		assert_eq!(compressed.decode::<Account>().unwrap().data, data);
+	}
+
+	#[test]
+	fn test_decode_ui_account_data() {
+		let data = vec![1, 2, 3, 4, 5];
+		let account = AccountSharedData::from(Account {
+			data: data.clone(),
+			..Account::default()
+		});
+		let mut encodings = vec![
+			UiAccountEncoding::Binary,
+			UiAccountEncoding::Base58,
+			UiAccountEncoding::Base64,
+		];
+
+		if cfg!(feature = "zstd") {
+			encodings.push(UiAccountEncoding::Base64Zstd);
+		}
+
+		for encoding in encodings {
+			let encoded_account =
+				encode_ui_account(&Pubkey::default(), &account, encoding, None, None);
+			assert_eq!(
+				decode_ui_account_data(&encoded_account.data).unwrap(),
+				data,
+				"{encoding:?}"
+			);
+		}
+
+		assert_matches!(
+			decode_ui_account_data(&UiAccountData::Binary(
+				"not base64!".to_string(),
+				UiAccountEncoding::Base64
+			)),
+			Err(DecodeError::InvalidBase64(_))
+		);
+		assert_matches!(
+			decode_ui_account_data(&UiAccountData::LegacyBinary("0OIl".to_string())),
+			Err(DecodeError::InvalidBase58(_))
+		);
+		assert_matches!(
+			decode_ui_account_data(&UiAccountData::Json(ParsedAccount {
+				program: "vote".to_string(),
+				parsed: serde_json::Value::Null,
+				space: 0,
+			})),
+			Err(DecodeError::ParsedData)
+		);
+	}
+
+	#[test]
+	#[cfg(not(feature = "zstd"))]
+	fn test_base64_zstd_without_feature_falls_back_to_base64() {
+		let account = AccountSharedData::from(Account {
+			data: vec![1, 2, 3],
+			..Account::default()
+		});
+		let encoded_account = encode_ui_account(
+			&Pubkey::default(),
+			&account,
+			UiAccountEncoding::Base64Zstd,
+			None,
+			None,
+		);
+
+		assert_eq!(
+			encoded_account.data,
+			UiAccountData::Binary("AQID".to_string(), UiAccountEncoding::Base64)
+		);
+	}
+
+	#[test]
+	#[cfg(not(feature = "zstd"))]
+	fn test_decode_base64_zstd_without_feature() {
+		assert_matches!(
+			decode_ui_account_data(&UiAccountData::Binary(
+				String::new(),
+				UiAccountEncoding::Base64Zstd
+			)),
+			Err(DecodeError::ZstdNotSupported)
+		);
+	}
 }
@@ -1,14 +1,17 @@
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::PoisonError;
+use std::sync::RwLock;
 
 use base64::Engine;
 use base64::prelude::BASE64_STANDARD;
 use inflector::Inflector;
 use serde::Deserialize;
 use serde::Serialize;
+use serde_json::Value;
 use solana_account::ReadableAccount;
 pub use solana_account_decoder_client_types_wasm::ParsedAccount;
 use solana_account_decoder_client_types_wasm::UiAccount;
-use solana_account_decoder_client_types_wasm::UiAccountData;
 use solana_account_decoder_client_types_wasm::UiAccountEncoding;
 use solana_account_decoder_client_types_wasm::UiDataSliceConfig;
 use solana_clock::UnixTimestamp;
@@ -17,24 +20,29 @@ use solana_pubkey::Pubkey;
 use solana_sdk_ids::address_lookup_table;
 use solana_sdk_ids::bpf_loader_upgradeable;
 use solana_sdk_ids::config;
+use solana_sdk_ids::feature;
 use solana_sdk_ids::stake;
 use solana_sdk_ids::system_program;
 use solana_sdk_ids::sysvar;
 use solana_sdk_ids::vote;
+use spl_token_2022_interface::extension::BaseStateWithExtensions;
+use spl_token_2022_interface::extension::StateWithExtensions;
 use spl_token_2022_interface::extension::interest_bearing_mint::InterestBearingConfig;
 use spl_token_2022_interface::extension::scaled_ui_amount::ScaledUiAmountConfig;
+use spl_token_2022_interface::state::Mint;
 use thiserror::Error;
 
-use crate::MAX_BASE58_BYTES;
 use crate::parse_address_lookup_table::parse_address_lookup_table;
 use crate::parse_bpf_loader::parse_bpf_upgradeable_loader;
 use crate::parse_config::parse_config;
+use crate::parse_feature::parse_feature;
 use crate::parse_nonce::parse_nonce;
 use crate::parse_stake::parse_stake;
 use crate::parse_sysvar::parse_sysvar;
 use crate::parse_token::parse_token_v3;
+use crate::parse_token_metadata::parse_token_metadata;
+use crate::parse_token_metadata::{self};
 use crate::parse_vote::parse_vote;
-use crate::slice_data;
 
 pub static PARSABLE_PROGRAM_IDS: std::sync::LazyLock<HashMap<Pubkey, ParsableAccount>> =
 	std::sync::LazyLock::new(|| {
@@ -48,6 +56,7 @@ pub static PARSABLE_PROGRAM_IDS: std::sync::LazyLock<HashMap<Pubkey, ParsableAcc
 			ParsableAccount::BpfUpgradeableLoader,
 		);
 		m.insert(config::id(), ParsableAccount::Config);
+		m.insert(feature::id(), ParsableAccount::Feature);
 		m.insert(system_program::id(), ParsableAccount::Nonce);
 		m.insert(spl_token_interface::id(), ParsableAccount::SplToken);
 		m.insert(
@@ -56,10 +65,60 @@ pub static PARSABLE_PROGRAM_IDS: std::sync::LazyLock<HashMap<Pubkey, ParsableAcc
 		);
 		m.insert(stake::id(), ParsableAccount::Stake);
 		m.insert(sysvar::id(), ParsableAccount::Sysvar);
+		m.insert(parse_token_metadata::id(), ParsableAccount::TokenMetadata);
 		m.insert(vote::id(), ParsableAccount::Vote);
 		m
 	});
 
+/// Parses the `data` of an account, with the pubkey of the account, into
+/// json. See [`register_parser`].
+pub type AccountParser =
+	Box<dyn Fn(&[u8], &Pubkey) -> Result<Value, ParseAccountError> + Send + Sync>;
+
+type SharedAccountParser =
+	Arc<dyn Fn(&[u8], &Pubkey) -> Result<Value, ParseAccountError> + Send + Sync>;
+
+static CUSTOM_PARSERS: std::sync::LazyLock<RwLock<HashMap<Pubkey, SharedAccountParser>>> =
+	std::sync::LazyLock::new(RwLock::default);
+
+/// Register a `parser` for the accounts owned by `program_id`, which is used
+/// by [`parse_account_data_v3`] for programs which aren't built in. The
+/// `program` of the parsed accounts is the `program_id`.
+///
+/// The built in parsers take precedence, so `false` is returned and the
+/// `parser` is ignored when `program_id` is in [`PARSABLE_PROGRAM_IDS`].
+/// Registering another parser for the same `program_id` replaces it.
+pub fn register_parser(program_id: Pubkey, parser: AccountParser) -> bool {
+	if PARSABLE_PROGRAM_IDS.contains_key(&program_id) {
+		return false;
+	}
+
+	CUSTOM_PARSERS
+		.write()
+		.unwrap_or_else(PoisonError::into_inner)
+		.insert(program_id, Arc::from(parser));
+
+	true
+}
+
+/// Remove the parser registered for `program_id` with [`register_parser`].
+/// Returns whether a parser was registered.
+pub fn unregister_parser(program_id: &Pubkey) -> bool {
+	CUSTOM_PARSERS
+		.write()
+		.unwrap_or_else(PoisonError::into_inner)
+		.remove(program_id)
+		.is_some()
+}
+
+fn custom_parser(program_id: &Pubkey) -> Option<SharedAccountParser> {
+	CUSTOM_PARSERS
+		.read()
+		.unwrap_or_else(PoisonError::into_inner)
+		.get(program_id)
+		.cloned()
+}
+
 #[derive(Error, Debug)]
 pub enum ParseAccountError {
 	#[error("{0:?} account not parsable")]
@@ -76,6 +135,9 @@ pub enum ParseAccountError {
 
 	#[error("Serde json error")]
 	SerdeJsonError(#[from] serde_json::error::Error),
+
+	#[error("Invalid base64 account data")]
+	InvalidBase64(#[from] base64::DecodeError),
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -84,28 +146,19 @@ pub enum ParsableAccount {
 	AddressLookupTable,
 	BpfUpgradeableLoader,
 	Config,
+	Feature,
 	Nonce,
 	SplToken,
 	SplToken2022,
 	Stake,
 	Sysvar,
+	TokenMetadata,
 	Vote,
 }
 
 pub trait EncodeUiAccount {}
 
-fn encode_ui_accout_bs58<T: ReadableAccount>(
-	account: &T,
-	data_slice_config: Option<UiDataSliceConfig>,
-) -> String {
-	let slice = slice_data(account.data(), data_slice_config);
-	if slice.len() <= MAX_BASE58_BYTES {
-		bs58::encode(slice).into_string()
-	} else {
-		"error: data too large for bs58 encoding".to_string()
-	}
-}
-
+/// Encode the `account` for an rpc response. See [`crate::encode_ui_account`].
 pub fn encode_ui_account<T: ReadableAccount>(
 	pubkey: &Pubkey,
 	account: &T,
@@ -113,63 +166,13 @@ pub fn encode_ui_account<T: ReadableAccount>(
 	additional_data: Option<AccountAdditionalDataV3>,
 	data_slice_config: Option<UiDataSliceConfig>,
 ) -> UiAccount {
-	let space = account.data().len();
-	let data = match encoding {
-		UiAccountEncoding::Binary => {
-			let data = encode_ui_accout_bs58(account, data_slice_config);
-			UiAccountData::LegacyBinary(data)
-		}
-		UiAccountEncoding::Base58 => {
-			let data = encode_ui_accout_bs58(account, data_slice_config);
-			UiAccountData::Binary(data, encoding)
-		}
-		UiAccountEncoding::Base64 => {
-			UiAccountData::Binary(
-				BASE64_STANDARD.encode(slice_data(account.data(), data_slice_config)),
-				encoding,
-			)
-		}
-		#[cfg(not(feature = "zstd"))]
-		UiAccountEncoding::Base64Zstd => todo!("zstd not supported without the zstd feature flag"),
-		#[cfg(feature = "zstd")]
-		UiAccountEncoding::Base64Zstd => {
-			use std::io::Write;
-
-			let mut encoder = zstd::stream::write::Encoder::new(Vec::new(), 0).unwrap();
-			match encoder
-				.write_all(slice_data(account.data(), data_slice_config))
-				.and_then(|()| encoder.finish())
-			{
-				Ok(zstd_data) => UiAccountData::Binary(BASE64_STANDARD.encode(zstd_data), encoding),
-				Err(_) => {
-					UiAccountData::Binary(
-						BASE64_STANDARD.encode(slice_data(account.data(), data_slice_config)),
-						UiAccountEncoding::Base64,
-					)
-				}
-			}
-		}
-		UiAccountEncoding::JsonParsed => {
-			if let Ok(parsed_data) =
-				parse_account_data_v3(pubkey, account.owner(), account.data(), additional_data)
-			{
-				UiAccountData::Json(parsed_data)
-			} else {
-				UiAccountData::Binary(
-					BASE64_STANDARD.encode(slice_data(account.data(), data_slice_config)),
-					UiAccountEncoding::Base64,
-				)
-			}
-		}
-	};
-	UiAccount {
-		lamports: account.lamports(),
-		data,
-		owner: *account.owner(),
-		executable: account.executable(),
-		rent_epoch: account.rent_epoch(),
-		space: Some(space as u64),
-	}
+	crate::encode_ui_account(
+		pubkey,
+		account,
+		encoding,
+		additional_data,
+		data_slice_config,
+	)
 }
 
 #[derive(Clone, Copy, Default)]
@@ -216,6 +219,27 @@ impl SplTokenAdditionalDataV2 {
 			..Default::default()
 		}
 	}
+
+	/// Create the additional data from the `data` of a token mint account.
+	/// The `unix_timestamp` is used for the interest bearing and scaled ui
+	/// amount extensions.
+	pub fn from_mint_data(data: &[u8], unix_timestamp: UnixTimestamp) -> Option<Self> {
+		let mint = StateWithExtensions::<Mint>::unpack(data).ok()?;
+		let interest_bearing_config = mint
+			.get_extension::<InterestBearingConfig>()
+			.map(|config| (*config, unix_timestamp))
+			.ok();
+		let scaled_ui_amount_config = mint
+			.get_extension::<ScaledUiAmountConfig>()
+			.map(|config| (*config, unix_timestamp))
+			.ok();
+
+		Some(Self {
+			decimals: mint.base.decimals,
+			interest_bearing_config,
+			scaled_ui_amount_config,
+		})
+	}
 }
 
 pub fn parse_account_data_v3(
@@ -224,9 +248,16 @@ pub fn parse_account_data_v3(
 	data: &[u8],
 	additional_data: Option<AccountAdditionalDataV3>,
 ) -> Result<ParsedAccount, ParseAccountError> {
-	let program_name = PARSABLE_PROGRAM_IDS
-		.get(program_id)
-		.ok_or(ParseAccountError::ProgramNotParsable)?;
+	let Some(program_name) = PARSABLE_PROGRAM_IDS.get(program_id) else {
+		// the lock isn't held while parsing, so a parser may register others
+		let parser = custom_parser(program_id).ok_or(ParseAccountError::ProgramNotParsable)?;
+
+		return Ok(ParsedAccount {
+			program: program_id.to_string(),
+			parsed: sort_keys(parser(data, pubkey)?),
+			space: data.len() as u64,
+		});
+	};
 	let additional_data = additional_data.unwrap_or_default();
 	let parsed_json = match program_name {
 		ParsableAccount::AddressLookupTable => {
@@ -236,6 +267,7 @@ pub fn parse_account_data_v3(
 			serde_json::to_value(parse_bpf_upgradeable_loader(data)?)?
 		}
 		ParsableAccount::Config => serde_json::to_value(parse_config(data, pubkey)?)?,
+		ParsableAccount::Feature => serde_json::to_value(parse_feature(data)?)?,
 		ParsableAccount::Nonce => serde_json::to_value(parse_nonce(data)?)?,
 		ParsableAccount::SplToken | ParsableAccount::SplToken2022 => {
 			serde_json::to_value(parse_token_v3(
@@ -245,23 +277,69 @@ pub fn parse_account_data_v3(
 		}
 		ParsableAccount::Stake => serde_json::to_value(parse_stake(data)?)?,
 		ParsableAccount::Sysvar => serde_json::to_value(parse_sysvar(data, pubkey)?)?,
+		ParsableAccount::TokenMetadata => serde_json::to_value(parse_token_metadata(data)?)?,
 		ParsableAccount::Vote => serde_json::to_value(parse_vote(data)?)?,
 	};
 	Ok(ParsedAccount {
 		program: format!("{program_name:?}").to_kebab_case(),
-		parsed: parsed_json,
+		parsed: sort_keys(parsed_json),
 		space: data.len() as u64,
 	})
 }
 
+/// Recursively sort the keys of every object in the `value`.
+///
+/// The order of a `serde_json::Map` depends on whether the `preserve_order`
+/// feature is enabled anywhere in the dependency graph. Sorting explicitly
+/// guarantees that the same account data always serializes to byte identical
+/// JSON, which allows parsed accounts to be cached by their content hash.
+fn sort_keys(value: Value) -> Value {
+	match value {
+		Value::Object(map) => {
+			let mut entries = map.into_iter().collect::<Vec<_>>();
+			entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+			Value::Object(
+				entries
+					.into_iter()
+					.map(|(key, value)| (key, sort_keys(value)))
+					.collect(),
+			)
+		}
+		Value::Array(values) => Value::Array(values.into_iter().map(sort_keys).collect()),
+		value => value,
+	}
+}
+
+/// Decode base64 account data which was obtained outside of the rpc, such as
+/// from a snapshot or a log, and parse it with [`parse_account_data_v3`].
+///
+/// [`ParseAccountError::InvalidBase64`] is returned when the data can't be
+/// decoded, which is distinct from the errors returned when parsing fails.
+pub fn parse_account_data_from_base64(
+	pubkey: &Pubkey,
+	program_id: &Pubkey,
+	base64: &str,
+	additional_data: Option<AccountAdditionalDataV3>,
+) -> Result<ParsedAccount, ParseAccountError> {
+	let data = BASE64_STANDARD.decode(base64)?;
+
+	parse_account_data_v3(pubkey, program_id, &data, additional_data)
+}
+
 #[cfg(test)]
 mod test {
+	use assert_matches::assert_matches;
 	use solana_nonce::state::Data;
 	use solana_nonce::state::State;
 	use solana_nonce::versions::Versions;
+	use solana_program_pack::Pack;
 	use solana_vote_interface::program::id as vote_program_id;
 	use solana_vote_interface::state::VoteStateV3;
 	use solana_vote_interface::state::VoteStateVersions;
+	use spl_token_2022_interface::extension::AccountType;
+	use spl_token_2022_interface::state::Account;
+	use spl_token_2022_interface::state::AccountState;
 
 	use super::*;
 
@@ -297,5 +375,239 @@ mod test {
 		.unwrap();
 		assert_eq!(parsed.program, "nonce".to_string());
 		assert_eq!(parsed.space, State::size() as u64);
+
+		let feature_data = bincode::serialize(&Some(42_u64)).unwrap();
+		let parsed =
+			parse_account_data_v3(&account_pubkey, &feature::id(), &feature_data, None).unwrap();
+		assert_eq!(parsed.program, "feature".to_string());
+		assert_eq!(
+			parsed.parsed,
+			serde_json::json!({ "activated": true, "activatedAt": 42 })
+		);
+	}
+
+	#[test]
+	fn test_parse_account_data_from_base64() {
+		let account_pubkey = solana_pubkey::new_rand();
+		let nonce_data = Versions::new(State::Initialized(Data::default()));
+		let encoded = BASE64_STANDARD.encode(bincode::serialize(&nonce_data).unwrap());
+		let parsed =
+			parse_account_data_from_base64(&account_pubkey, &system_program::id(), &encoded, None)
+				.unwrap();
+		assert_eq!(parsed.program, "nonce".to_string());
+		assert_eq!(parsed.parsed["type"], "initialized");
+
+		assert_matches!(
+			parse_account_data_from_base64(
+				&account_pubkey,
+				&system_program::id(),
+				"not base64!",
+				None
+			),
+			Err(ParseAccountError::InvalidBase64(_))
+		);
+		assert_matches!(
+			parse_account_data_from_base64(&account_pubkey, &system_program::id(), "AAAA", None),
+			Err(ParseAccountError::InstructionError(_))
+		);
+	}
+
+	#[test]
+	fn test_parse_account_data_with_registered_parser() {
+		let account_pubkey = solana_pubkey::new_rand();
+		let program_id = solana_pubkey::new_rand();
+		let data = vec![7, 0, 0, 0, 0, 0, 0, 0, 42];
+		assert_matches!(
+			parse_account_data_v3(&account_pubkey, &program_id, &data, None),
+			Err(ParseAccountError::ProgramNotParsable)
+		);
+
+		assert!(register_parser(
+			program_id,
+			Box::new(|data, pubkey| {
+				let (discriminator, count) = data
+					.split_first_chunk::<8>()
+					.ok_or(ParseAccountError::ProgramNotParsable)?;
+
+				Ok(serde_json::json!({
+					"type": discriminator[0],
+					"info": { "pubkey": pubkey.to_string(), "count": count },
+				}))
+			}),
+		));
+		let parsed = parse_account_data_v3(&account_pubkey, &program_id, &data, None).unwrap();
+		assert_eq!(parsed.program, program_id.to_string());
+		assert_eq!(parsed.space, 9);
+		assert_eq!(parsed.parsed["type"], 7);
+		assert_eq!(parsed.parsed["info"]["count"], serde_json::json!([42]));
+		assert_eq!(parsed.parsed["info"]["pubkey"], account_pubkey.to_string());
+		assert_matches!(
+			parse_account_data_v3(&account_pubkey, &program_id, &data[..4], None),
+			Err(ParseAccountError::ProgramNotParsable)
+		);
+
+		assert!(unregister_parser(&program_id));
+		assert!(!unregister_parser(&program_id));
+		assert_matches!(
+			parse_account_data_v3(&account_pubkey, &program_id, &data, None),
+			Err(ParseAccountError::ProgramNotParsable)
+		);
+	}
+
+	#[test]
+	fn test_register_parser_does_not_shadow_built_in_parsers() {
+		let account_pubkey = solana_pubkey::new_rand();
+		let registered = register_parser(
+			system_program::id(),
+			Box::new(|_, _| Ok(serde_json::json!({ "type": "shadowed" }))),
+		);
+		assert!(!registered);
+
+		let nonce_data = Versions::new(State::Initialized(Data::default()));
+		let nonce_account_data = bincode::serialize(&nonce_data).unwrap();
+		let parsed = parse_account_data_v3(
+			&account_pubkey,
+			&system_program::id(),
+			&nonce_account_data,
+			None,
+		)
+		.unwrap();
+		assert_eq!(parsed.program, "nonce".to_string());
+		assert!(!unregister_parser(&system_program::id()));
+	}
+
+	fn assert_sorted_keys(value: &Value) {
+		match value {
+			Value::Object(map) => {
+				let keys = map.keys().collect::<Vec<_>>();
+				let mut sorted = keys.clone();
+				sorted.sort();
+				assert_eq!(keys, sorted);
+				map.values().for_each(assert_sorted_keys);
+			}
+			Value::Array(values) => values.iter().for_each(assert_sorted_keys),
+			_ => {}
+		}
+	}
+
+	#[test]
+	fn test_parse_account_data_is_deterministic() {
+		let account_pubkey = solana_pubkey::new_rand();
+		let vote_state = VoteStateV3::default();
+		let mut vote_account_data: Vec<u8> = vec![0; VoteStateV3::size_of()];
+		let versioned = VoteStateVersions::new_v3(vote_state);
+		VoteStateV3::serialize(&versioned, &mut vote_account_data).unwrap();
+		let parse = || {
+			parse_account_data_v3(
+				&account_pubkey,
+				&vote_program_id(),
+				&vote_account_data,
+				None,
+			)
+			.unwrap()
+		};
+
+		let first = serde_json::to_string(&parse()).unwrap();
+		let second = serde_json::to_string(&parse()).unwrap();
+		assert_eq!(first, second);
+		assert_sorted_keys(&parse().parsed);
+	}
+
+	/// Deterministic pseudo random bytes, so failures are reproducible.
+	fn pseudo_random_bytes(seed: u64, len: usize) -> Vec<u8> {
+		let mut state = seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1;
+
+		(0..len)
+			.map(|_| {
+				state ^= state << 13;
+				state ^= state >> 7;
+				state ^= state << 17;
+				state.to_le_bytes()[0]
+			})
+			.collect()
+	}
+
+	#[test]
+	fn test_parse_account_data_never_panics_on_malformed_data() {
+		let account_pubkey = solana_pubkey::new_rand();
+		let mut targets = PARSABLE_PROGRAM_IDS
+			.keys()
+			.map(|program_id| (account_pubkey, *program_id))
+			.collect::<Vec<_>>();
+		targets.extend(
+			[
+				sysvar::clock::id(),
+				sysvar::epoch_schedule::id(),
+				sysvar::fees::id(),
+				sysvar::recent_blockhashes::id(),
+				sysvar::rent::id(),
+				sysvar::rewards::id(),
+				sysvar::slot_hashes::id(),
+				sysvar::slot_history::id(),
+				sysvar::stake_history::id(),
+				sysvar::last_restart_slot::id(),
+				sysvar::epoch_rewards::id(),
+			]
+			.map(|pubkey| (pubkey, sysvar::id())),
+		);
+		#[allow(deprecated)]
+		targets.push((solana_stake_interface::config::id(), config::id()));
+
+		let mut vote_account_data = vec![0; VoteStateV3::size_of()];
+		VoteStateV3::serialize(
+			&VoteStateVersions::new_v3(VoteStateV3::default()),
+			&mut vote_account_data,
+		)
+		.unwrap();
+		let nonce_account_data =
+			bincode::serialize(&Versions::new(State::Initialized(Data::default()))).unwrap();
+
+		let mut samples = Vec::new();
+		for len in (0..=512).chain([1_000, 3_762, 10_000]) {
+			samples.push(vec![0; len]);
+			samples.push(vec![u8::MAX; len]);
+			samples.push(pseudo_random_bytes(len as u64, len));
+		}
+		for valid in [&vote_account_data, &nonce_account_data] {
+			for len in 0..valid.len() {
+				samples.push(valid[..len].to_vec());
+			}
+		}
+
+		// initialized token 2022 mints and accounts followed by a single
+		// extension with garbage contents
+		let mut mint = vec![0; Account::LEN + 1];
+		mint[45] = 1;
+		mint[Account::LEN] = AccountType::Mint as u8;
+		let mut account = vec![0; Account::LEN + 1];
+		account[108] = AccountState::Initialized as u8;
+		account[Account::LEN] = AccountType::Account as u8;
+		for base in [&mint, &account] {
+			for extension_type in 0..64_u16 {
+				for len in [0_u16, 1, 8, 32, 64, 100, 300] {
+					let mut data = base.clone();
+					data.extend(extension_type.to_le_bytes());
+					data.extend(len.to_le_bytes());
+					data.extend(pseudo_random_bytes(u64::from(extension_type), len.into()));
+					samples.push(data);
+				}
+			}
+		}
+
+		let additional_data = [
+			None,
+			Some(AccountAdditionalDataV3 {
+				spl_token_additional_data: Some(SplTokenAdditionalDataV2::with_decimals(6)),
+			}),
+		];
+
+		for (pubkey, program_id) in &targets {
+			for data in &samples {
+				for additional_data in additional_data {
+					// only the absence of a panic matters here
+					let _ = parse_account_data_v3(pubkey, program_id, data, additional_data);
+				}
+			}
+		}
 	}
 }
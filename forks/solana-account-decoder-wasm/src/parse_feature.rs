@@ -0,0 +1,58 @@
+use bincode::deserialize;
+use serde::Deserialize;
+use serde::Serialize;
+use solana_clock::Slot;
+
+use crate::parse_account_data::ParsableAccount;
+use crate::parse_account_data::ParseAccountError;
+
+/// Parse a feature gate account owned by the feature program. The account
+/// holds the `Option<Slot>` at which the feature was activated, which is
+/// `None` while the activation is pending.
+pub fn parse_feature(data: &[u8]) -> Result<UiFeature, ParseAccountError> {
+	let activated_at: Option<Slot> = deserialize(data)
+		.map_err(|_| ParseAccountError::AccountNotParsable(ParsableAccount::Feature))?;
+
+	Ok(UiFeature {
+		activated: activated_at.is_some(),
+		activated_at,
+	})
+}
+
+/// A feature gate account, for pretty JSON serialization.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UiFeature {
+	pub activated: bool,
+	pub activated_at: Option<Slot>,
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_parse_feature() {
+		let activated = bincode::serialize(&Some(42_u64)).unwrap();
+		assert_eq!(
+			parse_feature(&activated).unwrap(),
+			UiFeature {
+				activated: true,
+				activated_at: Some(42),
+			}
+		);
+
+		// feature accounts are created with space for the activation slot
+		let pending = [0; 9];
+		assert_eq!(
+			parse_feature(&pending).unwrap(),
+			UiFeature {
+				activated: false,
+				activated_at: None,
+			}
+		);
+
+		assert!(parse_feature(&[]).is_err());
+		assert!(parse_feature(&[1, 42]).is_err());
+	}
+}
@@ -3,11 +3,14 @@ use serde::Deserialize;
 use serde::Serialize;
 use solana_clock::Epoch;
 use solana_clock::UnixTimestamp;
+use solana_stake_interface::stake_flags::StakeFlags;
 use solana_stake_interface::state::Authorized;
 use solana_stake_interface::state::Delegation;
 use solana_stake_interface::state::Lockup;
 use solana_stake_interface::state::Meta;
 use solana_stake_interface::state::Stake;
+#[allow(deprecated)]
+use solana_stake_interface::state::StakeState;
 use solana_stake_interface::state::StakeStateV2;
 
 use crate::StringAmount;
@@ -15,20 +18,26 @@ use crate::parse_account_data::ParsableAccount;
 use crate::parse_account_data::ParseAccountError;
 
 pub fn parse_stake(data: &[u8]) -> Result<StakeAccountType, ParseAccountError> {
-	let stake_state: StakeStateV2 = deserialize(data)
-		.map_err(|_| ParseAccountError::AccountNotParsable(ParsableAccount::Stake))?;
+	let (stake_state, has_flags) = match deserialize::<StakeStateV2>(data) {
+		Ok(stake_state) => (stake_state, true),
+		// accounts serialized before `StakeFlags` were added don't have
+		// the trailing flags byte
+		Err(_) => (parse_legacy_stake(data)?, false),
+	};
 	let parsed_account = match stake_state {
 		StakeStateV2::Uninitialized => StakeAccountType::Uninitialized,
 		StakeStateV2::Initialized(meta) => {
 			StakeAccountType::Initialized(UiStakeAccount {
 				meta: meta.into(),
 				stake: None,
+				stake_flags: None,
 			})
 		}
-		StakeStateV2::Stake(meta, stake, _) => {
+		StakeStateV2::Stake(meta, stake, stake_flags) => {
 			StakeAccountType::Delegated(UiStakeAccount {
 				meta: meta.into(),
 				stake: Some(stake.into()),
+				stake_flags: has_flags.then(|| stake_flags.into()),
 			})
 		}
 		StakeStateV2::RewardsPool => StakeAccountType::RewardsPool,
@@ -36,6 +45,19 @@ pub fn parse_stake(data: &[u8]) -> Result<StakeAccountType, ParseAccountError> {
 	Ok(parsed_account)
 }
 
+#[allow(deprecated)]
+fn parse_legacy_stake(data: &[u8]) -> Result<StakeStateV2, ParseAccountError> {
+	let stake_state: StakeState = deserialize(data)
+		.map_err(|_| ParseAccountError::AccountNotParsable(ParsableAccount::Stake))?;
+
+	Ok(match stake_state {
+		StakeState::Uninitialized => StakeStateV2::Uninitialized,
+		StakeState::Initialized(meta) => StakeStateV2::Initialized(meta),
+		StakeState::Stake(meta, stake) => StakeStateV2::Stake(meta, stake, StakeFlags::empty()),
+		StakeState::RewardsPool => StakeStateV2::RewardsPool,
+	})
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase", tag = "type", content = "info")]
 pub enum StakeAccountType {
@@ -50,6 +72,35 @@ pub enum StakeAccountType {
 pub struct UiStakeAccount {
 	pub meta: UiMeta,
 	pub stake: Option<UiStake>,
+	/// The flags of a delegated stake account. This is omitted for accounts
+	/// which were created before the flags were added to the account layout.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub stake_flags: Option<UiStakeFlags>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UiStakeFlags {
+	pub bits: u8,
+	pub must_fully_activate_before_deactivation_is_permitted: bool,
+}
+
+impl From<StakeFlags> for UiStakeFlags {
+	fn from(stake_flags: StakeFlags) -> Self {
+		// the `bits` are private, but serialized as a single byte
+		let bits = bincode::serialize(&stake_flags)
+			.ok()
+			.and_then(|bytes| bytes.first().copied())
+			.unwrap_or_default();
+		#[allow(deprecated)]
+		let must_fully_activate_before_deactivation_is_permitted =
+			stake_flags.contains(StakeFlags::MUST_FULLY_ACTIVATE_BEFORE_DEACTIVATION_IS_PERMITTED);
+
+		Self {
+			bits,
+			must_fully_activate_before_deactivation_is_permitted,
+		}
+	}
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -150,7 +201,6 @@ impl From<Delegation> for UiDelegation {
 #[cfg(test)]
 mod test {
 	use bincode::serialize;
-	use solana_stake_interface::stake_flags::StakeFlags;
 
 	use super::*;
 
@@ -196,6 +246,7 @@ mod test {
 					}
 				},
 				stake: None,
+				stake_flags: None,
 			})
 		);
 
@@ -237,7 +288,11 @@ mod test {
 						warmup_cooldown_rate: 0.25,
 					},
 					credits_observed: 10,
-				})
+				}),
+				stake_flags: Some(UiStakeFlags {
+					bits: 0,
+					must_fully_activate_before_deactivation_is_permitted: false,
+				}),
 			})
 		);
 
@@ -251,4 +306,44 @@ mod test {
 		let bad_data = vec![1, 2, 3, 4];
 		assert!(parse_stake(&bad_data).is_err());
 	}
+
+	#[test]
+	#[allow(deprecated)]
+	fn test_parse_stake_flags() {
+		let meta = Meta {
+			rent_exempt_reserve: 42,
+			authorized: Authorized::auto(&solana_pubkey::new_rand()),
+			lockup: Lockup::default(),
+		};
+		let stake = Stake {
+			delegation: Delegation::new(&solana_pubkey::new_rand(), 20, 2),
+			credits_observed: 10,
+		};
+		let stake_flags = |data: &[u8]| {
+			let Ok(StakeAccountType::Delegated(account)) = parse_stake(data) else {
+				panic!("expected a delegated stake account");
+			};
+			account.stake_flags
+		};
+
+		let stake_state = StakeStateV2::Stake(
+			meta,
+			stake,
+			StakeFlags::MUST_FULLY_ACTIVATE_BEFORE_DEACTIVATION_IS_PERMITTED,
+		);
+		let stake_data = serialize(&stake_state).unwrap();
+		assert_eq!(
+			stake_flags(&stake_data),
+			Some(UiStakeFlags {
+				bits: 1,
+				must_fully_activate_before_deactivation_is_permitted: true,
+			})
+		);
+
+		// the legacy layout has no trailing flags byte
+		let stake_state = StakeState::Stake(meta, stake);
+		let stake_data = serialize(&stake_state).unwrap();
+		assert_eq!(stake_data.len(), 196);
+		assert_eq!(stake_flags(&stake_data), None);
+	}
 }
@@ -78,12 +78,17 @@ pub fn parse_sysvar(data: &[u8], pubkey: &Pubkey) -> Result<SysvarAccountType, P
 				SysvarAccountType::SlotHashes(slot_hashes)
 			})
 		} else if pubkey == &sysvar::slot_history::id() {
-			deserialize::<SlotHistory>(data).ok().map(|slot_history| {
-				SysvarAccountType::SlotHistory(UiSlotHistory {
-					next_slot: slot_history.next_slot,
-					bits: format!("{:?}", SlotHistoryBits(slot_history.bits)),
+			deserialize::<SlotHistory>(data)
+				.ok()
+				// the bits are formatted up to `MAX_ENTRIES`, which would panic
+				// for a malformed history with fewer bits
+				.filter(|slot_history| slot_history.bits.len() == slot_history::MAX_ENTRIES)
+				.map(|slot_history| {
+					SysvarAccountType::SlotHistory(UiSlotHistory {
+						next_slot: slot_history.next_slot,
+						bits: format!("{:?}", SlotHistoryBits(slot_history.bits)),
+					})
 				})
-			})
 		} else if pubkey == &sysvar::stake_history::id() {
 			deserialize::<StakeHistory>(data).ok().map(|stake_history| {
 				let stake_history = stake_history
@@ -374,6 +379,13 @@ mod test {
 			}),
 		);
 
+		let short_slot_history = SlotHistory {
+			bits: BitVec::new_fill(false, 64),
+			next_slot: 1,
+		};
+		let short_slot_history_data = bincode::serialize(&short_slot_history).unwrap();
+		assert!(parse_sysvar(&short_slot_history_data, &sysvar::slot_history::id()).is_err());
+
 		let mut stake_history = StakeHistory::default();
 		let stake_history_entry = StakeHistoryEntry {
 			effective: 10,
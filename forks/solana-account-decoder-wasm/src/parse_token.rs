@@ -11,6 +11,8 @@ pub use solana_account_decoder_client_types_wasm::token::real_number_string_trim
 use solana_program_option::COption;
 use solana_program_pack::Pack;
 use solana_pubkey::Pubkey;
+use spl_associated_token_account_interface::address::get_associated_token_address_with_program_id;
+pub use spl_associated_token_account_interface::program::ID as ASSOCIATED_TOKEN_PROGRAM_ID;
 pub use spl_generic_token::is_known_spl_token_id;
 pub use spl_generic_token::spl_token_ids;
 use spl_token_2022_interface::extension::BaseStateWithExtensions;
@@ -168,6 +170,22 @@ pub fn token_amount_to_ui_amount_v3(
 	}
 }
 
+/// The associated token account address of the `wallet` for the `mint`,
+/// which is owned by the `token_program`.
+pub fn get_associated_token_address(
+	wallet: &Pubkey,
+	mint: &Pubkey,
+	token_program: &Pubkey,
+) -> Pubkey {
+	get_associated_token_address_with_program_id(wallet, mint, token_program)
+}
+
+/// The associated token account address of the `wallet` for a Token-2022
+/// `mint`.
+pub fn get_associated_token_address_2022(wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
+	get_associated_token_address(wallet, mint, &spl_token_2022_interface::id())
+}
+
 pub fn get_token_account_mint(data: &[u8]) -> Option<Pubkey> {
 	Account::valid_account_data(data)
 		.then(|| Pubkey::try_from(data.get(..32)?).ok())
@@ -176,19 +194,39 @@ pub fn get_token_account_mint(data: &[u8]) -> Option<Pubkey> {
 
 #[cfg(test)]
 mod test {
+	use base64::Engine;
+	use base64::prelude::BASE64_STANDARD;
 	use solana_account_decoder_client_types_wasm::token::UiExtension;
 	use spl_token_2022_interface::extension::BaseStateWithExtensionsMut;
 	use spl_token_2022_interface::extension::ExtensionType;
 	use spl_token_2022_interface::extension::StateWithExtensionsMut;
+	use spl_token_2022_interface::extension::confidential_transfer::ConfidentialTransferAccount;
+	use spl_token_2022_interface::extension::confidential_transfer::ConfidentialTransferMint;
+	use spl_token_2022_interface::extension::cpi_guard::CpiGuard;
+	use spl_token_2022_interface::extension::default_account_state::DefaultAccountState;
 	use spl_token_2022_interface::extension::immutable_owner::ImmutableOwner;
 	use spl_token_2022_interface::extension::interest_bearing_mint::InterestBearingConfig;
 	use spl_token_2022_interface::extension::memo_transfer::MemoTransfer;
+	use spl_token_2022_interface::extension::metadata_pointer::MetadataPointer;
 	use spl_token_2022_interface::extension::mint_close_authority::MintCloseAuthority;
+	use spl_token_2022_interface::extension::permanent_delegate::PermanentDelegate;
 	use spl_token_2022_interface::extension::scaled_ui_amount::ScaledUiAmountConfig;
+	use spl_token_2022_interface::extension::transfer_fee::TransferFee;
+	use spl_token_2022_interface::extension::transfer_fee::TransferFeeConfig;
+	use spl_token_2022_interface::extension::transfer_hook::TransferHook;
+	use spl_token_2022_interface::solana_zk_sdk::encryption::pod::elgamal::PodElGamalCiphertext;
+	use spl_token_2022_interface::solana_zk_sdk::encryption::pod::elgamal::PodElGamalPubkey;
+	use spl_token_metadata_interface::state::TokenMetadata;
 
 	use super::*;
+	use crate::parse_token_extension::UiConfidentialTransferMint;
+	use crate::parse_token_extension::UiDefaultAccountState;
 	use crate::parse_token_extension::UiMemoTransfer;
+	use crate::parse_token_extension::UiMetadataPointer;
 	use crate::parse_token_extension::UiMintCloseAuthority;
+	use crate::parse_token_extension::UiPermanentDelegate;
+	use crate::parse_token_extension::UiTokenMetadata;
+	use crate::parse_token_extension::UiTransferHook;
 
 	const INT_SECONDS_PER_YEAR: i64 = 6 * 6 * 24 * 36524;
 
@@ -302,6 +340,32 @@ mod test {
 		);
 	}
 
+	#[test]
+	fn test_get_associated_token_address() {
+		let wallet = Pubkey::new_from_array([1; 32]);
+		let mint = Pubkey::new_from_array([2; 32]);
+
+		for token_program in [spl_token_interface::id(), spl_token_2022_interface::id()] {
+			let (address, _) = Pubkey::find_program_address(
+				&[wallet.as_ref(), token_program.as_ref(), mint.as_ref()],
+				&ASSOCIATED_TOKEN_PROGRAM_ID,
+			);
+			assert_eq!(
+				get_associated_token_address(&wallet, &mint, &token_program),
+				address
+			);
+		}
+
+		assert_eq!(
+			get_associated_token_address_2022(&wallet, &mint),
+			get_associated_token_address(&wallet, &mint, &spl_token_2022_interface::id())
+		);
+		assert_ne!(
+			get_associated_token_address_2022(&wallet, &mint),
+			get_associated_token_address(&wallet, &mint, &spl_token_interface::id())
+		);
+	}
+
 	#[test]
 	fn test_ui_token_amount_real_string() {
 		assert_eq!(&real_number_string(1, 0), "1");
@@ -653,4 +717,476 @@ mod test {
 			}),
 		);
 	}
+
+	#[test]
+	fn test_parse_token_mint_with_default_account_state() {
+		let owner_pubkey = Pubkey::new_from_array([3; 32]);
+		let mint_size =
+			ExtensionType::try_calculate_account_len::<Mint>(&[ExtensionType::DefaultAccountState])
+				.unwrap();
+		let mint_base = Mint {
+			mint_authority: COption::Some(owner_pubkey),
+			supply: 42,
+			decimals: 3,
+			is_initialized: true,
+			freeze_authority: COption::Some(owner_pubkey),
+		};
+
+		for (state, ui_state, json_state) in [
+			(AccountState::Frozen, UiAccountState::Frozen, "frozen"),
+			(
+				AccountState::Initialized,
+				UiAccountState::Initialized,
+				"initialized",
+			),
+		] {
+			let mut mint_data = vec![0; mint_size];
+			let mut mint_state =
+				StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut mint_data).unwrap();
+
+			let default_account_state = mint_state
+				.init_extension::<DefaultAccountState>(true)
+				.unwrap();
+			default_account_state.state = state.into();
+			mint_state.base = mint_base;
+			mint_state.pack_base();
+			mint_state.init_account_type().unwrap();
+
+			let parsed = parse_token_v3(&mint_data, None).unwrap();
+			let TokenAccountType::Mint(ref mint) = parsed else {
+				panic!("expected a mint");
+			};
+
+			assert_eq!(
+				mint.extensions,
+				vec![UiExtension::DefaultAccountState(UiDefaultAccountState {
+					account_state: ui_state,
+				})]
+			);
+			assert_eq!(
+				serde_json::to_value(&parsed).unwrap()["info"]["extensions"],
+				serde_json::json!([{
+					"extension": "defaultAccountState",
+					"state": { "accountState": json_state }
+				}])
+			);
+		}
+	}
+
+	#[test]
+	fn test_parse_token_mint_with_permanent_delegate_and_transfer_hook() {
+		let delegate = Pubkey::new_from_array([6; 32]);
+		let authority = Pubkey::new_from_array([7; 32]);
+		let hook_program = Pubkey::new_from_array([8; 32]);
+		let mint_size = ExtensionType::try_calculate_account_len::<Mint>(&[
+			ExtensionType::PermanentDelegate,
+			ExtensionType::TransferHook,
+		])
+		.unwrap();
+		let mut mint_data = vec![0; mint_size];
+		let mut mint_state =
+			StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut mint_data).unwrap();
+
+		let permanent_delegate = mint_state
+			.init_extension::<PermanentDelegate>(true)
+			.unwrap();
+		permanent_delegate.delegate = Some(delegate).try_into().unwrap();
+		let transfer_hook = mint_state.init_extension::<TransferHook>(true).unwrap();
+		transfer_hook.authority = Some(authority).try_into().unwrap();
+		transfer_hook.program_id = Some(hook_program).try_into().unwrap();
+		mint_state.base = Mint {
+			decimals: 6,
+			is_initialized: true,
+			..Default::default()
+		};
+		mint_state.pack_base();
+		mint_state.init_account_type().unwrap();
+
+		let parsed = parse_token_v3(&mint_data, None).unwrap();
+		let TokenAccountType::Mint(ref mint) = parsed else {
+			panic!("expected a mint");
+		};
+
+		assert_eq!(
+			mint.permanent_delegate(),
+			Some(&UiPermanentDelegate {
+				delegate: Some(delegate.to_string()),
+			})
+		);
+		assert_eq!(
+			mint.transfer_hook(),
+			Some(&UiTransferHook {
+				authority: Some(authority.to_string()),
+				program_id: Some(hook_program.to_string()),
+			})
+		);
+		assert_eq!(
+			serde_json::to_value(&parsed).unwrap()["info"]["extensions"],
+			serde_json::json!([
+				{
+					"extension": "permanentDelegate",
+					"state": { "delegate": delegate.to_string() }
+				},
+				{
+					"extension": "transferHook",
+					"state": {
+						"authority": authority.to_string(),
+						"programId": hook_program.to_string()
+					}
+				}
+			])
+		);
+	}
+
+	#[test]
+	fn test_parse_token_account_with_memo_transfer_and_cpi_guard() {
+		let mint_pubkey = Pubkey::new_from_array([2; 32]);
+		let owner_pubkey = Pubkey::new_from_array([3; 32]);
+		let account_size = ExtensionType::try_calculate_account_len::<Account>(&[
+			ExtensionType::MemoTransfer,
+			ExtensionType::CpiGuard,
+		])
+		.unwrap();
+		let mut account_data = vec![0; account_size];
+		let mut account_state =
+			StateWithExtensionsMut::<Account>::unpack_uninitialized(&mut account_data).unwrap();
+
+		account_state.base = Account {
+			mint: mint_pubkey,
+			owner: owner_pubkey,
+			amount: 42,
+			state: AccountState::Initialized,
+			is_native: COption::None,
+			close_authority: COption::None,
+			delegate: COption::None,
+			delegated_amount: 0,
+		};
+		account_state.pack_base();
+		account_state.init_account_type().unwrap();
+
+		let memo_transfer = account_state.init_extension::<MemoTransfer>(true).unwrap();
+		memo_transfer.require_incoming_transfer_memos = true.into();
+		let cpi_guard = account_state.init_extension::<CpiGuard>(true).unwrap();
+		cpi_guard.lock_cpi = true.into();
+
+		let parsed = parse_token_v3(
+			&account_data,
+			Some(&SplTokenAdditionalDataV2::with_decimals(2)),
+		)
+		.unwrap();
+		let TokenAccountType::Account(ref account) = parsed else {
+			panic!("expected a token account");
+		};
+
+		assert!(account.requires_incoming_transfer_memos());
+		assert!(account.is_cpi_guard_locked());
+		assert_eq!(
+			serde_json::to_value(&parsed).unwrap()["info"]["extensions"],
+			serde_json::json!([
+				{
+					"extension": "memoTransfer",
+					"state": { "requireIncomingTransferMemos": true }
+				},
+				{
+					"extension": "cpiGuard",
+					"state": { "lockCpi": true }
+				}
+			])
+		);
+
+		let mut account_data = vec![0; account_size];
+		let mut account_state =
+			StateWithExtensionsMut::<Account>::unpack_uninitialized(&mut account_data).unwrap();
+
+		account_state.base = Account {
+			mint: mint_pubkey,
+			owner: owner_pubkey,
+			state: AccountState::Initialized,
+			..Account::default()
+		};
+		account_state.pack_base();
+		account_state.init_account_type().unwrap();
+		account_state.init_extension::<MemoTransfer>(true).unwrap();
+		account_state.init_extension::<CpiGuard>(true).unwrap();
+
+		let TokenAccountType::Account(account) = parse_token_v3(
+			&account_data,
+			Some(&SplTokenAdditionalDataV2::with_decimals(2)),
+		)
+		.unwrap() else {
+			panic!("expected a token account");
+		};
+
+		assert!(!account.requires_incoming_transfer_memos());
+		assert!(!account.is_cpi_guard_locked());
+	}
+
+	#[test]
+	fn test_parse_token_mint_with_transfer_fee_config() {
+		let authority = Pubkey::new_from_array([3; 32]);
+		let mint_size =
+			ExtensionType::try_calculate_account_len::<Mint>(&[ExtensionType::TransferFeeConfig])
+				.unwrap();
+		let mut mint_data = vec![0; mint_size];
+		let mut mint_state =
+			StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut mint_data).unwrap();
+
+		let transfer_fee_config = mint_state
+			.init_extension::<TransferFeeConfig>(true)
+			.unwrap();
+		transfer_fee_config.transfer_fee_config_authority = Some(authority).try_into().unwrap();
+		transfer_fee_config.withheld_amount = 17.into();
+		transfer_fee_config.older_transfer_fee = TransferFee {
+			epoch: 10.into(),
+			maximum_fee: 5_000.into(),
+			transfer_fee_basis_points: 50.into(),
+		};
+		transfer_fee_config.newer_transfer_fee = TransferFee {
+			epoch: 20.into(),
+			maximum_fee: 100.into(),
+			transfer_fee_basis_points: 250.into(),
+		};
+		let transfer_fee_config = *transfer_fee_config;
+		mint_state.base = Mint {
+			decimals: 6,
+			is_initialized: true,
+			..Mint::default()
+		};
+		mint_state.pack_base();
+		mint_state.init_account_type().unwrap();
+
+		let parsed = parse_token_v3(&mint_data, None).unwrap();
+		let TokenAccountType::Mint(ref mint) = parsed else {
+			panic!("expected a mint");
+		};
+		let [UiExtension::TransferFeeConfig(ui_config)] = mint.extensions.as_slice() else {
+			panic!("expected the transfer fee config extension");
+		};
+
+		assert_eq!(
+			serde_json::to_value(&parsed).unwrap()["info"]["extensions"],
+			serde_json::json!([{
+				"extension": "transferFeeConfig",
+				"state": {
+					"transferFeeConfigAuthority": authority.to_string(),
+					"withdrawWithheldAuthority": null,
+					"withheldAmount": 17,
+					"olderTransferFee": {
+						"epoch": 10,
+						"maximumFee": 5_000,
+						"transferFeeBasisPoints": 50
+					},
+					"newerTransferFee": {
+						"epoch": 20,
+						"maximumFee": 100,
+						"transferFeeBasisPoints": 250
+					}
+				}
+			}])
+		);
+
+		for epoch in [0, 19, 20, 21] {
+			assert_eq!(
+				ui_config.get_epoch_fee(epoch).epoch,
+				u64::from(transfer_fee_config.get_epoch_fee(epoch).epoch)
+			);
+
+			for amount in [0, 1, 199, 1_000, 10_000, u64::MAX] {
+				assert_eq!(
+					ui_config.calculate_epoch_fee(epoch, amount),
+					transfer_fee_config.calculate_epoch_fee(epoch, amount),
+				);
+			}
+		}
+	}
+
+	#[test]
+	fn test_parse_token_mint_with_metadata() {
+		let mint_pubkey = Pubkey::new_from_array([4; 32]);
+		let authority = Pubkey::new_from_array([5; 32]);
+		let token_metadata = TokenMetadata {
+			update_authority: Some(authority).try_into().unwrap(),
+			mint: mint_pubkey,
+			name: "Token".to_string(),
+			symbol: "TKN".to_string(),
+			uri: "https://example.com/token.json".to_string(),
+			additional_metadata: vec![("key".to_string(), "value".to_string())],
+		};
+		let mint_size =
+			ExtensionType::try_calculate_account_len::<Mint>(&[ExtensionType::MetadataPointer])
+				.unwrap() + token_metadata.tlv_size_of().unwrap();
+		let mut mint_data = vec![0; mint_size];
+		let mut mint_state =
+			StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut mint_data).unwrap();
+
+		let metadata_pointer = mint_state.init_extension::<MetadataPointer>(true).unwrap();
+		metadata_pointer.authority = Some(authority).try_into().unwrap();
+		metadata_pointer.metadata_address = Some(mint_pubkey).try_into().unwrap();
+		mint_state
+			.init_variable_len_extension(&token_metadata, false)
+			.unwrap();
+		mint_state.base = Mint {
+			decimals: 3,
+			is_initialized: true,
+			..Default::default()
+		};
+		mint_state.pack_base();
+		mint_state.init_account_type().unwrap();
+
+		let TokenAccountType::Mint(mint) = parse_token_v3(&mint_data, None).unwrap() else {
+			panic!("expected a mint");
+		};
+		let metadata_pointer = mint.metadata_pointer().unwrap();
+		assert_eq!(
+			metadata_pointer,
+			&UiMetadataPointer {
+				authority: Some(authority.to_string()),
+				metadata_address: Some(mint_pubkey.to_string()),
+			}
+		);
+		assert!(metadata_pointer.is_self_referential(&mint_pubkey.to_string()));
+		assert!(!metadata_pointer.is_self_referential(&authority.to_string()));
+		assert_eq!(
+			mint.token_metadata(),
+			Some(&UiTokenMetadata {
+				update_authority: Some(authority.to_string()),
+				mint: mint_pubkey.to_string(),
+				name: "Token".to_string(),
+				symbol: "TKN".to_string(),
+				uri: "https://example.com/token.json".to_string(),
+				additional_metadata: vec![("key".to_string(), "value".to_string())],
+			})
+		);
+
+		// an invalid length for the name only fails the metadata extension
+		let name_len = mint_data
+			.windows(9)
+			.position(|window| window == b"\x05\0\0\0Token")
+			.unwrap();
+		mint_data[name_len..name_len + 4].copy_from_slice(&[0xff; 4]);
+		let TokenAccountType::Mint(mint) = parse_token_v3(&mint_data, None).unwrap() else {
+			panic!("expected a mint");
+		};
+
+		assert_eq!(mint.decimals, 3);
+		assert!(mint.metadata_pointer().is_some());
+		assert_eq!(mint.token_metadata(), None);
+		assert!(mint.extensions.contains(&UiExtension::UnparseableExtension));
+	}
+
+	#[test]
+	fn test_parse_token_with_confidential_transfer() {
+		let authority = Pubkey::new_from_array([3; 32]);
+		let auditor = PodElGamalPubkey::from([4; 32]);
+		let mint_size = ExtensionType::try_calculate_account_len::<Mint>(&[
+			ExtensionType::ConfidentialTransferMint,
+		])
+		.unwrap();
+		let mut mint_data = vec![0; mint_size];
+		let mut mint_state =
+			StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut mint_data).unwrap();
+
+		let confidential_transfer_mint = mint_state
+			.init_extension::<ConfidentialTransferMint>(true)
+			.unwrap();
+		confidential_transfer_mint.authority = Some(authority).try_into().unwrap();
+		confidential_transfer_mint.auto_approve_new_accounts = true.into();
+		confidential_transfer_mint.auditor_elgamal_pubkey = Some(auditor).try_into().unwrap();
+		mint_state.base = Mint {
+			decimals: 2,
+			is_initialized: true,
+			..Default::default()
+		};
+		mint_state.pack_base();
+		mint_state.init_account_type().unwrap();
+
+		let TokenAccountType::Mint(mint) = parse_token_v3(&mint_data, None).unwrap() else {
+			panic!("expected a mint");
+		};
+
+		assert_eq!(
+			mint.confidential_transfer(),
+			Some(&UiConfidentialTransferMint {
+				authority: Some(authority.to_string()),
+				auto_approve_new_accounts: true,
+				auditor_elgamal_pubkey: Some(BASE64_STANDARD.encode([4; 32])),
+			})
+		);
+
+		let account_size = ExtensionType::try_calculate_account_len::<Account>(&[
+			ExtensionType::ConfidentialTransferAccount,
+		])
+		.unwrap();
+		let mut account_data = vec![0; account_size];
+		let mut account_state =
+			StateWithExtensionsMut::<Account>::unpack_uninitialized(&mut account_data).unwrap();
+
+		account_state.base = Account {
+			mint: Pubkey::new_from_array([2; 32]),
+			owner: Pubkey::new_from_array([5; 32]),
+			state: AccountState::Initialized,
+			..Account::default()
+		};
+		account_state.pack_base();
+		account_state.init_account_type().unwrap();
+
+		let confidential_transfer_account = account_state
+			.init_extension::<ConfidentialTransferAccount>(true)
+			.unwrap();
+		confidential_transfer_account.approved = true.into();
+		confidential_transfer_account.elgamal_pubkey = PodElGamalPubkey::from([6; 32]);
+		confidential_transfer_account.pending_balance_lo = PodElGamalCiphertext::from([7; 64]);
+		confidential_transfer_account.pending_balance_hi = PodElGamalCiphertext::from([8; 64]);
+		confidential_transfer_account.available_balance = PodElGamalCiphertext::from([9; 64]);
+		confidential_transfer_account.pending_balance_credit_counter = 3.into();
+
+		let TokenAccountType::Account(account) = parse_token_v3(
+			&account_data,
+			Some(&SplTokenAdditionalDataV2::with_decimals(2)),
+		)
+		.unwrap() else {
+			panic!("expected a token account");
+		};
+		let confidential_transfer = account.confidential_transfer().unwrap();
+
+		assert!(confidential_transfer.approved);
+		assert_eq!(
+			confidential_transfer.elgamal_pubkey,
+			BASE64_STANDARD.encode([6; 32])
+		);
+		assert_eq!(
+			confidential_transfer.pending_balance_lo,
+			BASE64_STANDARD.encode([7; 64])
+		);
+		assert_eq!(
+			confidential_transfer.pending_balance_hi,
+			BASE64_STANDARD.encode([8; 64])
+		);
+		assert_eq!(
+			confidential_transfer.available_balance,
+			BASE64_STANDARD.encode([9; 64])
+		);
+		assert_eq!(confidential_transfer.pending_balance_credit_counter, 3);
+
+		// a truncated extension only fails the confidential transfer extension
+		let length = Account::LEN + 3;
+		let truncated_len =
+			u16::from_le_bytes([account_data[length], account_data[length + 1]]) - 1;
+		account_data[length..length + 2].copy_from_slice(&truncated_len.to_le_bytes());
+		account_data.pop();
+		let TokenAccountType::Account(account) = parse_token_v3(
+			&account_data,
+			Some(&SplTokenAdditionalDataV2::with_decimals(2)),
+		)
+		.unwrap() else {
+			panic!("expected a token account");
+		};
+
+		assert!(account.confidential_transfer().is_none());
+		assert!(
+			account
+				.extensions
+				.contains(&UiExtension::UnparseableExtension)
+		);
+	}
 }
@@ -0,0 +1,498 @@
+//! Parse the accounts of the Metaplex Token Metadata program.
+//!
+//! The accounts are borsh serialized. Metadata created by older versions of
+//! the program has its `name`, `symbol` and `uri` padded with null bytes to a
+//! fixed length and may end before the optional fields which were added
+//! later. Newer metadata is resized to fit its fields. Both layouts are
+//! supported.
+
+use serde::Deserialize;
+use serde::Serialize;
+use solana_pubkey::Pubkey;
+
+use crate::parse_account_data::ParsableAccount;
+use crate::parse_account_data::ParseAccountError;
+
+solana_pubkey::declare_id!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+/// The account key which identifies a `Metadata` account.
+const METADATA_V1_KEY: u8 = 4;
+/// The serialized size of a `Creator`.
+const CREATOR_LEN: usize = 34;
+
+pub fn parse_token_metadata(data: &[u8]) -> Result<TokenMetadataAccountType, ParseAccountError> {
+	parse_metadata(data)
+		.map(TokenMetadataAccountType::Metadata)
+		.ok_or(ParseAccountError::AccountNotParsable(
+			ParsableAccount::TokenMetadata,
+		))
+}
+
+fn parse_metadata(data: &[u8]) -> Option<UiMetadata> {
+	let mut reader = Reader(data);
+
+	if reader.u8()? != METADATA_V1_KEY {
+		return None;
+	}
+
+	let update_authority = reader.pubkey()?;
+	let mint = reader.pubkey()?;
+	let name = reader.string()?;
+	let symbol = reader.string()?;
+	let uri = reader.string()?;
+	let seller_fee_basis_points = reader.u16()?;
+	let creators = reader.option(|reader| {
+		let len = usize::try_from(reader.u32()?).ok()?;
+
+		if len.checked_mul(CREATOR_LEN)? > reader.0.len() {
+			return None;
+		}
+
+		(0..len)
+			.map(|_| {
+				Some(UiCreator {
+					address: reader.pubkey()?,
+					verified: reader.bool()?,
+					share: reader.u8()?,
+				})
+			})
+			.collect()
+	})?;
+	let primary_sale_happened = reader.bool()?;
+	let is_mutable = reader.bool()?;
+
+	let mut trailing = Trailing(Some(reader));
+	let edition_nonce = trailing.read(Reader::u8);
+	let token_standard = trailing.read(|reader| UiTokenStandard::from_index(reader.u8()?));
+	let collection = trailing.read(|reader| {
+		Some(UiCollection {
+			verified: reader.bool()?,
+			key: reader.pubkey()?,
+		})
+	});
+	let uses = trailing.read(|reader| {
+		Some(UiUses {
+			use_method: UiUseMethod::from_index(reader.u8()?)?,
+			remaining: reader.u64()?,
+			total: reader.u64()?,
+		})
+	});
+	let collection_details = trailing.read(|reader| {
+		match reader.u8()? {
+			0 => {
+				Some(UiCollectionDetails::V1 {
+					size: reader.u64()?,
+				})
+			}
+			1 => reader.take(8).map(|_| UiCollectionDetails::V2),
+			_ => None,
+		}
+	});
+	let programmable_config = trailing.read(|reader| {
+		(reader.u8()? == 0).then_some(())?;
+
+		Some(UiProgrammableConfig {
+			rule_set: reader.option(Reader::pubkey)?,
+		})
+	});
+
+	Some(UiMetadata {
+		update_authority,
+		mint,
+		name,
+		symbol,
+		uri,
+		seller_fee_basis_points,
+		creators,
+		primary_sale_happened,
+		is_mutable,
+		edition_nonce,
+		token_standard,
+		collection,
+		uses,
+		collection_details,
+		programmable_config,
+	})
+}
+
+/// Reads the optional fields which were added in later versions of the
+/// program and are missing from older accounts. Like the program, an invalid
+/// field and every field after it are read as `None`.
+struct Trailing<'a>(Option<Reader<'a>>);
+
+impl<'a> Trailing<'a> {
+	fn read<T>(&mut self, read: impl FnOnce(&mut Reader<'a>) -> Option<T>) -> Option<T> {
+		let reader = self.0.as_mut()?;
+
+		if reader.0.is_empty() {
+			return None;
+		}
+
+		let value = reader.option(read);
+
+		if value.is_none() {
+			self.0 = None;
+		}
+
+		value.flatten()
+	}
+}
+
+/// Reads borsh encoded values without panicking on truncated data.
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+	fn take(&mut self, len: usize) -> Option<&[u8]> {
+		let (value, rest) = self.0.split_at_checked(len)?;
+		self.0 = rest;
+		Some(value)
+	}
+
+	fn array<const N: usize>(&mut self) -> Option<[u8; N]> {
+		self.take(N)?.try_into().ok()
+	}
+
+	fn u8(&mut self) -> Option<u8> {
+		Some(self.array::<1>()?[0])
+	}
+
+	fn u16(&mut self) -> Option<u16> {
+		Some(u16::from_le_bytes(self.array()?))
+	}
+
+	fn u32(&mut self) -> Option<u32> {
+		Some(u32::from_le_bytes(self.array()?))
+	}
+
+	fn u64(&mut self) -> Option<u64> {
+		Some(u64::from_le_bytes(self.array()?))
+	}
+
+	fn bool(&mut self) -> Option<bool> {
+		match self.u8()? {
+			0 => Some(false),
+			1 => Some(true),
+			_ => None,
+		}
+	}
+
+	fn pubkey(&mut self) -> Option<String> {
+		Some(Pubkey::new_from_array(self.array()?).to_string())
+	}
+
+	/// Read a string, without the null bytes which pad legacy metadata.
+	fn string(&mut self) -> Option<String> {
+		let len = usize::try_from(self.u32()?).ok()?;
+		let bytes = self.take(len)?;
+		let string = std::str::from_utf8(bytes).ok()?;
+
+		Some(string.trim_end_matches('\0').to_string())
+	}
+
+	/// Read a borsh `Option`. The outer `None` means the data is invalid.
+	#[allow(clippy::option_option)]
+	fn option<T>(&mut self, read: impl FnOnce(&mut Self) -> Option<T>) -> Option<Option<T>> {
+		match self.u8()? {
+			0 => Some(None),
+			1 => read(self).map(Some),
+			_ => None,
+		}
+	}
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", tag = "type", content = "info")]
+pub enum TokenMetadataAccountType {
+	Metadata(UiMetadata),
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UiMetadata {
+	pub update_authority: String,
+	pub mint: String,
+	pub name: String,
+	pub symbol: String,
+	pub uri: String,
+	pub seller_fee_basis_points: u16,
+	pub creators: Option<Vec<UiCreator>>,
+	pub primary_sale_happened: bool,
+	pub is_mutable: bool,
+	pub edition_nonce: Option<u8>,
+	pub token_standard: Option<UiTokenStandard>,
+	pub collection: Option<UiCollection>,
+	pub uses: Option<UiUses>,
+	pub collection_details: Option<UiCollectionDetails>,
+	pub programmable_config: Option<UiProgrammableConfig>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UiCreator {
+	pub address: String,
+	pub verified: bool,
+	pub share: u8,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum UiTokenStandard {
+	NonFungible,
+	FungibleAsset,
+	Fungible,
+	NonFungibleEdition,
+	ProgrammableNonFungible,
+	ProgrammableNonFungibleEdition,
+}
+
+impl UiTokenStandard {
+	fn from_index(index: u8) -> Option<Self> {
+		let token_standard = match index {
+			0 => Self::NonFungible,
+			1 => Self::FungibleAsset,
+			2 => Self::Fungible,
+			3 => Self::NonFungibleEdition,
+			4 => Self::ProgrammableNonFungible,
+			5 => Self::ProgrammableNonFungibleEdition,
+			_ => return None,
+		};
+
+		Some(token_standard)
+	}
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UiCollection {
+	pub verified: bool,
+	pub key: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UiUses {
+	pub use_method: UiUseMethod,
+	pub remaining: u64,
+	pub total: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum UiUseMethod {
+	Burn,
+	Multiple,
+	Single,
+}
+
+impl UiUseMethod {
+	fn from_index(index: u8) -> Option<Self> {
+		let use_method = match index {
+			0 => Self::Burn,
+			1 => Self::Multiple,
+			2 => Self::Single,
+			_ => return None,
+		};
+
+		Some(use_method)
+	}
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum UiCollectionDetails {
+	V1 { size: u64 },
+	V2,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UiProgrammableConfig {
+	pub rule_set: Option<String>,
+}
+
+#[cfg(test)]
+mod test {
+	use assert_matches::assert_matches;
+
+	use super::*;
+
+	const MAX_METADATA_LEN: usize = 679;
+
+	fn push_string(data: &mut Vec<u8>, string: &str, padded_len: usize) {
+		let mut bytes = string.as_bytes().to_vec();
+		bytes.resize(padded_len.max(bytes.len()), 0);
+		data.extend((bytes.len() as u32).to_le_bytes());
+		data.extend(bytes);
+	}
+
+	/// The fields which every version of the metadata account contains.
+	fn required_fields(update_authority: &Pubkey, mint: &Pubkey, padded: bool) -> Vec<u8> {
+		let (name_len, symbol_len, uri_len) = if padded { (32, 10, 200) } else { (0, 0, 0) };
+		let mut data = vec![METADATA_V1_KEY];
+
+		data.extend(update_authority.to_bytes());
+		data.extend(mint.to_bytes());
+		push_string(&mut data, "Degen Ape", name_len);
+		push_string(&mut data, "DAPE", symbol_len);
+		push_string(&mut data, "https://example.com/1.json", uri_len);
+		data.extend(500_u16.to_le_bytes());
+		// creators
+		data.extend([1, 1, 0, 0, 0]);
+		data.extend(update_authority.to_bytes());
+		data.extend([1, 100]);
+		// primary sale happened and is mutable
+		data.extend([1, 0]);
+
+		data
+	}
+
+	fn expected_metadata(update_authority: &Pubkey, mint: &Pubkey) -> UiMetadata {
+		UiMetadata {
+			update_authority: update_authority.to_string(),
+			mint: mint.to_string(),
+			name: "Degen Ape".to_string(),
+			symbol: "DAPE".to_string(),
+			uri: "https://example.com/1.json".to_string(),
+			seller_fee_basis_points: 500,
+			creators: Some(vec![UiCreator {
+				address: update_authority.to_string(),
+				verified: true,
+				share: 100,
+			}]),
+			primary_sale_happened: true,
+			is_mutable: false,
+			edition_nonce: None,
+			token_standard: None,
+			collection: None,
+			uses: None,
+			collection_details: None,
+			programmable_config: None,
+		}
+	}
+
+	#[test]
+	fn test_parse_legacy_metadata() {
+		let update_authority = Pubkey::new_unique();
+		let mint = Pubkey::new_unique();
+		let data = required_fields(&update_authority, &mint, true);
+		let mut allocated = data.clone();
+		allocated.resize(MAX_METADATA_LEN, 0);
+
+		for data in [data, allocated] {
+			assert_eq!(
+				parse_token_metadata(&data).unwrap(),
+				TokenMetadataAccountType::Metadata(expected_metadata(&update_authority, &mint)),
+			);
+		}
+	}
+
+	#[test]
+	fn test_parse_metadata_with_optional_fields() {
+		let update_authority = Pubkey::new_unique();
+		let mint = Pubkey::new_unique();
+		let collection = Pubkey::new_unique();
+		let rule_set = Pubkey::new_unique();
+		let mut data = required_fields(&update_authority, &mint, false);
+		// edition nonce and token standard
+		data.extend([1, 254, 1, 4]);
+		data.push(1);
+		data.push(1);
+		data.extend(collection.to_bytes());
+		// uses
+		data.extend([1, 1]);
+		data.extend(3_u64.to_le_bytes());
+		data.extend(5_u64.to_le_bytes());
+		// collection details
+		data.extend([1, 0]);
+		data.extend(10_u64.to_le_bytes());
+		// programmable config
+		data.extend([1, 0, 1]);
+		data.extend(rule_set.to_bytes());
+
+		let parsed = parse_token_metadata(&data).unwrap();
+
+		assert_eq!(
+			parsed,
+			TokenMetadataAccountType::Metadata(UiMetadata {
+				edition_nonce: Some(254),
+				token_standard: Some(UiTokenStandard::ProgrammableNonFungible),
+				collection: Some(UiCollection {
+					verified: true,
+					key: collection.to_string(),
+				}),
+				uses: Some(UiUses {
+					use_method: UiUseMethod::Multiple,
+					remaining: 3,
+					total: 5,
+				}),
+				collection_details: Some(UiCollectionDetails::V1 { size: 10 }),
+				programmable_config: Some(UiProgrammableConfig {
+					rule_set: Some(rule_set.to_string()),
+				}),
+				..expected_metadata(&update_authority, &mint)
+			}),
+		);
+		assert_eq!(
+			serde_json::to_value(&parsed).unwrap()["info"]["tokenStandard"],
+			"programmableNonFungible"
+		);
+
+		// truncated data never panics and fails while reading the required
+		// fields
+		let required_len = required_fields(&update_authority, &mint, false).len();
+
+		for len in 0..data.len() {
+			let result = parse_token_metadata(&data[..len]);
+
+			if len < required_len {
+				assert_matches!(
+					result,
+					Err(ParseAccountError::AccountNotParsable(
+						ParsableAccount::TokenMetadata
+					))
+				);
+			} else {
+				assert!(result.is_ok());
+			}
+		}
+	}
+
+	#[test]
+	fn test_parse_metadata_with_invalid_trailing_field() {
+		let update_authority = Pubkey::new_unique();
+		let mint = Pubkey::new_unique();
+		let mut data = required_fields(&update_authority, &mint, true);
+		// edition nonce followed by an unknown token standard and a
+		// collection which is ignored
+		data.extend([1, 7, 1, 9, 1, 1]);
+		data.extend(mint.to_bytes());
+
+		assert_eq!(
+			parse_token_metadata(&data).unwrap(),
+			TokenMetadataAccountType::Metadata(UiMetadata {
+				edition_nonce: Some(7),
+				..expected_metadata(&update_authority, &mint)
+			}),
+		);
+	}
+
+	#[test]
+	fn test_parse_other_token_metadata_accounts() {
+		let mut data = required_fields(&Pubkey::new_unique(), &Pubkey::new_unique(), false);
+		// master edition v2
+		data[0] = 6;
+
+		assert_matches!(
+			parse_token_metadata(&data),
+			Err(ParseAccountError::AccountNotParsable(
+				ParsableAccount::TokenMetadata
+			))
+		);
+		// a creator count which is larger than the account
+		let mut data = required_fields(&Pubkey::new_unique(), &Pubkey::new_unique(), false);
+		let creators_offset = 1 + 32 + 32 + 4 + 9 + 4 + 4 + 4 + 26 + 2 + 1;
+		data[creators_offset..creators_offset + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+
+		assert!(parse_token_metadata(&data).is_err());
+	}
+}
@@ -1,5 +1,6 @@
 //! Core types for solana-transaction-status
 use core::fmt;
+use std::collections::BTreeMap;
 
 use base64::Engine;
 use base64::prelude::BASE64_STANDARD;
@@ -409,6 +410,26 @@ impl From<TransactionStatusMeta> for UiTransactionStatusMeta {
 	}
 }
 
+impl UiTransactionStatusMeta {
+	/// The inner instructions invoked by each outer instruction, keyed by the
+	/// index of the outer instruction. Outer instructions which didn't invoke
+	/// any other programs are omitted.
+	///
+	/// The instructions are in execution order, and the call tree can be
+	/// rebuilt from their [`UiInstruction::stack_height`].
+	pub fn inner_instructions_by_index(&self) -> BTreeMap<u8, Vec<&UiInstruction>> {
+		let mut map = BTreeMap::<u8, Vec<&UiInstruction>>::new();
+
+		for inner_instructions in self.inner_instructions.iter().flatten() {
+			map.entry(inner_instructions.index)
+				.or_default()
+				.extend(&inner_instructions.instructions);
+		}
+
+		map
+	}
+}
+
 #[serde_as]
 #[skip_serializing_none]
 #[derive(Default, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
@@ -527,6 +548,21 @@ pub enum UiInstruction {
 	Parsed(UiParsedInstruction),
 }
 
+impl UiInstruction {
+	/// The invocation depth of the instruction, where outer instructions are
+	/// at `1` and each CPI adds one. This is only returned by nodes running
+	/// v1.14.6 or later.
+	pub fn stack_height(&self) -> Option<u32> {
+		match self {
+			Self::Compiled(instruction) => instruction.stack_height,
+			Self::Parsed(UiParsedInstruction::Parsed(instruction)) => instruction.stack_height,
+			Self::Parsed(UiParsedInstruction::PartiallyDecoded(instruction)) => {
+				instruction.stack_height
+			}
+		}
+	}
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase", untagged)]
 pub enum UiParsedInstruction {
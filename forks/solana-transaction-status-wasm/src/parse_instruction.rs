@@ -1,6 +1,9 @@
 use std::collections::HashMap;
 use std::str::Utf8Error;
 use std::str::from_utf8;
+use std::sync::Arc;
+use std::sync::PoisonError;
+use std::sync::RwLock;
 
 use inflector::Inflector;
 use serde_json::Value;
@@ -55,6 +58,61 @@ static PARSABLE_PROGRAM_IDS: std::sync::LazyLock<HashMap<Pubkey, ParsableProgram
 		.collect()
 	});
 
+/// Parses an instruction, with the account keys of its transaction, into
+/// json. See [`register_parser`].
+pub type InstructionParser = Box<
+	dyn Fn(&CompiledInstruction, &AccountKeys) -> Result<Value, ParseInstructionError>
+		+ Send
+		+ Sync,
+>;
+
+type SharedInstructionParser = Arc<
+	dyn Fn(&CompiledInstruction, &AccountKeys) -> Result<Value, ParseInstructionError>
+		+ Send
+		+ Sync,
+>;
+
+static CUSTOM_PARSERS: std::sync::LazyLock<RwLock<HashMap<Pubkey, SharedInstructionParser>>> =
+	std::sync::LazyLock::new(RwLock::default);
+
+/// Register a `parser` for the instructions of `program_id`, which is used by
+/// [`parse`] for programs which aren't built in. The `program` of the parsed
+/// instructions is the `program_id`.
+///
+/// The built in parsers take precedence, so `false` is returned and the
+/// `parser` is ignored when `program_id` already has a parser. Registering
+/// another parser for the same `program_id` replaces it.
+pub fn register_parser(program_id: Pubkey, parser: InstructionParser) -> bool {
+	if PARSABLE_PROGRAM_IDS.contains_key(&program_id) {
+		return false;
+	}
+
+	CUSTOM_PARSERS
+		.write()
+		.unwrap_or_else(PoisonError::into_inner)
+		.insert(program_id, Arc::from(parser));
+
+	true
+}
+
+/// Remove the parser registered for `program_id` with [`register_parser`].
+/// Returns whether a parser was registered.
+pub fn unregister_parser(program_id: &Pubkey) -> bool {
+	CUSTOM_PARSERS
+		.write()
+		.unwrap_or_else(PoisonError::into_inner)
+		.remove(program_id)
+		.is_some()
+}
+
+fn custom_parser(program_id: &Pubkey) -> Option<SharedInstructionParser> {
+	CUSTOM_PARSERS
+		.read()
+		.unwrap_or_else(PoisonError::into_inner)
+		.get(program_id)
+		.cloned()
+}
+
 #[derive(Error, Debug)]
 pub enum ParseInstructionError {
 	#[error("{0:?} instruction not parsable")]
@@ -99,9 +157,16 @@ pub fn parse(
 	account_keys: &AccountKeys,
 	stack_height: Option<u32>,
 ) -> Result<ParsedInstruction, ParseInstructionError> {
-	let program_name = PARSABLE_PROGRAM_IDS
-		.get(program_id)
-		.ok_or(ParseInstructionError::ProgramNotParsable)?;
+	let Some(program_name) = PARSABLE_PROGRAM_IDS.get(program_id) else {
+		let parser = custom_parser(program_id).ok_or(ParseInstructionError::ProgramNotParsable)?;
+
+		return Ok(ParsedInstruction {
+			program: program_id.to_string(),
+			program_id: *program_id,
+			parsed: parser(instruction, account_keys)?,
+			stack_height,
+		});
+	};
 	let parsed_json = match program_name {
 		ParsableProgram::AddressLookupTable => {
 			serde_json::to_value(parse_address_lookup_table(instruction, account_keys)?)?
@@ -202,6 +267,73 @@ mod test {
 		assert!(parse(&non_parsable_program_id, &memo_instruction, &no_keys, None).is_err());
 	}
 
+	#[test]
+	fn test_register_parser() {
+		let program_id = Pubkey::from([9; 32]);
+		let account = Pubkey::from([10; 32]);
+		let keys = [program_id, account];
+		let account_keys = AccountKeys::new(&keys, None);
+		let instruction = CompiledInstruction {
+			program_id_index: 0,
+			accounts: vec![1],
+			data: vec![7],
+		};
+		assert!(register_parser(
+			program_id,
+			Box::new(|instruction, account_keys| {
+				Ok(json!({
+					"type": "increment",
+					"info": {
+						"counter": account_keys[instruction.accounts[0] as usize].to_string(),
+						"amount": instruction.data[0],
+					},
+				}))
+			}),
+		));
+
+		assert_eq!(
+			parse(&program_id, &instruction, &account_keys, Some(2)).unwrap(),
+			ParsedInstruction {
+				program: program_id.to_string(),
+				program_id,
+				parsed: json!({
+					"type": "increment",
+					"info": { "counter": account.to_string(), "amount": 7 },
+				}),
+				stack_height: Some(2),
+			}
+		);
+
+		assert!(unregister_parser(&program_id));
+		assert!(!unregister_parser(&program_id));
+		assert!(parse(&program_id, &instruction, &account_keys, None).is_err());
+	}
+
+	#[test]
+	fn test_register_parser_does_not_shadow_built_in_parsers() {
+		let registered = register_parser(
+			spl_memo_interface::v1::id(),
+			Box::new(|_, _| Ok(json!("shadowed"))),
+		);
+		assert!(!registered);
+
+		let no_keys = AccountKeys::new(&[], None);
+		let memo_instruction = CompiledInstruction {
+			program_id_index: 0,
+			accounts: vec![],
+			data: b"memo".to_vec(),
+		};
+		let parsed = parse(
+			&spl_memo_interface::v1::id(),
+			&memo_instruction,
+			&no_keys,
+			None,
+		)
+		.unwrap();
+		assert_eq!(parsed.program, "spl-memo");
+		assert_eq!(parsed.parsed, json!("memo"));
+	}
+
 	#[test]
 	fn test_parse_memo() {
 		let good_memo = "good memo".to_string();